@@ -1,22 +1,107 @@
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use chrono::{DateTime, Utc, Duration, TimeZone, NaiveDate};
+use walkdir::WalkDir;
 use serde::{Deserialize, Serialize};
 use colored::*;
-use indicatif::{ProgressBar, ProgressStyle};
 use anyhow::{Result, Context};
 use crate::colors;
-use crate::config::{Config, CleanupAction, ProtectedFolder, ProtectionType};
+use crate::progress;
+use crate::trash_manifest::TrashManifest;
+use crate::history::History;
+use crate::config::{Config, CleanupAction, ConfirmPolicy, ProtectedFolder, ProtectionType};
 
-const COURSE_PATTERNS: &[(&str, &[&str])] = &[
-    ("cs", &["cs", "computer", "programming", "algorithm", "software"]),
-    ("math", &["math", "calculus", "algebra", "statistics", "geometry"]),
-    ("science", &["physics", "chemistry", "biology", "science", "lab"]),
-    ("engineering", &["engineer", "mechanical", "electrical", "civil", "robotics"]),
-    ("business", &["business", "management", "finance", "economics", "marketing"]),
-    ("humanities", &["history", "literature", "philosophy", "art", "psychology"]),
-];
+/// Final manifest written into each dated archive folder
+const MANIFEST_FILE: &str = "archive_info.json";
+/// Append-only log written while files are moved, folded into the manifest at the end
+const MANIFEST_LOG: &str = "archive_info.ndjson";
+/// Zip an archive folder's files are moved into when it's compressed; the
+/// manifest and reminder markers stay beside it
+const COMPRESSED_FILE: &str = "files.zip";
+
+/// Archive root (Archive mode) and the tracking folder used in Recycle Bin mode
+const ARCHIVE_DIR: &str = "CleanCrush-Archive";
+const TEMP_DIR: &str = "CleanCrush-Temp";
+
+/// Course folder a file is archived into: a user override, else the course
+/// with the longest keyword found in the file name (earlier courses break
+/// ties), else "general"
+pub fn detect_course(config: &Config, path: &Path) -> String {
+    detect_course_with_source(config, path).0
+}
+
+/// `detect_course`, plus whether the course came from a user override.
+/// Scanning and archiving both go through here so they always agree.
+pub fn detect_course_with_source(config: &Config, path: &Path) -> (String, bool) {
+    if let Some(course) = config.course_override(path) {
+        return (course.to_string(), true);
+    }
+    
+    let filename = path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_lowercase();
+    
+    // The longest keyword found wins, so "physics" isn't read as "cs"; ties
+    // go to the course listed first
+    let mut best: Option<(&String, usize)> = None;
+    for (course, keywords) in &config.courses {
+        for keyword in keywords {
+            let keyword = keyword.to_lowercase();
+            if keyword_in_name(&filename, &keyword) && best.is_none_or(|(_, len)| keyword.len() > len) {
+                best = Some((course, keyword.len()));
+            }
+        }
+    }
+    
+    match best {
+        Some((course, _)) => (course.clone(), false),
+        None => ("general".to_string(), false),
+    }
+}
+
+/// Keywords this short ("cs", "art") only count as whole words of a name
+const SHORT_KEYWORD_LEN: usize = 3;
+
+/// Whether a course keyword appears in a lowercase file name
+fn keyword_in_name(filename: &str, keyword: &str) -> bool {
+    if keyword.is_empty() {
+        return false;
+    }
+    if keyword.len() > SHORT_KEYWORD_LEN {
+        return filename.contains(keyword);
+    }
+    filename.split(|c: char| !c.is_alphabetic()).any(|word| word == keyword)
+}
+
+/// Where cleaning a file would send it, for previews like "🗑️ → Recycle Bin"
+pub fn destination_label(config: &Config, path: &Path) -> String {
+    match config.default_action {
+        CleanupAction::RecycleBin => "🗑️ → Recycle Bin".to_string(),
+        CleanupAction::Archive => format!("📁 → Archive/{}/", detect_course(config, path)),
+    }
+}
+
+/// Folders CleanCrush keeps archived files in. Scans skip them so archived
+/// files are never suggested (and cleaned) again.
+pub fn archive_roots() -> Vec<PathBuf> {
+    let base = crate::config::base_dir();
+    let mut roots = Vec::new();
+    for root in [base.join(ARCHIVE_DIR), base.join(TEMP_DIR)] {
+        // Scan paths are usually canonical, so match that form too
+        if let Ok(canonical) = root.canonicalize() {
+            if canonical != root {
+                roots.push(canonical);
+            }
+        }
+        roots.push(root);
+    }
+    roots
+}
 
 const CLOUD_FOLDERS: &[&str] = &[
     "onedrive",
@@ -31,6 +116,20 @@ const CLOUD_FOLDERS: &[&str] = &[
 pub struct ArchiveSystem {
     archive_path: PathBuf,
     config: Config,
+    output: OutputLevel,
+    /// Journal each cleanup for `restore-last` (off when the caller records
+    /// several runs as one)
+    record_history: bool,
+}
+
+/// How much a cleanup prints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputLevel {
+    /// Every file, and the full lists of failed and skipped files
+    #[default]
+    Detailed,
+    /// Just the totals (`--summary-only`)
+    Summary,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +140,91 @@ pub struct ArchiveInfo {
     pub files: Vec<ArchivedFileInfo>,
 }
 
+impl ArchiveInfo {
+    /// Load an archive folder's manifest, including entries from a leftover
+    /// log if a previous run was interrupted before consolidating
+    pub fn load(archive_dir: &Path) -> Result<Option<Self>> {
+        let info_path = archive_dir.join(MANIFEST_FILE);
+        let log_path = archive_dir.join(MANIFEST_LOG);
+        
+        let mut info = if info_path.exists() {
+            let data = fs::read_to_string(&info_path)
+                .with_context(|| format!("Failed to read {}", info_path.display()))?;
+            Some(serde_json::from_str::<ArchiveInfo>(&data)
+                .with_context(|| format!("Failed to parse {}", info_path.display()))?)
+        } else {
+            None
+        };
+        
+        if log_path.exists() {
+            let logged = Self::read_log(&log_path)?;
+            if !logged.is_empty() {
+                let info = info.get_or_insert_with(|| ArchiveInfo {
+                    archive_date: logged[0].archived_date,
+                    total_files: 0,
+                    total_size_bytes: 0,
+                    files: Vec::new(),
+                });
+                let mut seen: HashSet<PathBuf> = info.files.iter()
+                    .map(|f| f.archived_path.clone())
+                    .collect();
+                for file in logged {
+                    if !seen.insert(file.archived_path.clone()) {
+                        continue;
+                    }
+                    info.total_files += 1;
+                    info.total_size_bytes += file.size_bytes;
+                    info.files.push(file);
+                }
+            }
+        }
+        
+        Ok(info)
+    }
+    
+    /// Read entries from the append-only log. A torn last line from a crash
+    /// mid-write is ignored.
+    fn read_log(log_path: &Path) -> Result<Vec<ArchivedFileInfo>> {
+        let file = fs::File::open(log_path)
+            .with_context(|| format!("Failed to open {}", log_path.display()))?;
+        
+        let entries = BufReader::new(file)
+            .lines()
+            .map_while(|line| line.ok())
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<ArchivedFileInfo>(&line).ok())
+            .collect();
+        
+        Ok(entries)
+    }
+    
+    /// Atomically replace an archive folder's manifest
+    pub fn write(archive_dir: &Path, info: &ArchiveInfo) -> Result<()> {
+        let info_path = archive_dir.join(MANIFEST_FILE);
+        let temp_path = archive_dir.join(format!("{}.tmp", MANIFEST_FILE));
+        fs::write(&temp_path, serde_json::to_string_pretty(info)?)?;
+        fs::rename(&temp_path, &info_path)?;
+        Ok(())
+    }
+    
+    /// Fold any leftover log into the manifest and remove the log
+    pub fn consolidate(archive_dir: &Path) -> Result<Option<Self>> {
+        let info = match Self::load(archive_dir)? {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+        
+        Self::write(archive_dir, &info)?;
+        
+        let log_path = archive_dir.join(MANIFEST_LOG);
+        if log_path.exists() {
+            fs::remove_file(&log_path)?;
+        }
+        
+        Ok(Some(info))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArchivedFileInfo {
     pub original_path: PathBuf,
@@ -50,6 +234,148 @@ pub struct ArchivedFileInfo {
     pub size_bytes: u64,
     pub archived_date: DateTime<Utc>,
     pub original_modified: DateTime<Utc>,
+    /// Blake3 hash of the contents (missing in manifests from older versions)
+    #[serde(default)]
+    pub hash: Option<String>,
+}
+
+impl ArchivedFileInfo {
+    /// The zip and entry name when the file was compressed into its
+    /// archive's `files.zip` (the manifest then points inside the zip)
+    pub fn compressed_entry(&self) -> Option<(PathBuf, String)> {
+        let zip_path = self.archived_path.ancestors()
+            .find(|path| path.file_name().is_some_and(|name| name == COMPRESSED_FILE))?;
+        let name = self.archived_path.strip_prefix(zip_path).ok()?
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        Some((zip_path.to_path_buf(), name))
+    }
+    
+    /// Whether the archived copy is still there, in the folder or the zip
+    pub fn is_present(&self) -> bool {
+        self.archived_size().is_some()
+    }
+    
+    /// Size of the archived copy as it is now
+    fn archived_size(&self) -> Option<u64> {
+        match self.compressed_entry() {
+            Some((zip_path, name)) => open_zip(&zip_path).ok()?
+                .by_name(&name).ok()
+                .map(|file| file.size()),
+            None => fs::metadata(&self.archived_path).ok().map(|m| m.len()),
+        }
+    }
+    
+    /// Hash of the archived copy as it is now
+    fn archived_hash(&self) -> Option<String> {
+        let Some((zip_path, name)) = self.compressed_entry() else {
+            return crate::scanner::hash_file(&self.archived_path).ok();
+        };
+        
+        let mut zip = open_zip(&zip_path).ok()?;
+        let mut file = zip.by_name(&name).ok()?;
+        let mut hasher = blake3::Hasher::new();
+        std::io::copy(&mut file, &mut hasher).ok()?;
+        Some(hasher.finalize().to_string())
+    }
+}
+
+/// Open an archive's `files.zip` for reading
+fn open_zip(zip_path: &Path) -> Result<zip::ZipArchive<fs::File>> {
+    let file = fs::File::open(zip_path)
+        .with_context(|| format!("Failed to open {}", zip_path.display()))?;
+    zip::ZipArchive::new(file)
+        .with_context(|| format!("Failed to read {}", zip_path.display()))
+}
+
+/// Outcome of compressing an archive folder
+#[derive(Debug, Clone, Default)]
+pub struct CompressResult {
+    pub files: usize,
+    /// Size of the files before they were zipped
+    pub original_bytes: u64,
+    /// Size of the whole `files.zip` afterwards
+    pub zip_bytes: u64,
+}
+
+/// Archived files for one course, summed over every archive
+#[derive(Debug, Clone)]
+pub struct CourseStats {
+    pub course: String,
+    pub files: usize,
+    pub size_bytes: u64,
+}
+
+/// An archived file found by `archive find`
+#[derive(Debug, Clone)]
+pub struct ArchiveMatch {
+    pub archive_dir: PathBuf,
+    pub archive_date: DateTime<Utc>,
+    /// 1-based position in the archive's manifest, as `archive restore` takes it
+    pub index: usize,
+    pub file: ArchivedFileInfo,
+}
+
+/// Outcome of restoring files from an archive
+#[derive(Debug, Clone, Default)]
+pub struct RestoreResult {
+    pub restored: Vec<PathBuf>,
+    /// Restored under a new name because a different file was in the way
+    pub renamed: Vec<PathBuf>,
+    /// Not restored because an identical file was already there (--merge)
+    pub skipped_identical: Vec<PathBuf>,
+    /// Manifest entries whose archived copy is gone
+    pub missing: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+    /// Restored, but the archived copy had changed since it was archived
+    pub changed: Vec<PathBuf>,
+}
+
+/// Outcome of merging archives into one folder
+#[derive(Debug, Clone, Default)]
+pub struct ConsolidateResult {
+    pub target: PathBuf,
+    /// Files moved into the merged folder
+    pub moved: usize,
+    /// Moved under a `_N` name because another archive had the same one
+    pub renamed: Vec<PathBuf>,
+    /// Source folders removed once their files were merged
+    pub removed: Vec<PathBuf>,
+    /// Source folders left in place, and why
+    pub kept: Vec<(PathBuf, String)>,
+    pub failed: Vec<(PathBuf, String)>,
+    /// A source was marked keep forever, so the merged folder is too
+    pub keep_forever: bool,
+}
+
+/// Why `archive tidy` picked an archive folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UntidyReason {
+    /// Nothing left but the manifest and reminder markers
+    Empty,
+    /// Every file the manifest lists is gone, but other files remain
+    Stale { other_files: usize },
+}
+
+/// An archive folder `archive tidy` would clear out
+#[derive(Debug, Clone)]
+pub struct UntidyArchive {
+    pub path: PathBuf,
+    pub reason: UntidyReason,
+    pub size_bytes: u64,
+    /// Marked to keep forever, so left alone
+    pub keep_forever: bool,
+}
+
+/// Space freed by removing a file; a symlink frees nothing (its target stays)
+fn reclaimable_size(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => 0,
+        Ok(metadata) => metadata.len(),
+        Err(_) => 0,
+    }
 }
 
 impl ArchiveSystem {
@@ -57,26 +383,36 @@ impl ArchiveSystem {
     pub fn new(config: Config) -> Result<Self> {
         let archive_path = match &config.default_action {
             CleanupAction::Archive => {
-                let home = dirs::home_dir()
-                    .context("Could not find home directory")?;
-                let archive = home.join("CleanCrush-Archive");
+                let archive = crate::config::base_dir().join(ARCHIVE_DIR);
                 fs::create_dir_all(&archive)?;
                 archive
             }
             CleanupAction::RecycleBin => {
                 // Still create archive path for tracking, but won't be used for actual archiving
-                let home = dirs::home_dir()
-                    .context("Could not find home directory")?;
-                home.join("CleanCrush-Temp")
+                crate::config::base_dir().join(TEMP_DIR)
             }
         };
         
         Ok(Self {
             archive_path,
             config,
+            output: OutputLevel::default(),
+            record_history: true,
         })
     }
     
+    /// Set how much cleanups print
+    pub fn with_output(mut self, output: OutputLevel) -> Self {
+        self.output = output;
+        self
+    }
+    
+    /// Leave cleanups out of the `restore-last` journal; the caller records them
+    pub fn without_history(mut self) -> Self {
+        self.record_history = false;
+        self
+    }
+    
     /// Clean files (either to Recycle Bin or Archive based on config)
     pub fn clean_files(
         &self, 
@@ -90,9 +426,15 @@ impl ArchiveSystem {
             return Ok(CleanupResult::empty());
         }
         
-        println!();
-        println!("{} {}", "🧹 CLEANING FILES".bold().color(colors::HEADER), operation_name.dimmed());
-        println!("{}", "─".repeat(50).color(colors::PATH));
+        if !progress::quiet() {
+            println!();
+            println!("{} {}", "🧹 CLEANING FILES".bold().color(colors::HEADER), operation_name.dimmed());
+            println!("{}", "─".repeat(50).color(colors::PATH));
+        }
+        
+        log::info!("Cleanup '{}': {} files, action {:?}{}", operation_name, files.len(),
+            self.config.default_action,
+            if safe_mode || dry_run { " (preview only)" } else { "" });
         
         if safe_mode {
             println!("{} SAFE MODE: Showing preview only", "🔒".yellow());
@@ -106,10 +448,21 @@ impl ArchiveSystem {
             return self.preview_cleanup(files);
         }
         
-        match &self.config.default_action {
+        let result = match &self.config.default_action {
             CleanupAction::RecycleBin => self.clean_to_recycle_bin(files),
             CleanupAction::Archive => self.clean_to_archive(files),
+        }?;
+        log::info!("Cleanup '{}' done: {} processed ({} bytes), {} missing, {} failed", 
+            operation_name, result.files_processed, result.total_size_bytes,
+            result.missing_files.len(), result.failed_files.len());
+        
+        // Journal it for `restore-last`; the cleanup itself already succeeded
+        if self.record_history {
+            if let Err(e) = History::record(operation_name, self.config.default_action.clone(), &result.successful_files) {
+                println!("{} Couldn't record this cleanup for restore-last: {:#}", "⚠️".yellow(), e);
+            }
         }
+        Ok(result)
     }
     
     /// Preview cleanup without actually doing anything
@@ -119,11 +472,17 @@ impl ArchiveSystem {
         
         for (i, file) in files.iter().enumerate() {
             if !file.exists() {
+                result.missing_files.push(file.clone());
                 continue;
             }
             
-            let size = fs::metadata(file).map(|m| m.len()).unwrap_or(0);
+            let size = reclaimable_size(file);
             total_size += size;
+            result.files_processed += 1;
+            
+            if self.output == OutputLevel::Summary {
+                continue;
+            }
             
             println!("{:3}. {} ({:.1} MB)",
                 i + 1,
@@ -149,8 +508,6 @@ impl ArchiveSystem {
                     }
                 );
             }
-            
-            result.files_processed += 1;
         }
         
         result.total_size_bytes = total_size;
@@ -161,6 +518,9 @@ impl ArchiveSystem {
             result.files_processed,
             total_size as f64 / (1024.0 * 1024.0)
         );
+        if self.output == OutputLevel::Detailed {
+            Self::print_missing_files(&result);
+        }
         
         match &self.config.default_action {
             CleanupAction::RecycleBin => {
@@ -180,18 +540,16 @@ impl ArchiveSystem {
         let mut cloud_warnings = Vec::new();
         let mut locked_files = Vec::new();
         let mut protected_files = Vec::new();
+        let mut trashed = Vec::new();
         
-        let pb = ProgressBar::new(files.len() as u64);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} files {msg}")?
-                .progress_chars("#>-")
-        );
+        let pb = progress::file_bar(files.len() as u64, "{msg}");
         
         for file in files {
             pb.inc(1);
             
             if !file.exists() {
+                result.missing_files.push(file.clone());
+                log::info!("Skipped {}: not found", file.display());
                 pb.set_message("Skipped (not found)");
                 continue;
             }
@@ -200,6 +558,8 @@ impl ArchiveSystem {
             if self.is_in_cloud_folder(file) {
                 cloud_warnings.push(file.display().to_string());
                 if !self.confirm_cloud_deletion(file)? {
+                    result.skipped_files.push((file.clone(), "in a cloud folder".to_string()));
+                    log::info!("Skipped {}: in a cloud folder", file.display());
                     pb.set_message("Skipped (cloud)");
                     continue;
                 }
@@ -208,6 +568,8 @@ impl ArchiveSystem {
             if self.is_file_locked(file) {
                 locked_files.push(file.display().to_string());
                 if !self.handle_locked_file(file)? {
+                    result.skipped_files.push((file.clone(), "open in another program".to_string()));
+                    log::info!("Skipped {}: open in another program", file.display());
                     pb.set_message("Skipped (locked)");
                     continue;
                 }
@@ -216,13 +578,15 @@ impl ArchiveSystem {
             if let Some(protected) = self.config.is_protected(file) {
                 protected_files.push((file.display().to_string(), protected.protection_type.clone()));
                 if !self.confirm_protected_deletion(file, protected)? {
+                    result.skipped_files.push((file.clone(), "in a protected folder".to_string()));
+                    log::info!("Skipped {}: in a protected folder", file.display());
                     pb.set_message("Skipped (protected)");
                     continue;
                 }
             }
             
             // Get file size before deletion
-            let size = fs::metadata(file).map(|m| m.len()).unwrap_or(0);
+            let size = reclaimable_size(file);
             
             // Send to Recycle Bin
             match trash::delete(file) {
@@ -230,10 +594,19 @@ impl ArchiveSystem {
                     result.files_processed += 1;
                     result.total_size_bytes += size;
                     result.successful_files.push(file.clone());
+                    trashed.push((file.clone(), size));
+                    log::info!("Trashed {} ({} bytes)", file.display(), size);
                     pb.set_message("Deleted");
                 }
+                Err(_) if !file.exists() => {
+                    // Vanished between the check above and the delete
+                    result.missing_files.push(file.clone());
+                    log::info!("Skipped {}: not found", file.display());
+                    pb.set_message("Skipped (not found)");
+                }
                 Err(e) => {
                     result.failed_files.push((file.clone(), e.to_string()));
+                    log::warn!("Failed to trash {}: {}", file.display(), e);
                     pb.set_message("Failed");
                 }
             }
@@ -241,6 +614,11 @@ impl ArchiveSystem {
         
         pb.finish_and_clear();
         
+        // The files are already in the trash, so a manifest failure only warns
+        if let Err(e) = TrashManifest::record(&trashed) {
+            println!("{} Couldn't record trashed files for `cleancrush trash list`: {}", "⚠️".yellow(), e);
+        }
+        
         // Print summary
         self.print_cleanup_summary(&result, &cloud_warnings, &locked_files, &protected_files);
         
@@ -256,33 +634,56 @@ impl ArchiveSystem {
         fs::create_dir_all(&archive_dir)?;
         
         let mut result = CleanupResult::empty();
-        let mut archive_info = ArchiveInfo {
-            archive_date,
-            total_files: 0,
-            total_size_bytes: 0,
-            files: Vec::new(),
-        };
         
-        let pb = ProgressBar::new(files.len() as u64);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} files {msg}")?
-                .progress_chars("#>-")
-        );
+        // Record each move as it happens so an interrupted run still leaves
+        // a recoverable manifest behind
+        let log_path = archive_dir.join(MANIFEST_LOG);
+        let mut manifest_log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .with_context(|| format!("Failed to open {}", log_path.display()))?;
         
-        for file in files {
+        // Moves made so far (original, archived), and the error that stopped
+        // the run if it couldn't continue
+        let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+        let mut fatal: Option<(usize, anyhow::Error)> = None;
+        
+        let pb = progress::file_bar(files.len() as u64, "{msg}");
+        
+        for (index, file) in files.iter().enumerate() {
             pb.inc(1);
             
             if !file.exists() {
+                result.missing_files.push(file.clone());
+                log::info!("Skipped {}: not found", file.display());
                 pb.set_message("Skipped (not found)");
                 continue;
             }
             
+            // The manifest is JSON, which needs UTF-8 names; leave such files
+            // where they are rather than archive them with no record
+            if file.to_str().is_none() {
+                result.skipped_files.push((file.clone(), "file name isn't valid UTF-8, so the archive can't record it".to_string()));
+                log::info!("Skipped {}: file name isn't valid UTF-8", file.display());
+                pb.set_message("Skipped (non-UTF-8 name)");
+                continue;
+            }
+            
             // Check for locked files
             if self.is_file_locked(file) {
-                if !self.handle_locked_file(file)? {
-                    pb.set_message("Skipped (locked)");
-                    continue;
+                match self.handle_locked_file(file) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        result.skipped_files.push((file.clone(), "open in another program".to_string()));
+                        log::info!("Skipped {}: open in another program", file.display());
+                        pb.set_message("Skipped (locked)");
+                        continue;
+                    }
+                    Err(e) => {
+                        fatal = Some((index, e));
+                        break;
+                    }
                 }
             }
             
@@ -291,12 +692,13 @@ impl ArchiveSystem {
                 Ok(m) => m,
                 Err(_) => {
                     result.failed_files.push((file.clone(), "Cannot read metadata".to_string()));
+                    log::warn!("Failed to archive {}: cannot read metadata", file.display());
                     pb.set_message("Failed");
                     continue;
                 }
             };
             
-            let size = metadata.len();
+            let size = reclaimable_size(file);
             let modified: DateTime<Utc> = metadata.modified()
                 .unwrap_or_else(|_| SystemTime::now())
                 .into();
@@ -304,44 +706,28 @@ impl ArchiveSystem {
             // Determine course
             let course = self.detect_course(file);
             let course_dir = archive_dir.join(&course);
-            fs::create_dir_all(&course_dir)?;
+            if let Err(e) = fs::create_dir_all(&course_dir) {
+                fatal = Some((index, anyhow::Error::new(e)
+                    .context(format!("Failed to create {}", course_dir.display()))));
+                break;
+            }
             
             // Generate unique filename
-            let filename = file.file_name()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
-            
-            let mut dest_path = course_dir.join(&filename);
-            let mut counter = 1;
-            
-            while dest_path.exists() {
-                let stem = file.file_stem()
-                    .unwrap_or_default()
-                    .to_string_lossy();
-                let extension = file.extension()
-                    .unwrap_or_default()
-                    .to_string_lossy();
-                
-                let new_filename = if extension.is_empty() {
-                    format!("{}_{}", stem, counter)
-                } else {
-                    format!("{}_{}.{}", stem, counter, extension)
-                };
-                
-                dest_path = course_dir.join(new_filename);
-                counter += 1;
-                
-                if counter > 100 {
+            let dest_path = match Self::unique_destination(&course_dir, file) {
+                Some(dest) => dest,
+                None => {
                     result.failed_files.push((file.clone(), "Too many filename conflicts".to_string()));
+                    log::warn!("Failed to archive {}: too many filename conflicts", file.display());
                     pb.set_message("Failed");
                     continue;
                 }
-            }
+            };
             
             // Move file to archive
             match fs::rename(file, &dest_path) {
                 Ok(_) => {
+                    moved.push((file.clone(), dest_path.clone()));
+                    
                     // Create archive info entry
                     let archived_info = ArchivedFileInfo {
                         original_path: file.clone(),
@@ -354,31 +740,56 @@ impl ArchiveSystem {
                         size_bytes: size,
                         archived_date: Utc::now(),
                         original_modified: modified,
+                        hash: crate::scanner::hash_file(&dest_path).ok(),
                     };
                     
-                    archive_info.files.push(archived_info);
-                    archive_info.total_files += 1;
-                    archive_info.total_size_bytes += size;
+                    let logged = serde_json::to_string(&archived_info)
+                        .map_err(std::io::Error::from)
+                        .and_then(|line| writeln!(manifest_log, "{}", line))
+                        .and_then(|_| manifest_log.sync_data());
+                    if let Err(e) = logged {
+                        fatal = Some((index + 1, anyhow::Error::new(e)
+                            .context(format!("Failed to record {} in archive log", file.display()))));
+                        break;
+                    }
                     
                     result.files_processed += 1;
                     result.total_size_bytes += size;
                     result.successful_files.push(file.clone());
+                    log::info!("Archived {} to {} ({} bytes)", file.display(), dest_path.display(), size);
                     pb.set_message("Archived");
                 }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound && !file.exists() => {
+                    result.missing_files.push(file.clone());
+                    log::info!("Skipped {}: not found", file.display());
+                    pb.set_message("Skipped (not found)");
+                }
+                Err(e) if matches!(e.kind(), std::io::ErrorKind::StorageFull | std::io::ErrorKind::ReadOnlyFilesystem) => {
+                    // Every remaining file would fail the same way
+                    fatal = Some((index, anyhow::Error::new(e)
+                        .context(format!("Failed to archive {}", file.display()))));
+                    break;
+                }
                 Err(e) => {
                     result.failed_files.push((file.clone(), e.to_string()));
+                    log::warn!("Failed to archive {}: {}", file.display(), e);
                     pb.set_message("Failed");
                 }
             }
         }
         
         pb.finish_and_clear();
+        drop(manifest_log);
+        
+        if let Some((stopped_at, error)) = fatal {
+            self.recover_partial_archive(&archive_dir, &moved, &files[stopped_at..], &error);
+            return Err(error.context("Archiving stopped partway"));
+        }
         
-        // Save archive info
-        if !archive_info.files.is_empty() {
-            let info_path = archive_dir.join("archive_info.json");
-            let info_data = serde_json::to_string_pretty(&archive_info)?;
-            fs::write(info_path, info_data)?;
+        // Merge the log into archive_info.json (alongside any earlier run today)
+        ArchiveInfo::consolidate(&archive_dir)?;
+        if log_path.exists() {
+            fs::remove_file(&log_path)?;
         }
         
         // Print summary
@@ -390,27 +801,129 @@ impl ArchiveSystem {
         );
         println!("💾 Freed {:.1} MB", result.total_size_bytes as f64 / (1024.0 * 1024.0));
         
-        if !result.failed_files.is_empty() {
-            println!("{} {} files failed:", "⚠️".yellow(), result.failed_files.len());
-            for (file, error) in &result.failed_files {
-                println!("   • {}: {}", file.display(), error);
+        if self.output == OutputLevel::Summary {
+            Self::print_hidden_details(&result);
+        } else {
+            if !result.failed_files.is_empty() {
+                println!("{} {} files failed:", "⚠️".yellow(), result.failed_files.len());
+                for (file, error) in &result.failed_files {
+                    println!("   • {}: {}", file.display(), error);
+                }
+            }
+            
+            Self::print_missing_files(&result);
+            Self::print_skipped_files(&result);
+        }
+        
+        // The files are archived either way, so a failed zip only costs space
+        if self.config.compress_archives && result.files_processed > 0 {
+            match self.compress_archive(&archive_dir) {
+                Ok(compressed) => Self::print_compressed(&compressed),
+                Err(e) => {
+                    log::warn!("Failed to compress {}: {:#}", archive_dir.display(), e);
+                    println!("{} Couldn't compress the archive (files are kept uncompressed): {:#}", "⚠️".yellow(), e);
+                }
             }
         }
         
-        // Create reminder for 30 days from now
-        self.schedule_archive_reminder(&archive_dir)?;
+        // Create reminder for 30 days from now. The files are already safely
+        // archived, so a full or read-only drive must not fail the cleanup.
+        if let Err(e) = self.schedule_archive_reminder(&archive_dir) {
+            result.marker_errors.push(format!("{:#}", e));
+            println!();
+            println!("{} Cleanup succeeded, but the archive reminder couldn't be written:", "⚠️".yellow());
+            println!("   {:#}", e);
+            println!("   {} Check old archives yourself with {}", "💡".cyan(), "cleancrush archive list".bold());
+        }
         
         Ok(result)
     }
     
+    /// After an archive run stopped partway, say exactly where every file is
+    /// and offer to move the already-archived files back
+    fn recover_partial_archive(
+        &self,
+        archive_dir: &Path,
+        moved: &[(PathBuf, PathBuf)],
+        untouched: &[PathBuf],
+        error: &anyhow::Error,
+    ) {
+        println!();
+        println!("{}", "⚠️  ARCHIVING STOPPED PARTWAY".bold().color(colors::WARNING));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        println!("Error: {:#}", error);
+        println!();
+        println!("{} {} file{} already archived:", "📁".cyan(), moved.len(), if moved.len() == 1 { "" } else { "s" });
+        for (original, archived) in moved {
+            println!("   • {} → {}", original.display(), archived.display().to_string().color(colors::PATH));
+        }
+        println!("{} {} file{} not touched (still in place)", "📄".cyan(), untouched.len(), if untouched.len() == 1 { "" } else { "s" });
+        
+        let rollback = !moved.is_empty() && {
+            println!();
+            use dialoguer::{theme::ColorfulTheme, Select};
+            let choices = &[
+                "Roll back - move the archived files back where they were",
+                "Keep the partial archive",
+            ];
+            // Without a terminal to ask, leave things where they are
+            Select::with_theme(&ColorfulTheme::default())
+                .items(choices)
+                .default(0)
+                .interact()
+                .map(|choice| choice == 0)
+                .unwrap_or(false)
+        };
+        
+        let mut rolled_back = Vec::new();
+        if rollback {
+            for (original, archived) in moved {
+                if original.exists() {
+                    println!("{} {} exists again - left in the archive at {}", 
+                        "⚠️".yellow(), original.display(), archived.display());
+                    continue;
+                }
+                let moved_back = original.parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::rename(archived, original));
+                match moved_back {
+                    Ok(()) => rolled_back.push(archived.clone()),
+                    Err(e) => println!("{} Couldn't move back {} (still at {}): {}", 
+                        "❌".red(), original.display(), archived.display(), e),
+                }
+            }
+            println!("{} Moved {} of {} files back", "↩️".green(), rolled_back.len(), moved.len());
+        }
+        
+        // Keep the manifest in step with what's actually in the archive; if
+        // this fails too, the log is still read the next time it's loaded
+        let manifest = ArchiveInfo::consolidate(archive_dir).and_then(|info| match info {
+            Some(mut info) if !rolled_back.is_empty() => {
+                info.files.retain(|f| !rolled_back.contains(&f.archived_path));
+                info.total_files = info.files.len();
+                info.total_size_bytes = info.files.iter().map(|f| f.size_bytes).sum();
+                ArchiveInfo::write(archive_dir, &info)
+            }
+            _ => Ok(()),
+        });
+        if let Err(e) = manifest {
+            println!("{} Couldn't update the archive manifest: {:#}", "⚠️".yellow(), e);
+        }
+        
+        if !rollback && !moved.is_empty() {
+            println!("{} Archived files can be restored with {}", 
+                "💡".cyan(), "cleancrush archive restore latest".bold());
+        }
+    }
+    
     /// Check if file is in cloud folder
-    fn is_in_cloud_folder(&self, path: &Path) -> bool {
+    pub fn is_in_cloud_folder(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy().to_lowercase();
         CLOUD_FOLDERS.iter().any(|folder: &&str| path_str.contains(&folder.to_lowercase()))
     }
     
     /// Check if file is locked
-    fn is_file_locked(&self, path: &Path) -> bool {
+    pub fn is_file_locked(&self, path: &Path) -> bool {
         match fs::OpenOptions::new().read(true).write(true).open(path) {
             Ok(_) => false,
             Err(_) => true,
@@ -495,23 +1008,43 @@ impl ArchiveSystem {
         }
     }
     
-    /// Detect course from filename
-    fn detect_course(&self, path: &Path) -> String {
-        let filename = path.file_name()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_lowercase();
+    /// Pick a free name in `dir` for `file`, adding `_N` before the extension
+    /// on conflicts. Works on the raw OS name so non-UTF-8 names survive.
+    fn unique_destination(dir: &Path, file: &Path) -> Option<PathBuf> {
+        Self::unique_destination_avoiding(dir, file, &HashSet::new())
+    }
+    
+    /// Like `unique_destination`, also treating `taken` paths as occupied
+    /// (names planned by a dry run that don't exist yet)
+    fn unique_destination_avoiding(dir: &Path, file: &Path, taken: &HashSet<PathBuf>) -> Option<PathBuf> {
+        let free = |path: &PathBuf| !path.exists() && !taken.contains(path);
+        let filename = file.file_name().unwrap_or_default();
+        let dest_path = dir.join(filename);
+        if free(&dest_path) {
+            return Some(dest_path);
+        }
         
-        for (course, patterns) in COURSE_PATTERNS {
-            for pattern in *patterns {
-                if filename.contains(pattern) {
-                    let course_str: &str = *course;
-                    return course_str.to_string();
-                }
+        let stem = file.file_stem().unwrap_or_default();
+        for counter in 1..=100 {
+            let mut new_filename = OsString::from(stem);
+            new_filename.push(format!("_{}", counter));
+            if let Some(extension) = file.extension() {
+                new_filename.push(".");
+                new_filename.push(extension);
+            }
+            
+            let dest_path = dir.join(new_filename);
+            if free(&dest_path) {
+                return Some(dest_path);
             }
         }
         
-        "general".to_string()
+        None
+    }
+    
+    /// Detect course from filename
+    fn detect_course(&self, path: &Path) -> String {
+        detect_course(&self.config, path)
     }
     
     /// Print cleanup summary
@@ -522,13 +1055,21 @@ impl ArchiveSystem {
         locked_files: &[String],
         protected_files: &[(String, ProtectionType)],
     ) {
-        println!();
-        println!("{}", "🧹 CLEANUP COMPLETE".bold().color(colors::HEADER));
-        println!("{}", "─".repeat(50).color(colors::PATH));
+        let quiet = progress::quiet();
+        if !quiet {
+            println!();
+            println!("{}", "🧹 CLEANUP COMPLETE".bold().color(colors::HEADER));
+            println!("{}", "─".repeat(50).color(colors::PATH));
+        }
         
         println!("✅ Processed {} files", result.files_processed);
         println!("💾 Freed {:.1} MB", result.total_size_bytes as f64 / (1024.0 * 1024.0));
         
+        if self.output == OutputLevel::Summary {
+            Self::print_hidden_details(result);
+            return;
+        }
+        
         if !result.failed_files.is_empty() {
             println!();
             println!("{} {} files failed:", "⚠️".yellow(), result.failed_files.len());
@@ -537,6 +1078,9 @@ impl ArchiveSystem {
             }
         }
         
+        Self::print_missing_files(result);
+        Self::print_skipped_files(result);
+        
         if !cloud_warnings.is_empty() {
             println!();
             println!("{} {} files from cloud folders:", "☁️".yellow(), cloud_warnings.len());
@@ -565,11 +1109,15 @@ impl ArchiveSystem {
             }
         }
         
+        if quiet {
+            return;
+        }
+        
         match &self.config.default_action {
             CleanupAction::RecycleBin => {
                 println!();
                 println!("{} Files moved to Recycle Bin", "🗑️".green());
-                println!("   You have 30 days to restore them if needed");
+                println!("   You have 30 days to restore them if needed ({})", "cleancrush trash list".bold());
             }
             CleanupAction::Archive => {
                 println!();
@@ -579,86 +1127,179 @@ impl ArchiveSystem {
         }
     }
     
+    /// `--summary-only` stand-in for the failed and skipped lists: the
+    /// failure count, and a pointer to `--verbose` if anything was left out
+    fn print_hidden_details(result: &CleanupResult) {
+        if !result.failed_files.is_empty() {
+            println!("{} {} files failed", "⚠️".yellow(), result.failed_files.len());
+        }
+        if !result.skipped_files.is_empty() {
+            println!("{} Skipped {} files", "⏭️".yellow(), result.skipped_files.len());
+        }
+        
+        let hidden = result.failed_files.len() + result.missing_files.len()
+            + result.skipped_files.len();
+        if hidden > 0 {
+            println!("{} Add {} for the full lists", "💡".cyan(), "--verbose".bold());
+        }
+    }
+    
+    /// Report targets that no longer existed, separately from failures
+    fn print_missing_files(result: &CleanupResult) {
+        if result.missing_files.is_empty() {
+            return;
+        }
+        
+        println!();
+        println!("{} {} target{} no longer existed (already deleted or moved):", 
+            "👻".cyan(),
+            result.missing_files.len(),
+            if result.missing_files.len() == 1 { "" } else { "s" });
+        for file in result.missing_files.iter().take(5) {
+            println!("   • {}", file.display().to_string().dimmed());
+        }
+        if result.missing_files.len() > 5 {
+            println!("   ... and {} more", result.missing_files.len() - 5);
+        }
+        println!("   {} Run a fresh scan if your indices are out of date", "💡".cyan());
+    }
+    
+    /// Report files left in place (declined cloud, locked or protected
+    /// files), so a short count isn't a mystery
+    fn print_skipped_files(result: &CleanupResult) {
+        if result.skipped_files.is_empty() {
+            return;
+        }
+        
+        println!();
+        println!("{} Skipped {} file{}:", 
+            "⏭️".yellow(),
+            result.skipped_files.len(),
+            if result.skipped_files.len() == 1 { "" } else { "s" });
+        for (file, reason) in &result.skipped_files {
+            println!("   • {} ({})", file.display(), reason);
+        }
+    }
+    
     /// Schedule archive reminder for 30 days later
     fn schedule_archive_reminder(&self, archive_dir: &Path) -> Result<()> {
         let reminder_file = archive_dir.join(".reminder_date");
         let reminder_date = Utc::now() + Duration::days(30);
         
-        fs::write(reminder_file, reminder_date.to_rfc3339())?;
+        fs::write(&reminder_file, reminder_date.to_rfc3339())
+            .with_context(|| format!("Failed to write {}", reminder_file.display()))?;
         Ok(())
     }
     
+    /// Archives whose reminder is due: 30+ days old (or past a snooze), and
+    /// not marked to keep forever. Never prompts.
+    pub fn archives_needing_attention(&self) -> Result<Vec<(PathBuf, DateTime<Utc>)>> {
+        let now = Utc::now();
+        let mut due = Vec::new();
+        
+        for (archive_path, archive_date) in self.list_archives()? {
+            if archive_path.join(".keep_forever").exists() {
+                continue;
+            }
+            let remind_at = fs::read_to_string(archive_path.join(".reminder_date")).ok()
+                .and_then(|date| date.trim().parse::<DateTime<Utc>>().ok())
+                .unwrap_or(archive_date + Duration::days(30));
+            if remind_at <= now {
+                due.push((archive_path, archive_date));
+            }
+        }
+        
+        Ok(due)
+    }
+    
     /// Check archive reminders
 pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
-    let archives = self.list_archives()?;
+    let archives = self.archives_needing_attention()?;
     let mut old_archives = Vec::new();
     let now = Utc::now();
     
     for (archive_path, archive_date) in archives {
         let days_old = (now - archive_date).num_days();
         
-        // 30-day reminder
-        if days_old >= 30 {
-            old_archives.push(archive_path.clone());
-            
-            println!();
-            println!("{} ARCHIVE REMINDER", "⏰".bold().color(colors::WARNING));
-            println!("{}", "─".repeat(50).color(colors::PATH));
-            println!("Archive from {} is {} days old.", 
-                archive_date.format("%b %d, %Y").to_string().color(colors::SUCCESS),
-                days_old.to_string().color(colors::WARNING));
-            
-            let archive_size = self.dir_size(&archive_path)?;
-            let size_mb = archive_size as f64 / (1024.0 * 1024.0);
-            println!("Size: {:.1} MB", size_mb);
-            
-            // Show options
-            println!();
-            println!("Options:");
-            println!("  1. Clean (delete archive)");
-            println!("  2. Snooze (remind again in 7 days)");
-            println!("  3. Keep forever");
-            
-            use dialoguer::{theme::ColorfulTheme, Select};
-            let choice = Select::with_theme(&ColorfulTheme::default())
-                .items(&["Clean", "Snooze 7 days", "Keep forever"])
-                .default(0)
-                .interact()?;
-            
-            match choice {
-                0 => {
-                    // Clean archive
-                    println!("Cleaning archive: {}", archive_path.display());
-                    if let Err(e) = fs::remove_dir_all(&archive_path) {
-                        println!("{} Failed to clean: {}", "⚠️".yellow(), e);
-                    } else {
-                        println!("{} Archive cleaned", "✅".green());
-                    }
-                }
-                1 => {
-                    println!("{} Will remind again in 7 days", "⏰".cyan());
-                    // Implement snooze by updating reminder file
-                    let snooze_date = Utc::now() + Duration::days(7);
-                    let reminder_file = archive_path.join(".reminder_date");
-                    fs::write(reminder_file, snooze_date.to_rfc3339())?;
-                }
-                2 => {
-                    println!("{} Archive marked to keep forever", "💾".green());
-                    // Create a .keep_forever file
-                    let keep_file = archive_path.join(".keep_forever");
-                    fs::write(keep_file, "Keep forever - user choice")?;
-                }
-                _ => unreachable!(),
-            }
-        }
-    }
-    
-    Ok(old_archives)
-}
-    
-    /// Clean old archives with confirmation
-    pub fn clean_old_archives(&self, older_than_days: i64, skip_confirmation: bool) -> Result<CleanupResult> {
-        let mut result = CleanupResult::empty();
+        old_archives.push(archive_path.clone());
+        
+        println!();
+        println!("{} ARCHIVE REMINDER", "⏰".bold().color(colors::WARNING));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        println!("Archive from {} is {} days old.", 
+            archive_date.format("%b %d, %Y").to_string().color(colors::SUCCESS),
+            days_old.to_string().color(colors::WARNING));
+        
+        let archive_size = self.dir_size(&archive_path)?;
+        let size_mb = archive_size as f64 / (1024.0 * 1024.0);
+        println!("Size: {:.1} MB", size_mb);
+        
+        // Show options
+        println!();
+        println!("Options:");
+        println!("  1. Clean (delete archive)");
+        println!("  2. Snooze (remind again in 7 days)");
+        println!("  3. Keep forever");
+        
+        use dialoguer::{theme::ColorfulTheme, Select};
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .items(&["Clean", "Snooze 7 days", "Keep forever"])
+            .default(0)
+            .interact()?;
+        
+        match choice {
+            0 => {
+                // Clean archive
+                println!("Cleaning archive: {}", archive_path.display());
+                if let Err(e) = fs::remove_dir_all(&archive_path) {
+                    println!("{} Failed to clean: {}", "⚠️".yellow(), e);
+                } else {
+                    println!("{} Archive cleaned", "✅".green());
+                }
+            }
+            1 => {
+                // Implement snooze by updating reminder file
+                let snooze_date = Utc::now() + Duration::days(7);
+                let reminder_file = archive_path.join(".reminder_date");
+                match fs::write(&reminder_file, snooze_date.to_rfc3339()) {
+                    Ok(()) => println!("{} Will remind again in 7 days", "⏰".cyan()),
+                    Err(e) => println!("{} Couldn't save the snooze ({}): {}", 
+                        "⚠️".yellow(), reminder_file.display(), e),
+                }
+            }
+            2 => {
+                // Create a .keep_forever file
+                let keep_file = archive_path.join(".keep_forever");
+                match fs::write(&keep_file, "Keep forever - user choice") {
+                    Ok(()) => println!("{} Archive marked to keep forever", "💾".green()),
+                    Err(e) => println!("{} Couldn't mark the archive to keep forever ({}): {}", 
+                        "⚠️".yellow(), keep_file.display(), e),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    
+    Ok(old_archives)
+}
+    
+    /// Print what compressing an archive saved
+    fn print_compressed(compressed: &CompressResult) {
+        if compressed.files == 0 {
+            return;
+        }
+        println!("🗜️  Compressed {} file{} into {} ({} → {})",
+            compressed.files,
+            if compressed.files == 1 { "" } else { "s" },
+            COMPRESSED_FILE,
+            crate::cli::format_size(compressed.original_bytes),
+            crate::cli::format_size(compressed.zip_bytes));
+    }
+    
+    /// Clean old archives with confirmation. With `compress`, their files are
+    /// zipped instead of deleted.
+    pub fn clean_old_archives(&self, older_than_days: i64, confirm: ConfirmPolicy, compress: bool) -> Result<CleanupResult> {
+        let mut result = CleanupResult::empty();
         let cutoff_date = Utc::now() - Duration::days(older_than_days);
         
         if !self.archive_path.exists() {
@@ -669,10 +1310,15 @@ pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
         let archives = self.list_archives()?;
         let old_archives: Vec<_> = archives.into_iter()
             .filter(|(_, date)| *date < cutoff_date)
+            .filter(|(path, _)| !compress || Self::has_loose_files(path))
             .collect();
         
         if old_archives.is_empty() {
-            println!("{} No archives older than {} days", "✨".green(), older_than_days);
+            if compress {
+                println!("{} No uncompressed archives older than {} days", "✨".green(), older_than_days);
+            } else {
+                println!("{} No archives older than {} days", "✨".green(), older_than_days);
+            }
             return Ok(result);
         }
         
@@ -684,12 +1330,13 @@ pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
             println!("   • {} ({} days old)", path.display(), days_old);
         }
         
-        let mut should_clean = skip_confirmation;
-        if !skip_confirmation {
+        // The policy counts archive folders, not the files inside them
+        let mut should_clean = true;
+        if confirm.should_confirm(old_archives.len()) {
             use dialoguer::{theme::ColorfulTheme, Confirm};
             should_clean = Confirm::with_theme(&ColorfulTheme::default())
-                .with_prompt("Clean these old archives?")
-                .default(false)
+                .with_prompt(if compress { "Compress these old archives?" } else { "Clean these old archives?" })
+                .default(compress)
                 .interact()?;
         }
         
@@ -698,6 +1345,25 @@ pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
             return Ok(result);
         }
         
+        if compress {
+            for (archive_path, _) in old_archives {
+                match self.compress_archive(&archive_path) {
+                    Ok(compressed) => {
+                        result.files_processed += compressed.files;
+                        result.total_size_bytes += compressed.original_bytes.saturating_sub(compressed.zip_bytes);
+                        result.successful_files.push(archive_path.clone());
+                        println!("{} Compressed: {}", "✅".green(), archive_path.display());
+                        Self::print_compressed(&compressed);
+                    }
+                    Err(e) => {
+                        println!("{} Failed to compress: {} - {:#}", "❌".red(), archive_path.display(), e);
+                        result.failed_files.push((archive_path, format!("{:#}", e)));
+                    }
+                }
+            }
+            return Ok(result);
+        }
+        
         for (archive_path, _) in old_archives {
             match fs::remove_dir_all(&archive_path) {
                 Ok(_) => {
@@ -717,6 +1383,12 @@ pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
         Ok(result)
     }
     
+    /// Whether an archive folder still holds files outside its zip
+    fn has_loose_files(archive_dir: &Path) -> bool {
+        ArchiveInfo::load(archive_dir).ok().flatten()
+            .is_some_and(|info| info.files.iter().any(|f| f.compressed_entry().is_none() && f.archived_path.is_file()))
+    }
+    
     /// List all archives with their dates
     pub fn list_archives(&self) -> Result<Vec<(PathBuf, DateTime<Utc>)>> {
         let mut archives = Vec::new();
@@ -739,6 +1411,9 @@ pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
                     
                     let utc_date = Utc.from_utc_datetime(&datetime);
                     archives.push((path, utc_date));
+                } else if let Ok(Some(info)) = ArchiveInfo::load(&path) {
+                    // Consolidated archives are named by label, so date them by their manifest
+                    archives.push((path, info.archive_date));
                 }
             }
         }
@@ -749,6 +1424,761 @@ pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
         Ok(archives)
     }
     
+    /// Find an archive folder by date (YYYY-MM-DD), consolidated label, or "latest"
+    pub fn find_archive(&self, date: &str) -> Result<PathBuf> {
+        let archives = self.list_archives()?;
+        
+        if date == "latest" {
+            return archives.last()
+                .map(|(path, _)| path.clone())
+                .context("No archives found");
+        }
+        
+        if let Some((path, _)) = archives.iter().find(|(path, _)| path.file_name().is_some_and(|n| n == date)) {
+            return Ok(path.clone());
+        }
+        
+        let date = date.parse::<NaiveDate>()
+            .context(format!("No archive named '{}' (use YYYY-MM-DD, a name from `archive list`, or \"latest\")", date))?;
+        let folder = date.format("%Y-%m-%d").to_string();
+        
+        archives.into_iter()
+            .map(|(path, _)| path)
+            .find(|path| path.file_name().map(|n| n == folder.as_str()).unwrap_or(false))
+            .context(format!("No archive from {}", folder))
+    }
+    
+    /// Restore files from an archive. `indices` are 1-based positions in the
+    /// manifest; with `merge`, files identical to one already at the
+    /// destination are skipped instead of restored under a new name.
+    pub fn restore(
+        &self,
+        archive_dir: &Path,
+        indices: &[usize],
+        output: Option<&Path>,
+        merge: bool,
+        dry_run: bool,
+    ) -> Result<RestoreResult> {
+        // Fold in any leftover log first so restored entries can't reappear from it
+        let info = if dry_run {
+            ArchiveInfo::load(archive_dir)?
+        } else {
+            ArchiveInfo::consolidate(archive_dir)?
+        };
+        let mut info = info
+            .context(format!("No manifest found in {}", archive_dir.display()))?;
+        let mut result = RestoreResult::default();
+        
+        let selected: Vec<usize> = if indices.is_empty() {
+            (0..info.files.len()).collect()
+        } else {
+            indices.iter()
+                .filter_map(|&idx| {
+                    if idx > 0 && idx <= info.files.len() {
+                        Some(idx - 1)
+                    } else {
+                        eprintln!("{} Invalid index: {}", "⚠️".yellow(), idx);
+                        None
+                    }
+                })
+                .collect()
+        };
+        
+        let mut restored_entries = Vec::new();
+        // Entries extracted from each zip, dropped from it once the manifest is updated
+        let mut unzipped: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+        
+        for i in selected {
+            let entry = &info.files[i];
+            
+            if !entry.is_present() {
+                result.missing.push(entry.archived_path.clone());
+                continue;
+            }
+            
+            let wanted = match output {
+                Some(dir) => dir.join(entry.original_path.file_name().unwrap_or_default()),
+                None => entry.original_path.clone(),
+            };
+            let dest_dir = wanted.parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from("."));
+            
+            let dest_path = if wanted.exists() {
+                if merge && Self::same_contents(entry, &wanted) {
+                    result.skipped_identical.push(wanted);
+                    continue;
+                }
+                
+                match Self::unique_destination(&dest_dir, &wanted) {
+                    Some(dest) => dest,
+                    None => {
+                        result.failed.push((wanted, "Too many filename conflicts".to_string()));
+                        continue;
+                    }
+                }
+            } else {
+                wanted.clone()
+            };
+            
+            if Self::changed_since_archived(entry) {
+                result.changed.push(dest_path.clone());
+            }
+            
+            if !dry_run {
+                let compressed = entry.compressed_entry();
+                let moved = fs::create_dir_all(&dest_dir)
+                    .map_err(anyhow::Error::new)
+                    .and_then(|_| match &compressed {
+                        Some((zip_path, name)) => Self::extract_file(zip_path, name, &dest_path, entry.original_modified),
+                        None => Self::move_file(&entry.archived_path, &dest_path).map_err(anyhow::Error::new),
+                    });
+                if let Err(e) = moved {
+                    result.changed.retain(|p| p != &dest_path);
+                    result.failed.push((dest_path, format!("{:#}", e)));
+                    continue;
+                }
+                if let Some((zip_path, name)) = compressed {
+                    unzipped.entry(zip_path).or_default().insert(name);
+                }
+                restored_entries.push(i);
+            }
+            
+            if dest_path != wanted {
+                result.renamed.push(dest_path);
+            } else {
+                result.restored.push(dest_path);
+            }
+        }
+        
+        // Drop restored files from the manifest
+        if !restored_entries.is_empty() {
+            let mut i = 0;
+            info.files.retain(|_| {
+                let keep = !restored_entries.contains(&i);
+                i += 1;
+                keep
+            });
+            info.total_files = info.files.len();
+            info.total_size_bytes = info.files.iter().map(|f| f.size_bytes).sum();
+            ArchiveInfo::write(archive_dir, &info)?;
+        }
+        
+        // The files are already back; a zip that can't be rewritten just keeps a spare copy
+        for (zip_path, names) in unzipped {
+            if let Err(e) = Self::remove_from_zip(&zip_path, &names) {
+                log::warn!("Couldn't drop restored files from {}: {:#}", zip_path.display(), e);
+            }
+        }
+        
+        Ok(result)
+    }
+    
+    /// Restore specific files to where they were archived from, searching the
+    /// newest archives first. Paths found in no manifest are reported as missing.
+    pub fn restore_paths(&self, paths: &[PathBuf]) -> Result<RestoreResult> {
+        let mut result = RestoreResult::default();
+        let mut remaining: Vec<PathBuf> = paths.to_vec();
+        
+        for (archive_dir, _) in self.list_archives()?.into_iter().rev() {
+            if remaining.is_empty() {
+                break;
+            }
+            let Some(info) = ArchiveInfo::load(&archive_dir)? else {
+                continue;
+            };
+            
+            // 1-based, like the indices `archive restore` takes
+            let indices: Vec<usize> = info.files.iter().enumerate()
+                .filter(|(_, entry)| remaining.contains(&entry.original_path))
+                .map(|(i, _)| i + 1)
+                .collect();
+            if indices.is_empty() {
+                continue;
+            }
+            remaining.retain(|path| !info.files.iter().any(|entry| &entry.original_path == path));
+            
+            let restored = self.restore(&archive_dir, &indices, None, false, false)?;
+            result.restored.extend(restored.restored);
+            result.renamed.extend(restored.renamed);
+            result.skipped_identical.extend(restored.skipped_identical);
+            result.missing.extend(restored.missing);
+            result.failed.extend(restored.failed);
+            result.changed.extend(restored.changed);
+        }
+        
+        result.missing.extend(remaining);
+        Ok(result)
+    }
+    
+    /// Merge archive folders into one folder named `label` (e.g. "Fall2024").
+    /// Files keep their course subfolder, clashing names get a `_N` counter,
+    /// and the manifests are combined with the new archived paths. A source
+    /// folder is removed only after the merged manifest records its files, and
+    /// only if nothing else is left in it. `.keep_forever` carries over, as
+    /// does the latest reminder.
+    pub fn consolidate_archives(
+        &self,
+        sources: &[(PathBuf, DateTime<Utc>)],
+        label: &str,
+        dry_run: bool,
+    ) -> Result<ConsolidateResult> {
+        let target_dir = self.archive_path.join(label);
+        let mut result = ConsolidateResult {
+            target: target_dir.clone(),
+            ..Default::default()
+        };
+        
+        let existing = if dry_run {
+            ArchiveInfo::load(&target_dir)?
+        } else {
+            ArchiveInfo::consolidate(&target_dir)?
+        };
+        let mut merged = existing.unwrap_or_else(|| ArchiveInfo {
+            archive_date: DateTime::<Utc>::MIN_UTC,
+            total_files: 0,
+            total_size_bytes: 0,
+            files: Vec::new(),
+        });
+        // Date the merged folder by its newest part, so `archive clean` and
+        // reminders never reach it sooner than they would have reached a source
+        for (_, date) in sources {
+            merged.archive_date = merged.archive_date.max(*date);
+        }
+        
+        let mut reminder: Option<DateTime<Utc>> = None;
+        for dir in sources.iter().map(|(dir, _)| dir).chain([&target_dir]) {
+            result.keep_forever |= dir.join(".keep_forever").exists();
+            let remind_at = fs::read_to_string(dir.join(".reminder_date")).ok()
+                .and_then(|date| date.trim().parse::<DateTime<Utc>>().ok());
+            reminder = reminder.max(remind_at);
+        }
+        
+        let mut taken: HashSet<PathBuf> = merged.files.iter()
+            .map(|f| f.archived_path.clone())
+            .collect();
+        let mut markers_written = false;
+        
+        for (source_dir, _) in sources {
+            let info = if dry_run {
+                ArchiveInfo::load(source_dir)?
+            } else {
+                ArchiveInfo::consolidate(source_dir)?
+            };
+            let Some(mut info) = info else {
+                result.kept.push((source_dir.clone(), "no manifest".to_string()));
+                continue;
+            };
+            if info.files.iter().any(|f| f.compressed_entry().is_some()) {
+                result.kept.push((source_dir.clone(), "compressed - restore its files to merge it".to_string()));
+                continue;
+            }
+            
+            let mut left = Vec::new();
+            let mut moving = HashSet::new();
+            for mut entry in std::mem::take(&mut info.files) {
+                if !entry.archived_path.exists() {
+                    // Carry it over so restore still reports it as missing
+                    merged.files.push(entry);
+                    continue;
+                }
+                
+                // Keep the course subfolder the file was archived into
+                let relative_dir = entry.archived_path.strip_prefix(source_dir).ok()
+                    .and_then(Path::parent)
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(|| PathBuf::from(&entry.course));
+                let dest_dir = target_dir.join(relative_dir);
+                
+                let Some(dest_path) = Self::unique_destination_avoiding(&dest_dir, &entry.archived_path, &taken) else {
+                    result.failed.push((entry.archived_path.clone(), "Too many filename conflicts".to_string()));
+                    left.push(entry);
+                    continue;
+                };
+                
+                if !dry_run {
+                    let moved = fs::create_dir_all(&dest_dir)
+                        .and_then(|_| Self::move_file(&entry.archived_path, &dest_path));
+                    if let Err(e) = moved {
+                        result.failed.push((entry.archived_path.clone(), e.to_string()));
+                        left.push(entry);
+                        continue;
+                    }
+                }
+                
+                if dest_path.file_name() != entry.archived_path.file_name() {
+                    result.renamed.push(dest_path.clone());
+                }
+                result.moved += 1;
+                taken.insert(dest_path.clone());
+                moving.insert(std::mem::replace(&mut entry.archived_path, dest_path));
+                merged.files.push(entry);
+            }
+            
+            if !dry_run {
+                // Record where the files went before touching the source folder
+                merged.total_files = merged.files.len();
+                merged.total_size_bytes = merged.files.iter().map(|f| f.size_bytes).sum();
+                fs::create_dir_all(&target_dir)?;
+                ArchiveInfo::write(&target_dir, &merged)?;
+                
+                if !markers_written {
+                    if result.keep_forever {
+                        fs::write(target_dir.join(".keep_forever"), "Keep forever - user choice")?;
+                    }
+                    if let Some(remind_at) = reminder {
+                        fs::write(target_dir.join(".reminder_date"), remind_at.to_rfc3339())?;
+                    }
+                    markers_written = true;
+                }
+            }
+            
+            if !left.is_empty() {
+                if !dry_run {
+                    info.total_files = left.len();
+                    info.total_size_bytes = left.iter().map(|f| f.size_bytes).sum();
+                    info.files = left;
+                    ArchiveInfo::write(source_dir, &info)?;
+                }
+                result.kept.push((source_dir.clone(), "some files couldn't be moved".to_string()));
+                continue;
+            }
+            
+            let stray = Self::stray_files(source_dir, &moving);
+            if stray > 0 {
+                if !dry_run {
+                    info.total_files = 0;
+                    info.total_size_bytes = 0;
+                    ArchiveInfo::write(source_dir, &info)?;
+                }
+                result.kept.push((source_dir.clone(), format!("{} files not in its manifest", stray)));
+                continue;
+            }
+            
+            if !dry_run {
+                if let Err(e) = fs::remove_dir_all(source_dir) {
+                    result.kept.push((source_dir.clone(), e.to_string()));
+                    continue;
+                }
+            }
+            result.removed.push(source_dir.clone());
+        }
+        
+        Ok(result)
+    }
+    
+    /// Archive folders with nothing left in them, or whose manifest only
+    /// lists files that are gone
+    pub fn find_untidy_archives(&self) -> Result<Vec<UntidyArchive>> {
+        let mut untidy = Vec::new();
+        
+        for (archive_dir, _) in self.list_archives()? {
+            let files = ArchiveInfo::load(&archive_dir)?
+                .map(|info| info.files)
+                .unwrap_or_default();
+            let other_files = Self::stray_files(&archive_dir, &HashSet::new());
+            
+            let reason = if other_files == 0 {
+                UntidyReason::Empty
+            } else if !files.is_empty() && files.iter().all(|f| !f.is_present()) {
+                UntidyReason::Stale { other_files }
+            } else {
+                continue;
+            };
+            
+            untidy.push(UntidyArchive {
+                size_bytes: self.dir_size(&archive_dir)?,
+                keep_forever: archive_dir.join(".keep_forever").exists(),
+                path: archive_dir,
+                reason,
+            });
+        }
+        
+        Ok(untidy)
+    }
+    
+    /// Print what `archive tidy` found
+    pub fn print_untidy(&self, untidy: &[UntidyArchive]) {
+        println!();
+        println!("{}", "🧽 ARCHIVE TIDY".bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        
+        if untidy.is_empty() {
+            println!("{} Every archive folder still holds its files ✨", "✨".green());
+            return;
+        }
+        
+        for archive in untidy {
+            let what = match archive.reason {
+                UntidyReason::Empty => "empty - only the manifest and markers left".to_string(),
+                UntidyReason::Stale { other_files } => format!(
+                    "every listed file is gone, {} other file{} left ({})",
+                    other_files,
+                    if other_files == 1 { "" } else { "s" },
+                    crate::cli::format_size(archive.size_bytes)),
+            };
+            println!("• {} ({})", archive.path.display().to_string().color(colors::PATH), what);
+            if archive.keep_forever {
+                println!("     {} Marked to keep forever - left alone", "💾".green());
+            }
+        }
+    }
+    
+    /// Count files in an archive folder other than its manifest, markers and
+    /// the `moved` files
+    fn stray_files(archive_dir: &Path, moved: &HashSet<PathBuf>) -> usize {
+        const BOOKKEEPING: &[&str] = &[MANIFEST_FILE, MANIFEST_LOG, ".reminder_date", ".keep_forever"];
+        
+        WalkDir::new(archive_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| !e.file_type().is_dir())
+            .filter(|e| !moved.contains(e.path()))
+            .filter(|e| !BOOKKEEPING.iter().any(|name| e.file_name() == *name))
+            .count()
+    }
+    
+    /// Print what consolidating did (or would do)
+    pub fn print_consolidate_summary(&self, result: &ConsolidateResult, dry_run: bool) {
+        println!();
+        println!("{}", if dry_run { "📦 CONSOLIDATE PREVIEW" } else { "📦 CONSOLIDATE COMPLETE" }
+            .bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        
+        let verb = if dry_run { "Would move" } else { "Moved" };
+        println!("✅ {} {} files into {}",
+            verb, result.moved, result.target.display().to_string().color(colors::PATH));
+        
+        if !result.renamed.is_empty() {
+            println!("{} {} renamed because another archive had the same name:",
+                "✏️".yellow(), result.renamed.len());
+            for path in &result.renamed {
+                println!("   • {}", path.display().to_string().color(colors::PATH));
+            }
+        }
+        
+        if !result.removed.is_empty() {
+            println!("{} {} {} old archive folders",
+                "🧹".cyan(), if dry_run { "Would remove" } else { "Removed" }, result.removed.len());
+        }
+        
+        if !result.kept.is_empty() {
+            println!("{} {} archive folders kept:", "📁".yellow(), result.kept.len());
+            for (path, reason) in &result.kept {
+                println!("   • {} ({})", path.display(), reason);
+            }
+        }
+        
+        if result.keep_forever {
+            println!("{} Marked to keep forever (a merged archive was)", "💾".green());
+        }
+        
+        if !result.failed.is_empty() {
+            println!("{} {} files failed:", "⚠️".yellow(), result.failed.len());
+            for (path, error) in &result.failed {
+                println!("   • {}: {}", path.display(), error);
+            }
+        }
+    }
+    
+    /// Whether the archived copy was edited after it was archived (size, or
+    /// contents when the manifest has a hash)
+    fn changed_since_archived(entry: &ArchivedFileInfo) -> bool {
+        match entry.archived_size() {
+            Some(size) if size != entry.size_bytes => true,
+            Some(_) => entry.hash.as_ref().is_some_and(|hash| {
+                entry.archived_hash().is_some_and(|current| &current != hash)
+            }),
+            None => false,
+        }
+    }
+    
+    /// Whether an archived file matches the one already at `existing`
+    fn same_contents(entry: &ArchivedFileInfo, existing: &Path) -> bool {
+        let size_matches = fs::metadata(existing)
+            .map(|m| m.len() == entry.size_bytes)
+            .unwrap_or(false);
+        if !size_matches {
+            return false;
+        }
+        
+        let archived_hash = match &entry.hash {
+            Some(hash) => Some(hash.clone()),
+            None => entry.archived_hash(),
+        };
+        
+        match (archived_hash, crate::scanner::hash_file(existing).ok()) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+    
+    /// Move a file, falling back to copy + delete across filesystems
+    fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+        if fs::rename(from, to).is_ok() {
+            return Ok(());
+        }
+        
+        fs::copy(from, to)?;
+        fs::remove_file(from)
+    }
+    
+    /// Write a file out of an archive's zip to `to`, with the modified time
+    /// it had before it was archived. A partly written file is removed.
+    fn extract_file(zip_path: &Path, name: &str, to: &Path, modified: DateTime<Utc>) -> Result<()> {
+        let mut zip = open_zip(zip_path)?;
+        let mut file = zip.by_name(name)
+            .with_context(|| format!("{} isn't in {}", name, zip_path.display()))?;
+        
+        let extracted = fs::File::create(to).and_then(|mut out| {
+            std::io::copy(&mut file, &mut out)?;
+            out.set_modified(modified.into())
+        });
+        if let Err(e) = extracted {
+            let _ = fs::remove_file(to);
+            return Err(anyhow::Error::new(e).context(format!("Failed to extract {}", name)));
+        }
+        Ok(())
+    }
+    
+    /// Rebuild a zip without the `names` entries, removing it once nothing is left
+    fn remove_from_zip(zip_path: &Path, names: &HashSet<String>) -> Result<()> {
+        let mut zip = open_zip(zip_path)?;
+        let temp_path = zip_path.with_extension("zip.tmp");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&temp_path)
+            .with_context(|| format!("Failed to create {}", temp_path.display()))?);
+        
+        let mut kept = 0;
+        for i in 0..zip.len() {
+            let file = zip.by_index_raw(i)?;
+            if names.contains(file.name()) {
+                continue;
+            }
+            writer.raw_copy_file(file)?;
+            kept += 1;
+        }
+        writer.finish()?;
+        
+        if kept == 0 {
+            fs::remove_file(&temp_path)?;
+            fs::remove_file(zip_path)?;
+        } else {
+            fs::rename(&temp_path, zip_path)?;
+        }
+        Ok(())
+    }
+    
+    /// Move an archive folder's files into its `files.zip` and point the
+    /// manifest inside the zip. The zip is rebuilt beside the old one and
+    /// swapped in, and files are deleted only once the manifest records
+    /// where they went. Files the manifest doesn't list are left as they are.
+    pub fn compress_archive(&self, archive_dir: &Path) -> Result<CompressResult> {
+        let mut info = ArchiveInfo::consolidate(archive_dir)?
+            .context(format!("No manifest found in {}", archive_dir.display()))?;
+        let zip_path = archive_dir.join(COMPRESSED_FILE);
+        let mut result = CompressResult::default();
+        
+        let loose: Vec<usize> = info.files.iter().enumerate()
+            .filter(|(_, file)| file.compressed_entry().is_none() && file.archived_path.is_file())
+            .map(|(i, _)| i)
+            .collect();
+        
+        if !loose.is_empty() {
+            let temp_path = zip_path.with_extension("zip.tmp");
+            let zipped = match Self::write_zip(&zip_path, &temp_path, archive_dir, &info, &loose) {
+                Ok(zipped) => zipped,
+                Err(e) => {
+                    let _ = fs::remove_file(&temp_path);
+                    return Err(e);
+                }
+            };
+            fs::rename(&temp_path, &zip_path)
+                .with_context(|| format!("Failed to replace {}", zip_path.display()))?;
+            
+            let mut originals = Vec::new();
+            for (i, zipped_path) in zipped {
+                let entry = &mut info.files[i];
+                originals.push(std::mem::replace(&mut entry.archived_path, zipped_path));
+                result.files += 1;
+                result.original_bytes += entry.size_bytes;
+            }
+            ArchiveInfo::write(archive_dir, &info)?;
+            
+            for original in &originals {
+                if let Err(e) = fs::remove_file(original) {
+                    log::warn!("Zipped {} but couldn't remove it: {}", original.display(), e);
+                }
+            }
+            // Course folders the move emptied
+            for entry in fs::read_dir(archive_dir)?.filter_map(|e| e.ok()) {
+                if entry.path().is_dir() {
+                    let _ = fs::remove_dir(entry.path());
+                }
+            }
+        }
+        
+        result.zip_bytes = fs::metadata(&zip_path).map(|m| m.len()).unwrap_or(0);
+        Ok(result)
+    }
+    
+    /// Write `temp_path` holding everything already in `zip_path` plus the
+    /// `loose` manifest entries, returning each one's path inside the zip
+    fn write_zip(
+        zip_path: &Path,
+        temp_path: &Path,
+        archive_dir: &Path,
+        info: &ArchiveInfo,
+        loose: &[usize],
+    ) -> Result<Vec<(usize, PathBuf)>> {
+        let mut writer = zip::ZipWriter::new(fs::File::create(temp_path)
+            .with_context(|| format!("Failed to create {}", temp_path.display()))?);
+        let mut taken = HashSet::new();
+        
+        // Carry over what an earlier compression already zipped
+        if zip_path.exists() {
+            let mut existing = open_zip(zip_path)?;
+            for i in 0..existing.len() {
+                let file = existing.by_index_raw(i)?;
+                taken.insert(zip_path.join(file.name()));
+                writer.raw_copy_file(file)?;
+            }
+        }
+        
+        let mut zipped = Vec::new();
+        for &i in loose {
+            let entry = &info.files[i];
+            
+            // Keep the course subfolder, renaming if the zip already holds the name
+            let relative_dir = entry.archived_path.strip_prefix(archive_dir).ok()
+                .and_then(Path::parent)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from(&entry.course));
+            let dest_path = Self::unique_destination_avoiding(&zip_path.join(relative_dir), &entry.archived_path, &taken)
+                .context(format!("Too many filename conflicts for {}", entry.archived_path.display()))?;
+            let name = dest_path.strip_prefix(zip_path)?
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .large_file(entry.size_bytes >= u32::MAX as u64);
+            writer.start_file(name, options)?;
+            let mut file = fs::File::open(&entry.archived_path)
+                .with_context(|| format!("Failed to open {}", entry.archived_path.display()))?;
+            std::io::copy(&mut file, &mut writer)
+                .with_context(|| format!("Failed to compress {}", entry.archived_path.display()))?;
+            
+            taken.insert(dest_path.clone());
+            zipped.push((i, dest_path));
+        }
+        
+        writer.finish()?.sync_all()?;
+        Ok(zipped)
+    }
+    
+    /// List the files recorded in an archive's manifest
+    pub fn show_archive_files(&self, archive_dir: &Path) -> Result<()> {
+        let info = ArchiveInfo::load(archive_dir)?
+            .context(format!("No manifest found in {}", archive_dir.display()))?;
+        
+        println!();
+        println!("{} {}", "📁".cyan(), archive_dir.display().to_string().bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        
+        if info.files.is_empty() {
+            println!("No files left in this archive");
+            return Ok(());
+        }
+        
+        for (i, file) in info.files.iter().enumerate() {
+            let status = if file.is_present() { "".normal() } else { " (missing)".dimmed() };
+            println!("{:3}. {} ({:.1} MB, {}){}",
+                i + 1,
+                file.original_path.display().to_string().color(colors::PATH),
+                file.size_bytes as f64 / (1024.0 * 1024.0),
+                file.course.color(colors::HEADER),
+                status);
+        }
+        
+        Ok(())
+    }
+    
+    /// Let the user check which files in an archive to restore, returning
+    /// 1-based indices into its manifest (empty if nothing was picked)
+    pub fn pick_files(&self, archive_dir: &Path) -> Result<Vec<usize>> {
+        let info = ArchiveInfo::load(archive_dir)?
+            .context(format!("No manifest found in {}", archive_dir.display()))?;
+        if info.files.is_empty() {
+            println!("No files left in this archive");
+            return Ok(Vec::new());
+        }
+        
+        let labels: Vec<String> = info.files.iter()
+            .map(|file| format!("{} ({:.1} MB, {}){}",
+                file.original_path.display(),
+                file.size_bytes as f64 / (1024.0 * 1024.0),
+                file.course,
+                if file.is_present() { "" } else { " (missing)" }))
+            .collect();
+        
+        use dialoguer::{theme::ColorfulTheme, MultiSelect};
+        let picked = MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Files to restore (space to select, enter to continue)")
+            .items(&labels)
+            .interact()
+            .context("Failed to get selection")?;
+        
+        Ok(picked.into_iter().map(|i| i + 1).collect())
+    }
+    
+    /// Print a restore summary
+    pub fn print_restore_summary(&self, result: &RestoreResult, dry_run: bool) {
+        println!();
+        println!("{}", if dry_run { "📦 RESTORE PREVIEW" } else { "📦 RESTORE COMPLETE" }
+            .bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        
+        let verb = if dry_run { "Would restore" } else { "Restored" };
+        println!("✅ {} {} files", verb, result.restored.len() + result.renamed.len());
+        
+        if !result.renamed.is_empty() {
+            println!("{} {} renamed because a different file was in the way:", 
+                "✏️".yellow(), result.renamed.len());
+            for path in &result.renamed {
+                println!("   • {}", path.display().to_string().color(colors::PATH));
+            }
+        }
+        
+        if !result.skipped_identical.is_empty() {
+            println!("{} {} skipped (identical file already there)", 
+                "🔁".cyan(), result.skipped_identical.len());
+        }
+        
+        if !result.changed.is_empty() {
+            println!("{} {} changed in the archive since they were archived ({} as they are now):", 
+                "📝".yellow(), result.changed.len(), verb.to_lowercase());
+            for path in &result.changed {
+                println!("   • {}", path.display().to_string().color(colors::PATH));
+            }
+        }
+        
+        if !result.missing.is_empty() {
+            println!("{} {} no longer in the archive:", "👻".cyan(), result.missing.len());
+            for path in result.missing.iter().take(5) {
+                println!("   • {}", path.display().to_string().dimmed());
+            }
+        }
+        
+        if !result.failed.is_empty() {
+            println!("{} {} files failed:", "⚠️".yellow(), result.failed.len());
+            for (path, error) in &result.failed {
+                println!("   • {}: {}", path.display(), error);
+            }
+        }
+    }
+    
     /// Show archive statistics
     pub fn show_stats(&self) -> Result<()> {
         let archives = self.list_archives()?;
@@ -779,6 +2209,23 @@ pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
         
         println!("💾 Total size: {:.1} MB", total_size as f64 / (1024.0 * 1024.0));
         
+        // Which subjects generate the most clutter
+        let courses = self.course_stats(&archives);
+        if !courses.is_empty() {
+            let archived: u64 = courses.iter().map(|c| c.size_bytes).sum();
+            println!();
+            println!("{}", "📚 BY COURSE".bold().color(colors::HEADER));
+            for course in &courses {
+                let share = (course.size_bytes * 100).checked_div(archived).unwrap_or(0);
+                println!("   {} {:>4} file{} {:>10} ({}%)", 
+                    format!("{:<14}", course.course).color(colors::HEADER),
+                    course.files,
+                    if course.files == 1 { " " } else { "s" },
+                    crate::cli::format_size(course.size_bytes),
+                    share);
+            }
+        }
+        
         // Show archives that need cleaning (older than 30 days)
         let cutoff_date = Utc::now() - Duration::days(30);
         let old_archives: Vec<_> = archives.into_iter()
@@ -811,6 +2258,64 @@ pub fn check_archive_reminders(&self) -> Result<Vec<PathBuf>> {
         Ok(())
     }
     
+    /// Archived files and space per course across every archive, from their
+    /// manifests; biggest first
+    pub fn course_stats(&self, archives: &[(PathBuf, DateTime<Utc>)]) -> Vec<CourseStats> {
+        let mut courses: Vec<CourseStats> = Vec::new();
+        
+        for (path, _) in archives {
+            let info = match ArchiveInfo::load(path) {
+                Ok(Some(info)) => info,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("Skipping manifest in {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            for file in info.files {
+                match courses.iter_mut().find(|c| c.course == file.course) {
+                    Some(stats) => {
+                        stats.files += 1;
+                        stats.size_bytes += file.size_bytes;
+                    }
+                    None => courses.push(CourseStats {
+                        course: file.course,
+                        files: 1,
+                        size_bytes: file.size_bytes,
+                    }),
+                }
+            }
+        }
+        
+        courses.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.course.cmp(&b.course)));
+        courses
+    }
+    
+    /// Files in any archive whose original path contains `text`
+    /// (case-insensitive), oldest archive first
+    pub fn find_files(&self, text: &str) -> Result<Vec<ArchiveMatch>> {
+        let needle = text.to_lowercase();
+        let mut matches = Vec::new();
+        
+        for (archive_dir, archive_date) in self.list_archives()? {
+            let Some(info) = ArchiveInfo::load(&archive_dir)? else {
+                continue;
+            };
+            for (i, file) in info.files.into_iter().enumerate() {
+                if file.original_path.to_string_lossy().to_lowercase().contains(&needle) {
+                    matches.push(ArchiveMatch {
+                        archive_dir: archive_dir.clone(),
+                        archive_date,
+                        index: i + 1,
+                        file,
+                    });
+                }
+            }
+        }
+        
+        Ok(matches)
+    }
+    
     /// Calculate directory size recursively
     pub fn dir_size(&self, path: &Path) -> Result<u64> {
         let mut total = 0u64;
@@ -838,15 +2343,212 @@ pub struct CleanupResult {
     pub total_size_bytes: u64,
     pub successful_files: Vec<PathBuf>,
     pub failed_files: Vec<(PathBuf, String)>,
+    /// Targets that were already gone (deleted or moved since the scan)
+    pub missing_files: Vec<PathBuf>,
+    /// Files left in place on purpose, with the reason (cloud, locked, protected)
+    pub skipped_files: Vec<(PathBuf, String)>,
+    /// Reminder/marker files that couldn't be written; the cleanup itself succeeded
+    pub marker_errors: Vec<String>,
 }
 
 impl CleanupResult {
-    fn empty() -> Self {
+    pub fn empty() -> Self {
         Self {
             files_processed: 0,
             total_size_bytes: 0,
             successful_files: Vec::new(),
             failed_files: Vec::new(),
+            missing_files: Vec::new(),
+            skipped_files: Vec::new(),
+            marker_errors: Vec::new(),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_home;
+    
+    /// Archive system writing under the isolated test home
+    fn archiver() -> ArchiveSystem {
+        let mut config = Config::defaults();
+        config.default_action = CleanupAction::Archive;
+        ArchiveSystem::new(config).unwrap()
+    }
+    
+    /// Files with the given names and contents in a fresh folder under `home`
+    fn source_files(home: &Path, folder: &str, files: &[(&str, &str)]) -> Vec<PathBuf> {
+        let dir = home.join(folder);
+        fs::create_dir_all(&dir).unwrap();
+        files.iter()
+            .map(|(name, contents)| {
+                let path = dir.join(name);
+                fs::write(&path, contents).unwrap();
+                path
+            })
+            .collect()
+    }
+    
+    #[test]
+    fn an_interrupted_archive_can_still_be_restored() {
+        let (_guard, home) = isolated_home();
+        let archiver = archiver();
+        let files = source_files(&home, "interrupted", &[
+            ("lecture1.pdf", "first"),
+            ("lecture2.pdf", "second"),
+        ]);
+        
+        let result = archiver.clean_files(&files, false, false, "test").unwrap();
+        assert_eq!(result.files_processed, 2);
+        let archive_dir = archiver.find_archive("latest").unwrap();
+        
+        // Put the run back to how a crash before consolidating leaves it:
+        // only the log, ending in a half-written line
+        let info = ArchiveInfo::load(&archive_dir).unwrap().unwrap();
+        let mut log = String::new();
+        for entry in info.files.iter().filter(|entry| files.contains(&entry.original_path)) {
+            log.push_str(&serde_json::to_string(entry).unwrap());
+            log.push('\n');
+        }
+        log.push_str("{\"original_path\": \"/torn");
+        let others: Vec<_> = info.files.iter().filter(|entry| !files.contains(&entry.original_path)).cloned().collect();
+        fs::remove_file(archive_dir.join(MANIFEST_FILE)).unwrap();
+        if !others.is_empty() {
+            ArchiveInfo::write(&archive_dir, &ArchiveInfo { files: others, ..info }).unwrap();
+        }
+        fs::write(archive_dir.join(MANIFEST_LOG), log).unwrap();
+        
+        let recovered = ArchiveInfo::load(&archive_dir).unwrap().unwrap();
+        for file in &files {
+            assert!(recovered.files.iter().any(|entry| &entry.original_path == file), "{}", file.display());
+        }
+        
+        let restored = archiver.restore_paths(&files).unwrap();
+        assert_eq!(restored.restored.len(), 2);
+        assert!(restored.missing.is_empty());
+        assert_eq!(fs::read_to_string(&files[0]).unwrap(), "first");
+        assert!(!archive_dir.join(MANIFEST_LOG).exists());
+    }
+    
+    #[test]
+    fn logged_entries_are_merged_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = |name: &str| ArchivedFileInfo {
+            original_path: PathBuf::from("/home/me/Downloads").join(name),
+            archived_path: dir.path().join("general").join(name),
+            course: "general".to_string(),
+            file_type: "pdf".to_string(),
+            size_bytes: 10,
+            archived_date: Utc::now(),
+            original_modified: Utc::now(),
+            hash: None,
+        };
+        ArchiveInfo::write(dir.path(), &ArchiveInfo {
+            archive_date: Utc::now(),
+            total_files: 1,
+            total_size_bytes: 10,
+            files: vec![entry("a.pdf")],
+        }).unwrap();
+        // A retried run logs the same files again
+        let log: String = ["a.pdf", "b.pdf", "b.pdf"].iter()
+            .map(|name| serde_json::to_string(&entry(name)).unwrap() + "\n")
+            .collect();
+        fs::write(dir.path().join(MANIFEST_LOG), log).unwrap();
+        
+        let info = ArchiveInfo::load(dir.path()).unwrap().unwrap();
+        assert_eq!(info.files.len(), 2);
+        assert_eq!(info.total_files, 2);
+        assert_eq!(info.total_size_bytes, 20);
+    }
+    
+    #[test]
+    fn consolidated_archives_restore_by_original_path() {
+        let (_guard, home) = isolated_home();
+        let archiver = archiver();
+        
+        // Two date folders, each holding a different notes.pdf
+        let mut originals = Vec::new();
+        for (folder, date) in [("week1", "2024-01-10"), ("week2", "2024-01-11")] {
+            let files = source_files(&home, folder, &[("calculus notes.pdf", folder)]);
+            archiver.clean_files(&files, false, false, "test").unwrap();
+            
+            // Move today's run to the date it stands in for, as if made then
+            let today = archiver.find_archive("latest").unwrap();
+            let dated = archiver.archive_path.join(date);
+            fs::rename(&today, &dated).unwrap();
+            let mut info = ArchiveInfo::load(&dated).unwrap().unwrap();
+            for entry in &mut info.files {
+                entry.archived_path = dated.join(entry.archived_path.strip_prefix(&today).unwrap());
+            }
+            ArchiveInfo::write(&dated, &info).unwrap();
+            originals.extend(files);
         }
+        let sources: Vec<_> = archiver.list_archives().unwrap().into_iter()
+            .filter(|(path, _)| path.ends_with("2024-01-10") || path.ends_with("2024-01-11"))
+            .collect();
+        
+        let result = archiver.consolidate_archives(&sources, "Winter2024", false).unwrap();
+        assert_eq!(result.moved, 2);
+        assert_eq!(result.renamed.len(), 1);
+        assert_eq!(result.removed.len(), 2);
+        
+        let restored = archiver.restore_paths(&originals).unwrap();
+        assert_eq!(restored.restored.len(), 2);
+        assert!(restored.missing.is_empty());
+        assert_eq!(fs::read_to_string(&originals[0]).unwrap(), "week1");
+        assert_eq!(fs::read_to_string(&originals[1]).unwrap(), "week2");
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_names_are_skipped_with_a_reason() {
+        use std::os::unix::ffi::OsStrExt;
+        
+        let (_guard, home) = isolated_home();
+        let dir = home.join("legacy");
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join(std::ffi::OsStr::from_bytes(b"caf\xe9 notes.pdf"));
+        fs::write(&file, "latin-1 name").unwrap();
+        
+        let result = archiver().clean_files(std::slice::from_ref(&file), false, false, "test").unwrap();
+        
+        assert_eq!(result.files_processed, 0);
+        assert_eq!(result.skipped_files.len(), 1);
+        assert_eq!(result.skipped_files[0].0, file);
+        assert!(result.skipped_files[0].1.contains("UTF-8"));
+        assert!(file.exists());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn longer_keywords_beat_short_ones() {
+        let config = Config::defaults();
+        for (name, course) in [
+            ("physics_notes.pdf", "science"),
+            ("genetics-lab.pdf", "science"),
+            ("statistics hw.pdf", "math"),
+            ("economics.pptx", "business"),
+            ("CS101 slides.pdf", "cs"),
+            ("docs.txt", "general"),
+        ] {
+            assert_eq!(detect_course(&config, Path::new(name)), course, "{}", name);
+        }
+    }
+    
+    #[test]
+    fn scanning_and_archiving_agree_on_the_course() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::defaults();
+        for name in ["mechanics_week3.pdf", "data structures.pdf", "history essay.docx"] {
+            fs::write(dir.path().join(name), "some notes").unwrap();
+        }
+        
+        let result = crate::scanner::Scanner::new(config.clone(), false)
+            .scan(dir.path(), 30, 100)
+            .unwrap();
+        
+        assert_eq!(result.files.len(), 3);
+        for file in &result.files {
+            assert_eq!(file.course, detect_course(&config, &file.path), "{}", file.path.display());
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use colored::*;
+use anyhow::{Result, Context};
+use rand::seq::SliceRandom;
+use crate::colors;
+use crate::config::Config;
+use crate::scanner::FileInfo;
+
+const CALIBRATION_FILE: &str = ".cleancrush_calibration.json";
+
+/// Lower edges of the confidence bands files are sampled from
+pub const BAND_EDGES: [f32; 4] = [0.0, 0.4, 0.6, 0.8];
+
+/// Cutoffs tried when looking for the one that best matches the answers
+const CUTOFFS: [f32; 6] = [0.4, 0.5, 0.6, 0.7, 0.8, 0.9];
+
+/// Only the most recent answers are kept
+const MAX_ANSWERS: usize = 500;
+
+/// One calibration answer: how confident the scan was, and whether the user
+/// would actually clean the file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Answer {
+    pub confidence: f32,
+    pub would_clean: bool,
+}
+
+/// Calibration answers from every run. Only confidences and yes/no answers
+/// are kept - no file names or paths.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalibrationStats {
+    pub sessions: u32,
+    /// Oldest first
+    pub answers: Vec<Answer>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl CalibrationStats {
+    /// Get the path to the stats file
+    pub fn path() -> Result<PathBuf> {
+        Config::state_path(CALIBRATION_FILE)
+    }
+
+    /// Load the stats, or start fresh
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save the stats (written to a temp file first so it's never half-written)
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let tmp_path = path.with_extension("json.tmp");
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(&tmp_path, data)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Add one session's answers
+    pub fn record(&mut self, answers: &[Answer]) {
+        self.sessions += 1;
+        self.answers.extend_from_slice(answers);
+        let excess = self.answers.len().saturating_sub(MAX_ANSWERS);
+        self.answers.drain(..excess);
+        self.updated_at = Some(Utc::now());
+    }
+}
+
+/// Index of the band a confidence falls in
+fn band(confidence: f32) -> usize {
+    BAND_EDGES.iter().rposition(|&edge| confidence >= edge).unwrap_or(0)
+}
+
+/// Label like "0.4-0.6"
+fn band_label(index: usize) -> String {
+    let upper = BAND_EDGES.get(index + 1).copied().unwrap_or(1.0);
+    format!("{:.1}-{:.1}", BAND_EDGES[index], upper)
+}
+
+/// Pick up to `count` files spread across the confidence bands, shuffled so
+/// the order doesn't give the bands away
+pub fn sample(files: &[FileInfo], count: usize) -> Vec<&FileInfo> {
+    let mut bands: Vec<Vec<&FileInfo>> = vec![Vec::new(); BAND_EDGES.len()];
+    for file in files {
+        bands[band(file.confidence)].push(file);
+    }
+
+    let mut rng = rand::thread_rng();
+    for files in &mut bands {
+        files.shuffle(&mut rng);
+    }
+
+    // Round-robin so sparse bands don't leave the sample short
+    let mut picked = Vec::new();
+    let mut round = 0;
+    while picked.len() < count && bands.iter().any(|b| b.len() > round) {
+        for files in &bands {
+            if picked.len() < count {
+                if let Some(file) = files.get(round) {
+                    picked.push(*file);
+                }
+            }
+        }
+        round += 1;
+    }
+
+    picked.shuffle(&mut rng);
+    picked
+}
+
+/// Cutoff that best separates "yes" from "no" answers, with the share of
+/// answers it gets right. Ties go to the higher (safer) cutoff.
+pub fn best_cutoff(answers: &[Answer]) -> Option<(f32, f32)> {
+    if answers.is_empty() {
+        return None;
+    }
+
+    CUTOFFS.iter()
+        .map(|&cutoff| {
+            let agreed = answers.iter()
+                .filter(|a| a.would_clean == (a.confidence >= cutoff))
+                .count();
+            (cutoff, agreed as f32 / answers.len() as f32)
+        })
+        .fold(None, |best: Option<(f32, f32)>, (cutoff, accuracy)| match best {
+            Some((_, best_accuracy)) if best_accuracy > accuracy => best,
+            _ => Some((cutoff, accuracy)),
+        })
+}
+
+/// Print how well confidence predicted the answers, and a threshold to try
+pub fn print_report(answers: &[Answer], preselect_confidence: f32) {
+    let Some((cutoff, accuracy)) = best_cutoff(answers) else {
+        println!("{} No answers yet", "ℹ️".cyan());
+        return;
+    };
+
+    for (index, _) in BAND_EDGES.iter().enumerate().rev() {
+        let in_band: Vec<&Answer> = answers.iter()
+            .filter(|a| band(a.confidence) == index)
+            .collect();
+        if in_band.is_empty() {
+            continue;
+        }
+        let yes = in_band.iter().filter(|a| a.would_clean).count();
+        println!("  {} {}: {} of {} would clean ({:.0}%)",
+            "•".cyan(),
+            band_label(index).color(colors::HEADER),
+            yes,
+            in_band.len(),
+            yes as f32 * 100.0 / in_band.len() as f32);
+    }
+    println!();
+
+    let yes: Vec<&Answer> = answers.iter().filter(|a| a.would_clean).collect();
+    let no: Vec<&Answer> = answers.iter().filter(|a| !a.would_clean).collect();
+    if !yes.is_empty() {
+        let above = yes.iter().filter(|a| a.confidence >= cutoff).count();
+        println!("{} {:.0}% of your 'yes' answers were files at {:.1}+ confidence",
+            "✅".green(), above as f32 * 100.0 / yes.len() as f32, cutoff);
+    }
+    if !no.is_empty() {
+        let below = no.iter().filter(|a| a.confidence < cutoff).count();
+        println!("{} {:.0}% of your 'no' answers were files below {:.1}",
+            "🙅".cyan(), below as f32 * 100.0 / no.len() as f32, cutoff);
+    }
+    println!("{} A cutoff of {:.1} matches {:.0}% of your answers",
+        "🎯".cyan(), cutoff, accuracy * 100.0);
+
+    // Cutoffs are 0.1 apart, so anything closer than half a step already fits
+    if (cutoff - preselect_confidence).abs() > 0.05 {
+        let judgement = if cutoff > preselect_confidence {
+            "CleanCrush rates some files higher than you would"
+        } else {
+            "CleanCrush is more cautious than you are"
+        };
+        println!("{} {} - consider {} in ~/.cleancrush.json (now {:.2})",
+            "💡".cyan(),
+            judgement,
+            format!("\"preselect_confidence\": {:.1}", cutoff).bold(),
+            preselect_confidence);
+    } else {
+        println!("{} Your {} of {:.2} already fits your answers",
+            "💖".color(colors::HIGH_CONFIDENCE), "preselect_confidence".bold(), preselect_confidence);
+    }
+}
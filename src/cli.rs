@@ -1,5 +1,5 @@
 #[allow(unused_imports)]
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 use clap::{Parser, Subcommand, Args, ValueEnum};
 use std::path::PathBuf;
@@ -33,9 +33,29 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub verbose: bool,
     
+    /// Only print results and errors (no encouragement, banners or progress)
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+    
     /// Disable colored output
     #[arg(long, global = true)]
     pub no_color: bool,
+    
+    /// Don't measure Downloads growth for cleanup reminders
+    #[arg(long, global = true)]
+    pub no_scan: bool,
+    
+    /// Progress display: full bar, compact spinner, or none (for logs)
+    #[arg(long, global = true, value_enum, default_value = "bar")]
+    pub progress: ProgressMode,
+    
+    /// Diagnostic log level (default: off, or info with --log-file)
+    #[arg(long, global = true, value_enum)]
+    pub log_level: Option<LogLevel>,
+    
+    /// Append the diagnostic log to this file instead of stderr
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
 
     /// Show detailed help for specific command
     #[arg(long, short = 'H', global = true)]
@@ -68,34 +88,72 @@ pub enum Commands {
     #[command(subcommand)]
     Archive(ArchiveArgs),
     
+    /// List or restore files sent to the Recycle Bin/Trash
+    #[command(subcommand)]
+    Trash(TrashArgs),
+    
+    /// Put back everything the most recent cleanup removed; repeat to go further back
+    #[command(alias = "undo")]
+    RestoreLast(RestoreLastArgs),
+    
+    /// Never suggest a file again (name like resume.pdf, path, or glob)
+    IgnoreFile(IgnoreFileArgs),
+    
+    /// Correct the detected course for files
+    #[command(subcommand)]
+    Course(CourseArgs),
+    
+    /// Clear out old installers and downloaded archives
+    SweepInstallers(SweepInstallersArgs),
+    
+    /// Remove browser re-downloads like "notes (1).pdf", keeping the original
+    DedupeDownloads(DedupeDownloadsArgs),
+    
+    /// Clear Jupyter checkpoints and Python bytecode caches
+    SweepCache(SweepCacheArgs),
+    
     /// Manage schedule and reminders
     #[command(subcommand)]
     Schedule(ScheduleArgs),
     
     /// Show statistics and achievements
-    Stats,
+    Stats(StatsArgs),
+    
+    /// Everything at a glance: exam mode, reminders, archives, streak, score
+    Status,
     
     /// Calculate folder cleanliness score
     Score(ScoreArgs),
     
-    /// Show configuration
-    Config,
+    /// Show the single cleanup with the biggest payoff
+    Top(TopArgs),
+    
+    /// Show or change configuration
+    Config(ConfigArgs),
+    
+    /// Run first-time setup (or write default settings without prompting)
+    Init(InitArgs),
     
     /// Show achievements and progress
     Achievements,
 
+    /// Check configuration and progress data for problems
+    Doctor(DoctorArgs),
+    
+    /// Check how well confidence scores match what you'd actually clean
+    Calibrate(CalibrateArgs),
+
     /// Show help and examples
     ShowHelp,
     
     /// Show version information
-    Version,
+    Version(VersionArgs),
 }
 
 #[derive(Args, Debug)]
 pub struct ScanArgs {
-    /// Path to scan (default: Downloads folder)
-    #[arg(default_value = ".")]
-    pub path: PathBuf,
+    /// Path to scan (default: your configured cluttered folders)
+    pub path: Option<PathBuf>,
     
     /// Consider files older than N days as "old"
     #[arg(short = 'D', long, default_value_t = 60)]
@@ -110,8 +168,76 @@ pub struct ScanArgs {
     pub detailed: bool,
     
     /// Maximum files to scan
-    #[arg(long, default_value_t = 5000)]
+    #[arg(long, default_value_t = 5000, value_parser = parse_limit)]
     pub limit: usize,
+    
+    /// How many folder levels to look into (0 = unlimited; deeper scans reach --limit sooner)
+    #[arg(long, default_value_t = 3)]
+    pub depth: usize,
+    
+    /// Only include files of at least this many MB
+    #[arg(long, value_name = "MB", value_parser = parse_size_mb)]
+    pub min_size: Option<f64>,
+    
+    /// Only include files of at most this many MB
+    #[arg(long, value_name = "MB", value_parser = parse_size_mb)]
+    pub max_size: Option<f64>,
+    
+    /// Only scan these extensions (e.g. png,jpg,jpeg)
+    #[arg(long, value_delimiter = ',')]
+    pub ext: Vec<String>,
+    
+    /// Skip these extensions (e.g. png,jpg)
+    #[arg(long, value_delimiter = ',')]
+    pub exclude_ext: Vec<String>,
+    
+    /// Skip files and folders matching this name or glob (repeatable, e.g. node_modules or "*.tmp")
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+    
+    /// Stop early as if the file limit was reached (for testing)
+    #[arg(long)]
+    pub pretend_full: bool,
+    
+    /// Also write a Markdown report of the scan to this file
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+    
+    /// Save the scan so suggest/clean/delete can reuse it with --from
+    #[arg(long, value_name = "FILE")]
+    pub save: Option<PathBuf>,
+    
+    /// Also show how the suggestions spread across age buckets
+    #[arg(long)]
+    pub histogram: bool,
+    
+    /// Print the scan as JSON instead of the summary (implies --quiet)
+    #[arg(long, conflicts_with_all = ["detailed", "stream", "histogram"])]
+    pub json: bool,
+    
+    /// With --json: write the JSON to this file instead of stdout
+    #[arg(long, value_name = "FILE", requires = "json")]
+    pub out: Option<PathBuf>,
+    
+    /// Also look inside .zip files (names and sizes only, nothing extracted)
+    #[arg(long)]
+    pub inspect_archives: bool,
+    
+    /// Print high-confidence suggestions as they're found (discovery order, not ranked)
+    #[arg(long, conflicts_with = "detailed")]
+    pub stream: bool,
+    
+    /// With --stream: also clean each high-confidence suggestion as it's found
+    #[arg(long, requires = "stream")]
+    pub stream_clean: bool,
+    
+    /// With --stream-clean: don't ask before starting
+    #[arg(short = 'y', long, requires = "stream_clean")]
+    pub yes: bool,
+    
+    /// Allow the home folder, the filesystem root or a top-level folder
+    #[arg(long = "i-know-what-im-doing")]
+    pub i_know_what_im_doing: bool,
 }
 
 #[derive(Args, Debug)]
@@ -128,9 +254,37 @@ pub struct SuggestArgs {
     #[arg(long, value_enum)]
     pub category: Option<FileCategory>,
     
+    /// Only include files of at least this many MB
+    #[arg(long, value_name = "MB", value_parser = parse_size_mb)]
+    pub min_size: Option<f64>,
+    
+    /// Only include files of at most this many MB
+    #[arg(long, value_name = "MB", value_parser = parse_size_mb)]
+    pub max_size: Option<f64>,
+    
     /// Show all files, not just suggestions
     #[arg(long)]
     pub all: bool,
+    
+    /// Skip files and folders matching this name or glob (repeatable, e.g. node_modules or "*.tmp")
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+    
+    /// Group suggestions under headers
+    #[arg(long, value_enum)]
+    pub group_by: Option<GroupBy>,
+    
+    /// Order suggestions by confidence, size, age or name
+    #[arg(long, value_enum, default_value_t = SortBy::Confidence)]
+    pub sort: SortBy,
+    
+    /// Use a scan saved with `scan --save` instead of re-scanning
+    #[arg(long, value_name = "FILE", conflicts_with = "exclude")]
+    pub from: Option<PathBuf>,
+    
+    /// Also look inside .zip files (names and sizes only, nothing extracted)
+    #[arg(long)]
+    pub inspect_archives: bool,
 }
 
 #[derive(Args, Debug)]
@@ -154,6 +308,116 @@ pub struct CleanArgs {
     /// Skip confirmation prompts
     #[arg(short = 'y', long)]
     pub yes: bool,
+    
+    /// Always ask before cleaning, whatever the config says
+    #[arg(long, conflicts_with = "yes")]
+    pub confirm: bool,
+    
+    /// Only clean these extensions (e.g. png,jpg,jpeg)
+    #[arg(long, value_delimiter = ',')]
+    pub ext: Vec<String>,
+    
+    /// Skip files and folders matching this name or glob (repeatable, e.g. node_modules or "*.tmp")
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+    
+    /// Pick the highest-impact files until this much space is freed (e.g. 5GB)
+    #[arg(long, value_parser = parse_size, conflicts_with = "mode")]
+    pub target_free: Option<u64>,
+    
+    /// Use a scan saved with `scan --save` instead of re-scanning
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["ext", "exclude"])]
+    pub from: Option<PathBuf>,
+    
+    /// Also look inside .zip files (names and sizes only, nothing extracted)
+    #[arg(long)]
+    pub inspect_archives: bool,
+    
+    /// Also remove symlinks (only the link goes; its target is kept)
+    #[arg(long)]
+    pub include_symlinks: bool,
+    
+    /// Only print the totals (add --verbose for the full lists)
+    #[arg(long)]
+    pub summary_only: bool,
+    
+    /// Only files last modified on or after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub since: Option<NaiveDate>,
+    
+    /// Only files last modified on or before this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub until: Option<NaiveDate>,
+    
+    /// With --mode duplicates: always keep copies matching this glob (repeatable)
+    #[arg(long, value_name = "GLOB", value_parser = parse_glob)]
+    pub keep_pattern: Vec<glob::Pattern>,
+    
+    /// With --mode duplicates: copy to keep when no --keep-pattern matches (default: oldest)
+    #[arg(long, value_enum)]
+    pub keep: Option<KeepCopy>,
+    
+    /// Allow the home folder, the filesystem root or a top-level folder
+    #[arg(long = "i-know-what-im-doing")]
+    pub i_know_what_im_doing: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SweepInstallersArgs {
+    /// Folder to sweep (default: Downloads folder)
+    pub path: Option<PathBuf>,
+    
+    /// Only installers older than N days
+    #[arg(long, default_value_t = 30)]
+    pub days: u64,
+    
+    /// Dry run (show what would be done)
+    #[arg(long)]
+    pub dry_run: bool,
+    
+    /// Skip confirmation prompts
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+    
+    /// Allow the home folder, the filesystem root or a top-level folder
+    #[arg(long = "i-know-what-im-doing")]
+    pub i_know_what_im_doing: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DedupeDownloadsArgs {
+    /// Folder to check (default: Downloads folder)
+    pub path: Option<PathBuf>,
+    
+    /// Dry run (show what would be done)
+    #[arg(long)]
+    pub dry_run: bool,
+    
+    /// Skip confirmation prompts
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+    
+    /// Allow the home folder, the filesystem root or a top-level folder
+    #[arg(long = "i-know-what-im-doing")]
+    pub i_know_what_im_doing: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SweepCacheArgs {
+    /// Folder to sweep (searched all the way down)
+    pub path: PathBuf,
+    
+    /// Dry run (show what would be done)
+    #[arg(long)]
+    pub dry_run: bool,
+    
+    /// Skip confirmation prompts
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+    
+    /// Allow the home folder, the filesystem root or a top-level folder
+    #[arg(long = "i-know-what-im-doing")]
+    pub i_know_what_im_doing: bool,
 }
 
 #[derive(Args, Debug)]
@@ -162,6 +426,14 @@ pub struct DeleteArgs {
     #[arg(long)]
     pub path: Option<PathBuf>,
     
+    /// Use a scan saved with `scan --save` so indices match exactly
+    #[arg(long, value_name = "FILE", conflicts_with = "path")]
+    pub from: Option<PathBuf>,
+    
+    /// Include .zip files, as `suggest --inspect-archives` does
+    #[arg(long)]
+    pub inspect_archives: bool,
+    
     /// File indices to delete (from suggest command)
     #[arg(required_unless_present = "all", conflicts_with = "all")]
     pub indices: Vec<usize>,
@@ -182,9 +454,29 @@ pub struct DeleteArgs {
     #[arg(long, conflicts_with_all = &["indices", "all", "duplicates"])]
     pub large: Option<u64>,
     
+    /// Dry run (show what would be removed)
+    #[arg(long)]
+    pub dry_run: bool,
+    
     /// Skip confirmation prompts
     #[arg(short = 'y', long)]
     pub yes: bool,
+    
+    /// Always ask before deleting, whatever the config says
+    #[arg(long, conflicts_with = "yes")]
+    pub confirm: bool,
+    
+    /// Also remove symlinks (only the link goes; its target is kept)
+    #[arg(long)]
+    pub include_symlinks: bool,
+    
+    /// Only print the totals (add --verbose for the full lists)
+    #[arg(long)]
+    pub summary_only: bool,
+    
+    /// Allow the home folder, the filesystem root or a top-level folder
+    #[arg(long = "i-know-what-im-doing")]
+    pub i_know_what_im_doing: bool,
 }
 
 
@@ -203,24 +495,68 @@ pub enum ExamArgs {
     /// Set exam dates manually
     Set {
         /// Start date (YYYY-MM-DD)
-        start_date: String,
+        #[arg(value_parser = parse_date)]
+        start_date: NaiveDate,
         
         /// End date (YYYY-MM-DD)
-        end_date: String,
+        #[arg(value_parser = parse_date)]
+        end_date: NaiveDate,
         
         /// Exam period name
         #[arg(short, long)]
         name: Option<String>,
     },
     
+    /// Push back the active exam's end date, keeping its tracked files
+    Extend {
+        /// Days to add to the end date (counted from today if none is set)
+        #[arg(long, required_unless_present = "to", conflicts_with = "to")]
+        days: Option<u32>,
+        
+        /// New end date (YYYY-MM-DD)
+        #[arg(long, value_parser = parse_date)]
+        to: Option<NaiveDate>,
+    },
+    
     /// End exam and show cleanup options
-    End,
+    End {
+        /// Skip confirmation before cleaning exam files
+        #[arg(short = 'y', long)]
+        yes: bool,
+        
+        /// Always ask before cleaning exam files, whatever the config says
+        #[arg(long, conflicts_with = "yes")]
+        confirm: bool,
+    },
     
     /// Show exam status
     Status,
     
     /// List tracked exam files
     List,
+    
+    /// Restore what the last `exam end` cleaned and resume tracking
+    Undo,
+    
+    /// Keep files out of exam tracking by extension or name
+    #[command(subcommand)]
+    Ignore(ExamIgnoreArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ExamIgnoreArgs {
+    /// Never track matching files: an extension like .ics, or part of a file name
+    Add {
+        pattern: String,
+    },
+    
+    /// Track matching files again
+    Remove {
+        pattern: String,
+    },
+    
+    /// List the ignored patterns
+    List,
 }
 
 #[derive(Subcommand, Debug)]
@@ -228,7 +564,12 @@ pub enum ProtectArgs {
     /// Add folder to protection list
     Add {
         /// Folder to protect
-        path: PathBuf,
+        #[arg(required_unless_present = "glob", conflicts_with = "glob")]
+        path: Option<PathBuf>,
+        
+        /// Protect every path matching a pattern instead, e.g. Personal or "*/private/*"
+        #[arg(long, value_name = "PATTERN", value_parser = parse_glob)]
+        glob: Option<glob::Pattern>,
         
         /// Protection type
         #[arg(long, value_enum, default_value_t = ProtectionTypeCli::Soft)]
@@ -238,12 +579,24 @@ pub enum ProtectArgs {
     /// Remove folder from protection list
     Remove {
         /// Folder to unprotect
-        path: PathBuf,
+        #[arg(required_unless_present = "glob", conflicts_with = "glob")]
+        path: Option<PathBuf>,
+        
+        /// Pattern to unprotect (as given to protect add --glob)
+        #[arg(long, value_name = "PATTERN")]
+        glob: Option<String>,
     },
     
     /// List protected folders
     List,
     
+    /// Drop protected folders that no longer exist
+    Prune {
+        /// Don't ask before dropping them
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+    
     /// Clear all protected folders
     Clear,
 }
@@ -262,14 +615,32 @@ pub enum ArchiveArgs {
         /// Skip confirmation
         #[arg(short = 'y', long)]
         yes: bool,
+        
+        /// Always ask before deleting archives, whatever the config says
+        #[arg(long, conflicts_with = "yes")]
+        confirm: bool,
+        
+        /// Zip the old archives' files instead of deleting them
+        #[arg(long)]
+        compress: bool,
     },
     
     /// Show archive statistics
     Stats,
     
+    /// Search every archive for files whose original path contains TEXT
+    Find {
+        /// Part of the file name or original path (case-insensitive)
+        text: String,
+        
+        /// Restore match number N from the list
+        #[arg(long, value_name = "N")]
+        restore: Option<usize>,
+    },
+    
     /// Restore files from archive
     Restore {
-        /// Archive date (YYYY-MM-DD) or "latest"
+        /// Archive date (YYYY-MM-DD), label, or "latest"
         date: String,
         
         /// File indices to restore
@@ -279,12 +650,87 @@ pub enum ArchiveArgs {
         #[arg(long, conflicts_with = "indices")]
         all: bool,
         
+        /// Pick the files to restore from a checklist
+        #[arg(short, long, conflicts_with_all = ["indices", "all"])]
+        interactive: bool,
+        
         /// Restore to different location
         #[arg(short, long)]
         output: Option<PathBuf>,
+        
+        /// Skip files identical to one already at the destination
+        #[arg(long)]
+        merge: bool,
+    },
+    
+    /// Remove archive folders that are empty or whose files are all gone
+    Tidy {
+        /// Show what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+        
+        /// Also remove folders with leftover files without asking
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+    
+    /// Merge several archives into one folder, e.g. "Fall2024"
+    Consolidate {
+        /// Archives to merge (YYYY-MM-DD, a label, or "latest")
+        #[arg(required_unless_present = "all")]
+        dates: Vec<String>,
+        
+        /// Merge every archive
+        #[arg(long, conflicts_with = "dates")]
+        all: bool,
+        
+        /// Name of the merged folder
+        #[arg(long, value_parser = parse_archive_label)]
+        into: String,
+        
+        /// Show what would be merged without moving anything
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum TrashArgs {
+    /// List files trashed in the last 30 days
+    List,
+    
+    /// Restore trashed files to their original location
+    Restore {
+        /// File numbers from `trash list`
+        indices: Vec<usize>,
+        
+        /// Restore all listed files
+        #[arg(long, conflicts_with = "indices")]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CourseArgs {
+    /// Assign a course to a file or glob pattern
+    Set {
+        /// File path, or glob such as "STAT200*" or "~/Downloads/stat/*.pdf"
+        target: String,
+        
+        /// Course name (e.g. math)
+        course: String,
+    },
+    
+    /// Remove a course override
+    Remove {
+        /// File path or glob used when the override was set
+        target: String,
+    },
+    
+    /// List course overrides
+    List,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ScheduleArgs {
     /// Set reminder schedule
@@ -297,19 +743,198 @@ pub enum ScheduleArgs {
     /// Show current schedule
     Show,
     
-    /// Run scheduled cleanup now
+    /// Clean duplicates and old files in the default folder without prompting
     Run,
 }
 
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// Summarize the last week or month, with a bar per day
+    #[arg(long, value_enum, conflicts_with = "reset")]
+    pub period: Option<StatsPeriod>,
+    
+    /// Start a fresh slate: zero streaks and totals, clear achievements (asks first)
+    #[arg(long)]
+    pub reset: bool,
+    
+    /// With --reset: keep the achievements already earned
+    #[arg(long, requires = "reset")]
+    pub keep_achievements: bool,
+    
+    /// With --reset: don't ask first
+    #[arg(short = 'y', long, requires = "reset")]
+    pub yes: bool,
+}
+
 #[derive(Args, Debug)]
 pub struct ScoreArgs {
     /// Path to score
     #[arg(default_value = ".")]
     pub path: PathBuf,
     
-    /// Show detailed breakdown
+    /// List every file that costs points, with its cost
     #[arg(short, long)]
     pub detailed: bool,
+    
+    /// List the files behind each penalty, with a delete command for each group
+    #[arg(long)]
+    pub explain: bool,
+    
+    /// Fast estimate: no hashing, and big folders are sampled
+    #[arg(long, conflicts_with = "explain")]
+    pub quick: bool,
+    
+    /// Show how the folder's score changed over the last runs (no scan)
+    #[arg(long, conflicts_with_all = ["detailed", "explain", "quick"])]
+    pub trend: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct TopArgs {
+    /// Path to check
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct InitArgs {
+    /// Write default settings without prompting (also CLEANCRUSH_NONINTERACTIVE=1)
+    #[arg(long)]
+    pub non_interactive: bool,
+    
+    /// Replace an existing config (the old one is kept as a backup)
+    #[arg(short, long)]
+    pub force: bool,
+    
+    /// Print the config that would be written instead of saving it
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct IgnoreFileArgs {
+    /// File name (resume.pdf), path, or glob (transcript*.pdf)
+    #[arg(required_unless_present = "list")]
+    pub name: Option<String>,
+    
+    /// Take the name off the list instead
+    #[arg(long)]
+    pub remove: bool,
+    
+    /// Show the never-suggest list
+    #[arg(long, conflicts_with_all = ["name", "remove"])]
+    pub list: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: Option<ConfigAction>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Change a preference without re-running setup
+    #[command(subcommand)]
+    Set(ConfigSetting),
+    
+    /// Manage the filename keywords that detect each course
+    #[command(subcommand)]
+    Course(ConfigCourseArgs),
+    
+    /// Start the setup wizard over, keeping your streaks and achievements
+    Reset {
+        /// Don't ask before resetting
+        #[arg(short = 'y', long)]
+        yes: bool,
+        
+        /// Also reset streaks, achievements and cleanup totals
+        #[arg(long)]
+        hard: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigSetting {
+    /// Where cleaned files go
+    DefaultAction {
+        #[arg(value_enum)]
+        value: CleanupActionCli,
+    },
+    
+    /// How often to remind you to clean up
+    ReminderSchedule {
+        #[arg(value_enum)]
+        value: ScheduleType,
+    },
+    
+    /// Offer exam tracking when lots of new study files show up
+    ExamMonitoring {
+        #[arg(value_enum)]
+        value: Toggle,
+    },
+    
+    /// How scan signals combine into a confidence score
+    ConfidenceModel {
+        #[arg(value_enum)]
+        value: ConfidenceModelCli,
+    },
+    
+    /// Size in MB above which a large file costs more in the score
+    VeryLargeMb {
+        #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+        value: u64,
+    },
+    
+    /// Zip archived files after each archive run to save space
+    CompressArchives {
+        #[arg(value_enum)]
+        value: Toggle,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCourseArgs {
+    /// Add a keyword to a course, creating the course if it's new
+    Add {
+        /// Course name (e.g. nursing)
+        name: String,
+        
+        /// Word in file names that marks this course (e.g. anatomy)
+        keyword: String,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct RestoreLastArgs {
+    /// Show what would be restored without moving anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct VersionArgs {
+    /// Print version and build details as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DoctorArgs {
+    /// Fix every problem found without asking
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct CalibrateArgs {
+    /// Folder to sample files from
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+    
+    /// How many files to ask about
+    #[arg(short = 'n', long, default_value_t = 12)]
+    pub samples: usize,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -322,6 +947,66 @@ pub enum FileCategory {
     Assignment,
     Reference,
     Other,
+    Empty,
+}
+
+impl FileCategory {
+    /// The scanner category this filter selects, or `None` for `all`
+    pub fn to_core(&self) -> Option<crate::FileCategory> {
+        match self {
+            FileCategory::All => None,
+            FileCategory::Duplicate => Some(crate::FileCategory::Duplicate),
+            FileCategory::Old => Some(crate::FileCategory::Old),
+            FileCategory::Large => Some(crate::FileCategory::Large),
+            FileCategory::Lecture => Some(crate::FileCategory::Lecture),
+            FileCategory::Assignment => Some(crate::FileCategory::Assignment),
+            FileCategory::Reference => Some(crate::FileCategory::Reference),
+            FileCategory::Other => Some(crate::FileCategory::Other),
+            FileCategory::Empty => Some(crate::FileCategory::Empty),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum GroupBy {
+    /// Detected (or overridden) course
+    Course,
+    /// Cleanup category (duplicate, old, large, ...)
+    Category,
+    /// Parent folder
+    Folder,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortBy {
+    /// Most confident first (the scan's own order)
+    Confidence,
+    /// Biggest first
+    Size,
+    /// Oldest first
+    Age,
+    /// Alphabetical by file name
+    Name,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// Progress bar sized to the terminal
+    Bar,
+    /// Compact spinner for narrow terminals
+    Spinner,
+    /// No progress output
+    None,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -334,12 +1019,24 @@ pub enum CleanMode {
     Old,
     /// Clean only large files
     Large,
-    /// Clean by confidence score
+    /// Clean only empty (0 byte) files
+    Empty,
+    /// Clean files in the high confidence tier (`confidence_tiers`)
     Confidence,
     /// Interactive selection
     Interactive,
 }
 
+/// Which copy of a duplicate group survives a cleanup
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum KeepCopy {
+    /// Keep the least recently modified copy
+    #[default]
+    Oldest,
+    /// Keep the most recently modified copy
+    Newest,
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum ProtectionTypeCli {
     /// Never scan folder
@@ -348,6 +1045,46 @@ pub enum ProtectionTypeCli {
     Soft,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CleanupActionCli {
+    /// Move to the Recycle Bin/Trash
+    RecycleBin,
+    /// Move into course folders in the archive
+    Archive,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Toggle {
+    On,
+    Off,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ConfidenceModelCli {
+    /// Strongest single signal wins
+    Max,
+    /// Signals compound (old + large + duplicated scores higher)
+    Combined,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum StatsPeriod {
+    /// The last 7 days
+    Week,
+    /// The last 30 days
+    Month,
+}
+
+impl StatsPeriod {
+    /// Days covered, today included
+    pub fn days(&self) -> i64 {
+        match self {
+            StatsPeriod::Week => 7,
+            StatsPeriod::Month => 30,
+        }
+    }
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum ScheduleType {
     /// No reminders
@@ -358,6 +1095,78 @@ pub enum ScheduleType {
     Monthly,
 }
 
+/// Parse a size like "5GB", "500MB", "1.5g" or "2048" (bytes)
+pub fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    
+    let number: f64 = number.parse()
+        .map_err(|_| format!("invalid size '{}' (try 5GB or 500MB)", value))?;
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1024,
+        "m" | "mb" => 1024 * 1024,
+        "g" | "gb" => 1024 * 1024 * 1024,
+        "t" | "tb" => 1024 * 1024 * 1024 * 1024,
+        other => return Err(format!("unknown size unit '{}' (use KB, MB, GB or TB)", other)),
+    };
+    
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Parse a glob pattern such as `*/organized/*`
+pub fn parse_glob(value: &str) -> Result<glob::Pattern, String> {
+    glob::Pattern::new(value.trim())
+        .map_err(|e| format!("invalid pattern '{}': {}", value, e))
+}
+
+/// Parse a consolidated archive name such as `Fall2024`: a plain folder
+/// name that can't be mistaken for a dated archive
+pub fn parse_archive_label(value: &str) -> Result<String, String> {
+    let label = value.trim();
+    if label.is_empty() || label.starts_with('.') || label.contains(['/', '\\']) {
+        return Err(format!("invalid archive name '{}' (use a plain folder name like Fall2024)", value));
+    }
+    if label == "latest" || label.parse::<NaiveDate>().is_ok() {
+        return Err(format!("'{}' is reserved for dated archives - pick a name like Fall2024", value));
+    }
+    Ok(label.to_string())
+}
+
+/// Parse a file cap for `scan --limit`; it has to let at least one file through
+pub fn parse_limit(value: &str) -> Result<usize, String> {
+    match value.trim().parse::<usize>() {
+        Ok(0) => Err("the limit must be at least 1".to_string()),
+        Ok(limit) => Ok(limit),
+        Err(_) => Err(format!("invalid limit '{}' (use a whole number like 20000)", value)),
+    }
+}
+
+/// Parse a size in MB for `--min-size`/`--max-size`, e.g. 50 or 0.5
+pub fn parse_size_mb(value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(mb) if mb.is_finite() && mb >= 0.0 => Ok(mb),
+        _ => Err(format!("invalid size '{}' (use a number of MB like 50 or 0.5)", value)),
+    }
+}
+
+/// Parse a date like "2025-10-14"
+pub fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{}' (use YYYY-MM-DD)", value))
+}
+
+/// Format bytes as MB, or GB for larger sizes
+pub fn format_size(bytes: u64) -> String {
+    let mb = bytes as f64 / (1024.0 * 1024.0);
+    if mb >= 1024.0 {
+        format!("{:.1} GB", mb / 1024.0)
+    } else {
+        format!("{:.1} MB", mb)
+    }
+}
+
 impl Cli {
     /// Print help with examples
     pub fn print_help() {
@@ -369,7 +1178,12 @@ impl Cli {
         println!("{}", "OPTIONS:".bold());
         println!("  --safe           Safe mode (preview only, no changes)");
         println!("  -v, --verbose    Verbose output");
+        println!("  -q, --quiet      Only results and errors (for scripts and CI)");
         println!("  --no-color       Disable colored output");
+        println!("  --no-scan        Skip the Downloads growth check in reminders");
+        println!("  --progress <bar|spinner|none>  Progress display (default: bar)");
+        println!("  --log-level <LEVEL>  Diagnostic log: off, error, warn, info, debug, trace");
+        println!("  --log-file <PATH>    Append the diagnostic log to a file (level defaults to info)");
         println!("  -h, --help       Print help");
         println!("  -V, --version    Print version");
         println!();
@@ -382,52 +1196,126 @@ impl Cli {
         println!("  {}  Show cleanup suggestions", "suggest".cyan().bold());
         println!("      cleancrush suggest ~/Downloads");
         println!("      cleancrush suggest --confidence 0.8");
+        println!("      cleancrush suggest --group-by course");
         println!();
         println!("  {}  Clean files", "clean".cyan().bold());
         println!("      cleancrush clean --mode duplicates ~/Downloads");
         println!("      cleancrush clean --mode old --days 90");
+        println!("      cleancrush clean --ext png,jpg,jpeg");
+        println!("      cleancrush clean --target-free 5GB");
         println!();
         println!("  {}  Delete specific files", "delete".cyan().bold());
         println!("      cleancrush delete 1 3 5 --path ~/Downloads");
         println!("      cleancrush delete --duplicates --path ~/Downloads");
         println!("      cleancrush delete --all --path ~/Downloads");
         println!();
+        println!("  {}  Clear old installers and archives", "sweep-installers".cyan().bold());
+        println!("      cleancrush sweep-installers ~/Downloads");
+        println!("      cleancrush sweep-installers --days 90 --dry-run");
+        println!();
+        println!("  {}  Remove re-downloads like \"notes (1).pdf\"", "dedupe-downloads".cyan().bold());
+        println!("      cleancrush dedupe-downloads ~/Downloads --dry-run");
+        println!();
+        println!("  {}  Clear Jupyter checkpoints and Python caches", "sweep-cache".cyan().bold());
+        println!("      cleancrush sweep-cache ~/Projects --dry-run");
+        println!();
         println!("  {}  Manage exam mode", "exam".cyan().bold());
         println!("      cleancrush exam on");
         println!("      cleancrush exam set 2024-12-01 2024-12-15");
+        println!("      cleancrush exam extend --days 7");
         println!("      cleancrush exam status");
         println!("      cleancrush exam end");
+        println!("      cleancrush exam undo");
+        println!("      cleancrush exam ignore add .ics");
         println!();
         println!("  {}  Manage protected folders", "protect".cyan().bold());
         println!("      cleancrush protect add ~/Documents");
         println!("      cleancrush protect add ~/Desktop --protection hard");
+        println!("      cleancrush protect add --glob \"*/private/*\" --protection hard");
         println!("      cleancrush protect list");
+        println!("      cleancrush protect prune");
         println!();
         println!("  {}  Manage archives", "archive".cyan().bold());
         println!("      cleancrush archive list");
         println!("      cleancrush archive clean --days 30");
+        println!("      cleancrush archive clean 30 --compress");
         println!("      cleancrush archive stats");
+        println!("      cleancrush archive find notes");
+        println!("      cleancrush archive restore latest --all --merge");
+        println!("      cleancrush archive restore latest --interactive");
+        println!("      cleancrush archive consolidate --all --into Fall2024");
+        println!("      cleancrush archive tidy --dry-run");
+        println!();
+        println!("  {}  List or restore trashed files", "trash".cyan().bold());
+        println!("      cleancrush trash list");
+        println!("      cleancrush trash restore 1 2");
+        println!();
+        println!("  {}  Undo the most recent cleanup (trash or archive; alias: undo)", "restore-last".cyan().bold());
+        println!("      cleancrush restore-last");
+        println!("      cleancrush restore-last --dry-run");
+        println!();
+        println!("  {}  Never suggest a specific file again", "ignore-file".cyan().bold());
+        println!("      cleancrush ignore-file resume.pdf");
+        println!("      cleancrush ignore-file \"transcript*.pdf\"");
+        println!("      cleancrush ignore-file --list");
+        println!();
+        println!("  {}  Correct detected courses", "course".cyan().bold());
+        println!("      cleancrush course set \"STAT200*\" math");
+        println!("      cleancrush course set ~/Downloads/notes.pdf cs");
+        println!("      cleancrush course list");
         println!();
         println!("  {}  Manage reminders", "schedule".cyan().bold());
         println!("      cleancrush schedule set weekly");
         println!("      cleancrush schedule show");
+        println!("      cleancrush schedule run         # unattended cleanup, e.g. from cron");
         println!();
         println!("  {}  Show statistics", "stats".cyan().bold());
         println!("      cleancrush stats");
+        println!("      cleancrush stats --period week");
+        println!("      cleancrush stats --reset --keep-achievements");
+        println!();
+        println!("  {}  See where you stand at a glance", "status".cyan().bold());
+        println!("      cleancrush status");
         println!();
         println!("  {}  Calculate cleanliness score", "score".cyan().bold());
         println!("      cleancrush score ~/Downloads");
         println!("      cleancrush score --detailed");
+        println!("      cleancrush score ~/Downloads --explain");
+        println!("      cleancrush score ~/Downloads --quick");
+        println!("      cleancrush score ~/Downloads --trend");
         println!();
-        println!("  {}  Show configuration", "config".cyan().bold());
+        println!("  {}  Show the one cleanup worth doing first", "top".cyan().bold());
+        println!("      cleancrush top ~/Downloads");
+        println!();
+        println!("  {}  Show or change configuration", "config".cyan().bold());
         println!("      cleancrush config");
+        println!("      cleancrush config set default-action archive");
+        println!("      cleancrush config set exam-monitoring off");
+        println!("      cleancrush config course add nursing anatomy");
+        println!("      cleancrush config reset");
+        println!();
+        println!("  {}  Run setup again or write defaults", "init".cyan().bold());
+        println!("      cleancrush init --force");
+        println!("      cleancrush init --non-interactive");
+        println!();
+        println!("  {}  Check for configuration problems", "doctor".cyan().bold());
+        println!("      cleancrush doctor");
+        println!("      cleancrush doctor --yes");
+        println!();
+        println!("  {}  Check confidence scores against your own judgement", "calibrate".cyan().bold());
+        println!("      cleancrush calibrate ~/Downloads");
         println!();
         println!("  {}  Show help", "help".cyan().bold());
         println!("      cleancrush help");
         println!();
+        println!("  {}  Show version and build details", "version".cyan().bold());
+        println!("      cleancrush version");
+        println!("      cleancrush version --json");
+        println!();
         println!("{}", "EXAMPLES:".dimmed());
         println!("  # First-time setup");
         println!("  cleancrush");
+        println!("  cleancrush init --non-interactive   # CI/containers, no prompts");
         println!();
         println!("  # Regular cleanup workflow");
         println!("  cleancrush scan ~/Downloads");
@@ -462,6 +1350,20 @@ impl Cli {
         println!("Student-focused exam file cleanup tool");
         println!("Repository: {}", env!("CARGO_PKG_REPOSITORY"));
         println!("License: {}", env!("CARGO_PKG_LICENSE"));
+        println!("Build: {} ({})", env!("GIT_HASH"), env!("BUILD_DATE"));
+    }
+    
+    /// Print version and build details as JSON (for bug reports)
+    pub fn print_version_json() {
+        let info = serde_json::json!({
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+            "repository": env!("CARGO_PKG_REPOSITORY"),
+            "license": env!("CARGO_PKG_LICENSE"),
+            "git_commit": env!("GIT_HASH"),
+            "build_date": env!("BUILD_DATE"),
+        });
+        println!("{}", serde_json::to_string_pretty(&info).unwrap_or_default());
     }
 
     ///Print command specifific help
@@ -478,18 +1380,44 @@ impl Cli {
                 println!("Usage: cleancrush scan [PATH] [OPTIONS]");
                 println!();
                 println!("Arguments:");
-                println!("  [PATH]                  Path to scan (default: current directory)");
+                println!("  [PATH]                  Path to scan (default: folders from the setup wizard)");
                 println!();
                 println!("Options:");
                 println!("  --days N                Consider files older than N days as 'old' (default: 60)");
                 println!("  --large N               Consider files larger than N MB as 'large' (default: 100)");
                 println!("  --detailed              Show detailed file information");
                 println!("  --limit N               Maximum files to scan (default: 5000)");
+                println!("  --depth N               Folder levels to look into (default: 3, 0 = unlimited)");
+                println!("                          (deeper scans reach --limit sooner)");
+                println!("  --min-size MB           Only include files of at least this size");
+                println!("  --max-size MB           Only include files of at most this size");
+                println!("  --ext EXT,...           Only scan these extensions (e.g. png,jpg)");
+                println!("  --exclude-ext EXT,...   Skip these extensions (e.g. png,jpg)");
+                println!("  --exclude PATTERN       Skip files/folders matching a name or glob (repeatable)");
+                println!("  --pretend-full          Stop early as if the file limit was reached (for testing)");
+                println!("  --report FILE           Also write a Markdown report (summary, findings, top 20, score)");
+                println!("  --save FILE             Save the scan for suggest/clean/delete --from");
+                println!("  --histogram             Also show suggestions by age (0-7, 8-30, 31-60, 61-90, 90+ days)");
+                println!("  --json                  Print the scan as JSON instead of the summary");
+                println!("  --out FILE              With --json: write it to a file instead");
+                println!("  --inspect-archives      Also list what .zip files contain (names/sizes only)");
+                println!("  --stream                Print high-confidence suggestions as they're found");
+                println!("                          (discovery order - no overall ranking)");
+                println!("  --stream-clean          With --stream: clean them as they're found too");
+                println!("  -y, --yes               With --stream-clean: don't ask before starting");
+                println!("  --i-know-what-im-doing  Allow ~, / or a top-level folder like /home");
                 println!();
                 println!("Examples:");
                 println!("  cleancrush scan ~/Downloads");
                 println!("  cleancrush scan --days 90 --large 200");
                 println!("  cleancrush scan --detailed --limit 1000");
+                println!("  cleancrush scan --depth 6 ~/School");
+                println!("  cleancrush scan --ext png,jpg,jpeg ~/Downloads");
+                println!("  cleancrush scan ~/Downloads --report report.md");
+                println!("  cleancrush scan ~/Downloads --histogram");
+                println!("  cleancrush scan ~/Downloads --json --out scans/today.json");
+                println!("  cleancrush scan ~/Downloads --inspect-archives");
+                println!("  cleancrush scan ~/Downloads --stream --stream-clean");
             }
             Commands::Suggest(_) => {
                 println!("Show detailed cleanup suggestions with confidence scores");
@@ -503,11 +1431,17 @@ impl Cli {
                 println!("  --confidence FLOAT      Minimum confidence score to show (0.0-1.0, default: 0.4)");
                 println!("  --category CATEGORY     Filter by category (duplicate, old, large, lecture, assignment, reference, other)");
                 println!("  --all                   Show all files, not just suggestions");
+                println!("  --exclude PATTERN       Skip files/folders matching a name or glob (repeatable)");
+                println!("  --group-by KEY          Group suggestions by course, category or folder");
+                println!("  --sort KEY              Order by confidence (default), size, age or name");
+                println!("  --from FILE             Use a scan saved with 'scan --save'");
                 println!();
                 println!("Examples:");
                 println!("  cleancrush suggest ~/Downloads");
                 println!("  cleancrush suggest --confidence 0.8");
                 println!("  cleancrush suggest --category duplicate");
+                println!("  cleancrush suggest --group-by course");
+                println!("  cleancrush suggest --sort size");
             }
             Commands::Clean(_) => {
                 println!("Clean files (delete or archive based on config)");
@@ -518,15 +1452,32 @@ impl Cli {
                 println!("  [PATH]                  Path to clean (default: current directory)");
                 println!();
                 println!("Options:");
-                println!("  --mode MODE             Cleanup mode: all, duplicates, old, large, confidence, interactive (default: all)");
+                println!("  --mode MODE             Cleanup mode: all, duplicates, old, large, empty, confidence, interactive (default: all)");
                 println!("  --days N                Days threshold for old files (default: 60)");
                 println!("  --dry-run               Dry run (show what would be done)");
                 println!("  -y, --yes               Skip confirmation prompts");
+                println!("  --confirm               Always ask first (overrides confirm_defaults)");
+                println!("  --ext EXT,...           Only clean these extensions (e.g. png,jpg)");
+                println!("  --exclude PATTERN       Skip files/folders matching a name or glob (repeatable)");
+                println!("  --target-free SIZE      Free at least SIZE (e.g. 5GB), biggest sure wins first");
+                println!("  --from FILE             Use a scan saved with 'scan --save'");
+                println!("  --include-symlinks      Also remove symlinks (skipped by default)");
+                println!("  --summary-only          Only print the totals (--verbose for the full lists)");
+                println!("  --since DATE            Only files modified on or after DATE (YYYY-MM-DD)");
+                println!("  --until DATE            Only files modified on or before DATE (YYYY-MM-DD)");
+                println!("  --keep-pattern GLOB     Duplicates mode: always keep copies matching GLOB (repeatable)");
+                println!("  --keep oldest|newest    Duplicates mode: copy kept when no pattern matches (default: oldest)");
+                println!("  --i-know-what-im-doing  Allow ~, / or a top-level folder like /home");
                 println!();
                 println!("Examples:");
                 println!("  cleancrush clean --mode duplicates ~/Downloads");
                 println!("  cleancrush clean --mode old --days 90");
                 println!("  cleancrush clean --dry-run --mode all");
+                println!("  cleancrush clean --ext png,jpg,jpeg ~/Downloads");
+                println!("  cleancrush clean --exclude node_modules --exclude '*.tmp' ~/Downloads");
+                println!("  cleancrush clean --target-free 5GB ~/Downloads");
+                println!("  cleancrush clean --since 2025-10-01 --until 2025-10-31 ~/Downloads");
+                println!("  cleancrush clean --mode duplicates --keep-pattern \"*/organized/*\" ~/Downloads");
             }
             Commands::Delete(_) => {
                 println!("Delete specific files by index or pattern");
@@ -538,17 +1489,43 @@ impl Cli {
                 println!();
                 println!("Options:");
                 println!("  --path PATH             Path that was scanned (for context)");
+                println!("  --from FILE             Use a scan saved with 'scan --save' (stable indices)");
                 println!("  --all                   Delete all suggested files");
                 println!("  --duplicates            Delete only duplicate files");
                 println!("  --old [DAYS]            Delete only old files (older than N days)");
                 println!("  --large [MB]            Delete only large files (larger than N MB)");
+                println!("  --dry-run               Show what would be removed without touching anything");
                 println!("  -y, --yes               Skip confirmation prompts");
+                println!("  --confirm               Always ask first (overrides confirm_defaults)");
+                println!("  --include-symlinks      Also remove symlinks (skipped by default)");
+                println!("  --summary-only          Only print the totals (--verbose for the full lists)");
+                println!("  --i-know-what-im-doing  Allow ~, / or a top-level folder like /home");
                 println!();
                 println!("Examples:");
                 println!("  cleancrush delete 1 3 5 --path ~/Downloads");
                 println!("  cleancrush delete --duplicates --path ~/Downloads");
                 println!("  cleancrush delete --all --path ~/Downloads");
                 println!("  cleancrush delete --old 90 --path ~/Downloads");
+                println!("  cleancrush delete 1 3 5 --from scan.json");
+                println!("  cleancrush delete --all --path ~/Downloads --dry-run");
+            }
+            Commands::Init(_) => {
+                println!("Run first-time setup, or write default settings without prompting");
+                println!();
+                println!("Usage: cleancrush init [OPTIONS]");
+                println!();
+                println!("Options:");
+                println!("  --non-interactive       Write defaults without prompting");
+                println!("  -f, --force             Replace an existing config (old one kept as backup)");
+                println!("  --dry-run               Print the config instead of saving it");
+                println!();
+                println!("Defaults: Recycle Bin, no protected folders, weekly reminders, exam monitoring on.");
+                println!("Setup never prompts when CLEANCRUSH_NONINTERACTIVE=1 is set or stdin isn't a terminal.");
+                println!();
+                println!("Examples:");
+                println!("  cleancrush init");
+                println!("  cleancrush init --non-interactive");
+                println!("  cleancrush init --non-interactive --dry-run");
             }
             
             Commands::Achievements => {
@@ -584,13 +1561,59 @@ impl Commands {
             Commands::Exam(_) => "exam",
             Commands::Protect(_) => "protect",
             Commands::Archive(_) => "archive",
+            Commands::Trash(_) => "trash",
+            Commands::RestoreLast(_) => "restore-last",
+            Commands::IgnoreFile(_) => "ignore-file",
+            Commands::Course(_) => "course",
+            Commands::SweepInstallers(_) => "sweep-installers",
+            Commands::DedupeDownloads(_) => "dedupe-downloads",
+            Commands::SweepCache(_) => "sweep-cache",
             Commands::Schedule(_) => "schedule",
-            Commands::Stats => "stats",
+            Commands::Stats(_) => "stats",
+            Commands::Status => "status",
             Commands::Score(_) => "score",
-            Commands::Config => "config",
+            Commands::Top(_) => "top",
+            Commands::Config(_) => "config",
+            Commands::Init(_) => "init",
             Commands::Achievements => "achievements",
+            Commands::Doctor(_) => "doctor",
+            Commands::Calibrate(_) => "calibrate",
             Commands::ShowHelp => "help",
-            Commands::Version => "version",
+            Commands::Version(_) => "version",
         }
     }
-}
\ No newline at end of file
+    
+    /// Whether the command can write config, exam or archive state
+    pub fn mutates_state(&self) -> bool {
+        !matches!(
+            self,
+            Commands::Stats(StatsArgs { reset: false, .. })
+                | Commands::Status
+                | Commands::Score(ScoreArgs { trend: true, .. })
+                | Commands::Top(_)
+                | Commands::Config(ConfigArgs { action: None })
+                | Commands::Achievements
+                | Commands::Trash(TrashArgs::List)
+                | Commands::IgnoreFile(IgnoreFileArgs { list: true, .. })
+                | Commands::ShowHelp
+                | Commands::Version(_)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn command(args: &[&str]) -> Commands {
+        Cli::try_parse_from([&["cleancrush"], args].concat()).unwrap().command
+    }
+    
+    #[test]
+    fn suggest_takes_the_lock_since_it_saves_the_last_scan() {
+        assert!(command(&["suggest", "."]).mutates_state());
+        assert!(command(&["clean", "."]).mutates_state());
+        assert!(!command(&["status"]).mutates_state());
+        assert!(!command(&["top", "."]).mutates_state());
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use dirs;
 use anyhow::{Result, Context};
@@ -7,6 +9,7 @@ use chrono::{DateTime, Utc};
 use dialoguer::{theme::ColorfulTheme, Select, MultiSelect, Confirm, Input};
 use colored::*;
 use crate::colors;
+use crate::gamification::StreakWindow;
 
 const SYSTEM_PATHS: &[&str] = &[
     r"C:\Windows", r"C:\Program Files", r"C:\ProgramData",
@@ -32,6 +35,93 @@ pub struct Config {
     pub achievements: Vec<String>,
     pub total_files_cleaned: u64,
     pub total_space_freed_mb: u64,
+    #[serde(default)]
+    pub best_cleanup_mb: u64,
+    
+    // Course labels that win over automatic detection
+    #[serde(default)]
+    pub course_overrides: Vec<CourseOverride>,
+    
+    // How scan signals are turned into a confidence score
+    #[serde(default)]
+    pub confidence_model: ConfidenceModel,
+    
+    // Files at or above this confidence start checked in `clean --mode interactive`
+    #[serde(default = "default_preselect_confidence")]
+    pub preselect_confidence: f32,
+    
+    // Suggestions above the first confidence are colored high, above the second medium
+    #[serde(default = "default_confidence_tiers")]
+    pub confidence_tiers: (f32, f32),
+    
+    // Folders that get cluttered; scanned when no path is given
+    #[serde(default)]
+    pub default_scan_paths: Vec<PathBuf>,
+    
+    // Files smaller than this (in KB) have their confidence capped; 0 turns it off
+    #[serde(default = "default_tiny_file_kb")]
+    pub tiny_file_kb: u64,
+    
+    // Large files over this size (in MB) cost more in the cleanliness score
+    #[serde(default = "default_very_large_mb")]
+    pub very_large_mb: u64,
+    
+    // Zip each archive's files after archiving (off keeps them as plain files)
+    #[serde(default)]
+    pub compress_archives: bool,
+    
+    // Whether destructive commands ask before acting (`--yes`/`--confirm` override)
+    #[serde(default)]
+    pub confirm_defaults: ConfirmDefaults,
+    
+    // Files never suggested: a file name, an absolute path, or a glob
+    #[serde(default)]
+    pub never_suggest: Vec<String>,
+    
+    // Files kept out of exam tracking: an extension (`.ics`) or part of a file name
+    #[serde(default)]
+    pub exam_ignore: Vec<String>,
+    
+    // Course names and the filename keywords that detect them, longest keyword wins
+    #[serde(default = "default_courses")]
+    pub courses: Vec<(String, Vec<String>)>,
+}
+
+/// Default pre-selection threshold for interactive cleanup
+pub const DEFAULT_PRESELECT_CONFIDENCE: f32 = 0.8;
+
+fn default_preselect_confidence() -> f32 {
+    DEFAULT_PRESELECT_CONFIDENCE
+}
+
+/// Default cutoffs for the high and medium confidence colors
+pub const DEFAULT_CONFIDENCE_TIERS: (f32, f32) = (0.8, 0.6);
+
+fn default_confidence_tiers() -> (f32, f32) {
+    DEFAULT_CONFIDENCE_TIERS
+}
+
+/// Built-in courses, from `COURSE_PATTERNS`
+pub fn default_courses() -> Vec<(String, Vec<String>)> {
+    crate::COURSE_PATTERNS.iter()
+        .map(|(course, keywords)| {
+            (course.to_string(), keywords.iter().map(|k| k.to_string()).collect())
+        })
+        .collect()
+}
+
+/// Default size below which a file frees too little to rank highly
+pub const DEFAULT_TINY_FILE_KB: u64 = 8;
+
+fn default_tiny_file_kb() -> u64 {
+    DEFAULT_TINY_FILE_KB
+}
+
+/// Default size above which a large file counts as very large
+pub const DEFAULT_VERY_LARGE_MB: u64 = 500;
+
+fn default_very_large_mb() -> u64 {
+    DEFAULT_VERY_LARGE_MB
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,10 +130,124 @@ pub enum CleanupAction {
     Archive,
 }
 
+/// Filesystems on Windows and macOS ignore case by default
+const CASE_INSENSITIVE_PATHS: bool = cfg!(any(windows, target_os = "macos"));
+
+/// `path.starts_with(folder)`, ignoring case where the filesystem usually
+/// does, so a protected `C:\Users\Me\Documents` covers `c:\users\me\documents\a.pdf`.
+/// Trailing separators never matter since whole components are compared.
+pub fn path_starts_with(path: &Path, folder: &Path) -> bool {
+    if !CASE_INSENSITIVE_PATHS {
+        return path.starts_with(folder);
+    }
+    starts_with_folded(path, folder)
+}
+
+/// `path.starts_with(folder)` comparing each component case-insensitively
+fn starts_with_folded(path: &Path, folder: &Path) -> bool {
+    let fold = |c: std::path::Component| c.as_os_str().to_string_lossy().to_lowercase();
+    let mut components = path.components();
+    folder.components().all(|f| components.next().is_some_and(|c| fold(c) == fold(f)))
+}
+
+/// Whether an `exam_ignore` pattern covers a file: `.ics` matches the
+/// extension, anything else any part of the file name (case-insensitive)
+pub fn exam_ignore_matches(pattern: &str, path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+    match pattern.strip_prefix('.') {
+        Some(extension) if !extension.contains('.') => {
+            path.extension().is_some_and(|ext| ext.to_string_lossy().to_lowercase() == extension)
+        }
+        _ => name.contains(pattern),
+    }
+}
+
+/// Where a path really is: relative parts and symlinked folders resolved, but
+/// a symlink itself left as the link. Falls back to the path as given.
+fn resolved_location(path: &Path) -> PathBuf {
+    let resolved = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => {
+            parent.canonicalize().map(|parent| parent.join(name))
+        }
+        _ => path.canonicalize(),
+    };
+    resolved.unwrap_or_else(|_| path.to_path_buf())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProtectedFolder {
+    /// Folder path, or a glob when `glob` is set
     pub path: PathBuf,
     pub protection_type: ProtectionType,
+    /// `path` is a pattern: with a separator (`*/private/*`) it matches the
+    /// path or a folder above it, otherwise (`Personal`) any folder or file name
+    #[serde(default)]
+    pub glob: bool,
+}
+
+impl ProtectedFolder {
+    /// Check if this entry covers a path
+    pub fn matches(&self, path: &Path) -> bool {
+        if !self.glob {
+            return path_starts_with(path, &self.path);
+        }
+        
+        let text = self.path.to_string_lossy();
+        let Ok(pattern) = glob::Pattern::new(&text) else {
+            return false;
+        };
+        let options = glob::MatchOptions {
+            case_sensitive: false,
+            ..glob::MatchOptions::new()
+        };
+        
+        if text.contains(['/', '\\']) {
+            path.ancestors().any(|p| pattern.matches_path_with(p, options))
+        } else {
+            path.components().any(|c| match c {
+                std::path::Component::Normal(name) => pattern.matches_with(&name.to_string_lossy(), options),
+                _ => false,
+            })
+        }
+    }
+}
+
+/// User-chosen course for a file path or glob pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourseOverride {
+    /// Absolute file path, or a glob (bare globs like `STAT200*` match file names)
+    pub pattern: String,
+    pub course: String,
+}
+
+impl CourseOverride {
+    /// Whether the pattern contains glob wildcards
+    pub fn is_glob(&self) -> bool {
+        self.pattern.contains(['*', '?', '['])
+    }
+    
+    /// Check if this override applies to a file
+    pub fn matches(&self, path: &Path) -> bool {
+        if !self.is_glob() {
+            return path == Path::new(&self.pattern);
+        }
+        
+        let Ok(pattern) = glob::Pattern::new(&self.pattern) else {
+            return false;
+        };
+        let options = glob::MatchOptions {
+            case_sensitive: false,
+            ..glob::MatchOptions::new()
+        };
+        
+        if self.pattern.contains(['/', '\\']) {
+            pattern.matches_path_with(path, options)
+        } else {
+            path.file_name()
+                .map(|name| pattern.matches_with(&name.to_string_lossy(), options))
+                .unwrap_or(false)
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +256,91 @@ pub enum ProtectionType {
     Soft,  // Scan but warn before actions
 }
 
+/// How independent cleanup signals combine into one confidence score
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ConfidenceModel {
+    /// Strongest single signal wins
+    #[default]
+    Max,
+    /// Signals compound: 1 − Π(1 − pᵢ)
+    Combined,
+}
+
+impl ConfidenceModel {
+    /// Combine per-signal confidences into one score
+    pub fn combine(&self, signals: &[f32]) -> f32 {
+        match self {
+            Self::Max => signals.iter().copied().fold(0.0, f32::max),
+            Self::Combined => {
+                1.0 - signals.iter().map(|p| 1.0 - p.clamp(0.0, 1.0)).product::<f32>()
+            }
+        }
+    }
+}
+
+/// When a destructive command asks "are you sure?"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConfirmPolicy {
+    AlwaysConfirm,
+    NeverConfirm,
+    /// Only ask when more than this many files are affected
+    ConfirmAboveCount(usize),
+}
+
+impl ConfirmPolicy {
+    /// Apply command-line overrides: `--yes` never prompts, `--confirm` always does
+    pub fn resolve(self, yes: bool, confirm: bool) -> Self {
+        if yes {
+            Self::NeverConfirm
+        } else if confirm {
+            Self::AlwaysConfirm
+        } else {
+            self
+        }
+    }
+    
+    /// Whether to prompt before touching `count` files
+    pub fn should_confirm(&self, count: usize) -> bool {
+        match self {
+            Self::AlwaysConfirm => true,
+            Self::NeverConfirm => false,
+            Self::ConfirmAboveCount(limit) => count > *limit,
+        }
+    }
+}
+
+impl std::fmt::Display for ConfirmPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlwaysConfirm => write!(f, "always ask"),
+            Self::NeverConfirm => write!(f, "never ask"),
+            Self::ConfirmAboveCount(limit) => write!(f, "ask above {} files", limit),
+        }
+    }
+}
+
+/// Confirmation policy for each destructive command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfirmDefaults {
+    pub clean: ConfirmPolicy,
+    pub delete: ConfirmPolicy,
+    pub archive_clean: ConfirmPolicy,
+    pub exam_end: ConfirmPolicy,
+}
+
+impl Default for ConfirmDefaults {
+    fn default() -> Self {
+        Self {
+            clean: ConfirmPolicy::AlwaysConfirm,
+            delete: ConfirmPolicy::AlwaysConfirm,
+            archive_clean: ConfirmPolicy::AlwaysConfirm,
+            // Picking a cleanup option at the end of exam mode is already a choice
+            exam_end: ConfirmPolicy::NeverConfirm,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ReminderSchedule {
     Never,
@@ -66,14 +355,120 @@ pub struct ExamTrackingState {
     pub end_date: Option<String>,
     pub tracked_files: Vec<PathBuf>,
     pub exam_period_name: Option<String>,
+    /// What each tracked file looked like when first tracked, so post-exam
+    /// cleanup only removes those exact files
+    #[serde(default)]
+    pub fingerprints: HashMap<PathBuf, FileFingerprint>,
+    /// When each file was first tracked, to spot files edited during the exam
+    #[serde(default)]
+    pub added_dates: HashMap<PathBuf, DateTime<Utc>>,
+    /// Size, type, course and category of each tracked file, so `exam status`
+    /// stays accurate after a restart (missing in older configs)
+    #[serde(default)]
+    pub details: HashMap<PathBuf, TrackedFileDetails>,
+}
+
+impl ExamTrackingState {
+    /// Stop tracking the files `matches` picks; returns how many
+    pub fn untrack(&mut self, matches: impl Fn(&Path) -> bool) -> usize {
+        let before = self.tracked_files.len();
+        self.tracked_files.retain(|path| !matches(path));
+        self.fingerprints.retain(|path, _| !matches(path));
+        self.added_dates.retain(|path, _| !matches(path));
+        self.details.retain(|path, _| !matches(path));
+        before - self.tracked_files.len()
+    }
+}
+
+/// What the scan knew about a tracked file when it was added
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackedFileDetails {
+    pub size_bytes: u64,
+    pub file_type: String,
+    pub course: String,
+    pub category: crate::exam::FileCategory,
+}
+
+/// Size and modification time of a file at tracking time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileFingerprint {
+    pub size_bytes: u64,
+    pub modified: DateTime<Utc>,
+}
+
+impl FileFingerprint {
+    /// Fingerprint a file as it is on disk now
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            size_bytes: metadata.len(),
+            modified: metadata.modified().ok()?.into(),
+        })
+    }
+}
+
+/// Points at a config file to use instead of `~/.cleancrush.json`
+pub const CONFIG_ENV: &str = "CLEANCRUSH_CONFIG";
+
+fn config_override() -> Option<PathBuf> {
+    std::env::var_os(CONFIG_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Set to `1` to never prompt during setup (CI, containers, scripts)
+pub const NONINTERACTIVE_ENV: &str = "CLEANCRUSH_NONINTERACTIVE";
+
+/// Whether setup has to run without prompts: `CLEANCRUSH_NONINTERACTIVE` is
+/// set, or stdin isn't a terminal so nobody could answer the wizard
+pub fn non_interactive() -> bool {
+    let forced = std::env::var(NONINTERACTIVE_ENV)
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"));
+    forced || !std::io::stdin().is_terminal()
+}
+
+/// Folder CleanCrush keeps its files under: the home directory, or when there
+/// is none (sandboxes, CI) the folder holding `CLEANCRUSH_CONFIG`, or a
+/// `cleancrush` folder in the temp directory
+pub fn base_dir() -> PathBuf {
+    if let Some(home) = dirs::home_dir() {
+        return home;
+    }
+    
+    let base = fallback_base_dir(config_override());
+    
+    static WARN_ONCE: std::sync::Once = std::sync::Once::new();
+    WARN_ONCE.call_once(|| {
+        eprintln!("{} No home directory found - keeping CleanCrush files in {}", 
+            "⚠️".yellow(), base.display());
+        let _ = fs::create_dir_all(&base);
+    });
+    base
+}
+
+/// Base folder without a home directory, given the `CLEANCRUSH_CONFIG` override
+fn fallback_base_dir(config_override: Option<PathBuf>) -> PathBuf {
+    config_override
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| std::env::temp_dir().join("cleancrush"))
 }
 
 impl Config {
-    /// Get the path to the config file
+    /// Get the path to the config file (`CLEANCRUSH_CONFIG` wins if set)
     pub fn config_path() -> Result<PathBuf> {
-        let home = dirs::home_dir()
-            .context("Could not find home directory")?;
-        Ok(home.join(".cleancrush.json"))
+        if let Some(path) = config_override() {
+            return Ok(path);
+        }
+        Ok(base_dir().join(".cleancrush.json"))
+    }
+    
+    /// Get the path to a state file kept next to the config file
+    pub fn state_path(file_name: &str) -> Result<PathBuf> {
+        let config_path = Self::config_path()?;
+        let dir = config_path.parent()
+            .context("Config path has no parent directory")?;
+        Ok(dir.join(file_name))
     }
     
     /// Get the path to the config backup file
@@ -91,26 +486,43 @@ impl Config {
             let data = fs::read_to_string(&config_path)
                 .context("Failed to read config file")?;
             
-            match serde_json::from_str(&data) {
-                Ok(config) => Ok(config),
+            match serde_json::from_str::<Self>(&data) {
+                Ok(mut config) => {
+                    log::debug!("Loaded config from {}", config_path.display());
+                    config.resolve_protected_folders();
+                    Ok(config)
+                }
                 Err(e) => {
+                    log::warn!("Config {} failed to parse: {}", config_path.display(), e);
+                    
+                    // A bad gamification section shouldn't cost the user their preferences
+                    if let Some(config) = Self::recover_gamification(&data) {
+                        eprintln!("{} Progress data was corrupted and has been reset", "⚠️".yellow());
+                        eprintln!("   Run {} to check for other problems", "cleancrush doctor".bold());
+                        return Ok(config);
+                    }
+
                     // Config is corrupted, try backup
                     eprintln!("{} Config corrupted, trying backup...", "⚠️".yellow());
                     if let Ok(backup) = Self::load_backup() {
+                        log::warn!("Config restored from backup");
                         eprintln!("{} Restored from backup", "✅".green());
                         return Ok(backup);
                     }
                     Err(e.into())
                 }
             }
+        } else if non_interactive() {
+            // Nobody to answer the wizard, so don't hang waiting for input
+            let config = Self::defaults();
+            config.save()?;
+            log::info!("No config found, saved defaults to {}", config_path.display());
+            eprintln!("{} No config found - saved default settings to {}", "ℹ️".cyan(), config_path.display());
+            eprintln!("   Run {} in a terminal to choose your own", "cleancrush init --force".bold());
+            Ok(config)
         } else {
             // No config exists, run first-time setup
-            println!("{}", "=".repeat(60).color(colors::HEADER));
-            println!("{}", "   🧹 CLEANCRUSH - FIRST TIME SETUP   ".bold());
-            println!("{}", "=".repeat(60).color(colors::HEADER));
-            println!();
-            
-            let config = Self::run_first_time_wizard()?;
+            let config = Self::first_time_setup()?;
             config.save()?;
             
             println!();
@@ -122,6 +534,128 @@ impl Config {
         }
     }
     
+    /// Settings used when setup can't ask: Recycle Bin, no protected folders,
+    /// weekly reminders, exam monitoring on
+    pub fn defaults() -> Self {
+        Config {
+            default_action: CleanupAction::RecycleBin,
+            protected_folders: Vec::new(),
+            reminder_schedule: ReminderSchedule::Weekly,
+            enable_exam_monitoring: true,
+            last_cleanup: None,
+            last_reminder: None,
+            exam_tracking: None,
+            streaks: 0,
+            achievements: Vec::new(),
+            total_files_cleaned: 0,
+            total_space_freed_mb: 0,
+            best_cleanup_mb: 0,
+            course_overrides: Vec::new(),
+            confidence_model: ConfidenceModel::default(),
+            preselect_confidence: DEFAULT_PRESELECT_CONFIDENCE,
+            confidence_tiers: DEFAULT_CONFIDENCE_TIERS,
+            tiny_file_kb: DEFAULT_TINY_FILE_KB,
+            very_large_mb: DEFAULT_VERY_LARGE_MB,
+            compress_archives: false,
+            default_scan_paths: Vec::new(),
+            confirm_defaults: ConfirmDefaults::default(),
+            never_suggest: Vec::new(),
+            exam_ignore: Vec::new(),
+            courses: default_courses(),
+        }
+    }
+    
+    /// Large files over this many bytes count as very large in the score
+    pub fn very_large_bytes(&self) -> u64 {
+        self.very_large_mb.saturating_mul(1024 * 1024)
+    }
+    
+    /// Tier of a confidence score: "high" above the first `confidence_tiers`
+    /// cutoff, "medium" above the second, "low" otherwise
+    pub fn confidence_level(&self, confidence: f32) -> &'static str {
+        // Tolerate the cutoffs being given the other way round
+        let (a, b) = self.confidence_tiers;
+        let (high, medium) = (a.max(b), a.min(b));
+        
+        if confidence > high {
+            "high"
+        } else if confidence > medium {
+            "medium"
+        } else {
+            "low"
+        }
+    }
+    
+    /// Color for a confidence score's tier
+    pub fn confidence_color(&self, confidence: f32) -> Color {
+        match self.confidence_level(confidence) {
+            "high" => colors::HIGH_CONFIDENCE,
+            "medium" => colors::MEDIUM_CONFIDENCE,
+            _ => colors::LOW_CONFIDENCE,
+        }
+    }
+    
+    /// Show the setup banner and run the wizard (nothing is saved)
+    pub fn first_time_setup() -> Result<Self> {
+        println!("{}", "=".repeat(60).color(colors::HEADER));
+        println!("{}", "   🧹 CLEANCRUSH - FIRST TIME SETUP   ".bold());
+        println!("{}", "=".repeat(60).color(colors::HEADER));
+        println!();
+        
+        Self::run_first_time_wizard()
+    }
+    
+    /// Carry streaks, achievements and cleanup totals over from `old`, e.g.
+    /// when the settings are reset
+    pub fn keep_progress_from(&mut self, old: &Config) {
+        self.streaks = old.streaks;
+        self.achievements = old.achievements.clone();
+        self.total_files_cleaned = old.total_files_cleaned;
+        self.total_space_freed_mb = old.total_space_freed_mb;
+        self.best_cleanup_mb = old.best_cleanup_mb;
+        self.last_cleanup = old.last_cleanup.clone();
+    }
+    
+    /// Zero streaks and cleanup totals for a fresh start, clearing
+    /// achievements too unless `keep_achievements`
+    pub fn reset_progress(&mut self, keep_achievements: bool) {
+        self.streaks = 0;
+        self.total_files_cleaned = 0;
+        self.total_space_freed_mb = 0;
+        self.best_cleanup_mb = 0;
+        if !keep_achievements {
+            self.achievements.clear();
+        }
+    }
+    
+    /// Try to parse a config whose only damage is in the gamification fields
+    fn recover_gamification(data: &str) -> Option<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(data).ok()?;
+        let object = value.as_object_mut()?;
+
+        let streaks_ok = object.get("streaks")
+            .and_then(|v| v.as_u64())
+            .is_some_and(|v| v <= u32::MAX as u64);
+        if !streaks_ok {
+            object.insert("streaks".to_string(), serde_json::json!(0));
+        }
+
+        let achievements_ok = object.get("achievements")
+            .and_then(|v| v.as_array())
+            .is_some_and(|list| list.iter().all(|a| a.is_string()));
+        if !achievements_ok {
+            object.insert("achievements".to_string(), serde_json::json!([]));
+        }
+
+        for key in ["total_files_cleaned", "total_space_freed_mb"] {
+            if object.get(key).and_then(|v| v.as_u64()).is_none() {
+                object.insert(key.to_string(), serde_json::json!(0));
+            }
+        }
+
+        serde_json::from_value(value).ok()
+    }
+
     /// Load config from backup file
     fn load_backup() -> Result<Self> {
         let backup_path = Self::backup_path()?;
@@ -155,6 +689,7 @@ impl Config {
         // Atomically rename temp file to final location
         fs::rename(&temp_path, &config_path)
             .context("Failed to finalize config")?;
+        log::debug!("Saved config to {}", config_path.display());
         
         Ok(())
     }
@@ -179,23 +714,55 @@ impl Config {
         
         println!();
         
-        // 2. Folder protection
-        println!("{}", "2. FOLDER PROTECTION".bold());
-        println!("Which folders contain personal files?");
+        // 2. Cluttered folders
+        println!("{}", "2. CLUTTERED FOLDERS".bold());
+        println!("Which folders get cluttered? (scanned when you don't give a path)");
         
-        let mut default_folders = vec![
-            (dirs::home_dir().unwrap().join("Documents"), false),
-            (dirs::home_dir().unwrap().join("Desktop").join("Personal"), false),
-            (dirs::home_dir().unwrap().join("Pictures"), false),
-            (dirs::home_dir().unwrap().join("Projects"), false),
-        ];
+        let clutter_candidates: Vec<PathBuf> = [dirs::download_dir(), dirs::desktop_dir()]
+            .into_iter()
+            .flatten()
+            .collect();
+        let clutter_names: Vec<String> = clutter_candidates.iter()
+            .map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| p.display().to_string()))
+            .collect();
+        let clutter_defaults: Vec<bool> = (0..clutter_candidates.len()).map(|i| i == 0).collect();
         
-        let selections = MultiSelect::with_theme(&theme)
-            .items(&["Documents", "Desktop/Personal", "Pictures", "Projects"])
+        let clutter_selections = MultiSelect::with_theme(&theme)
+            .items(&clutter_names)
+            .defaults(&clutter_defaults)
             .interact()?;
         
-        for &idx in &selections {
-            default_folders[idx].1 = true;
+        let default_scan_paths: Vec<PathBuf> = clutter_selections.iter()
+            .map(|&idx| clutter_candidates[idx].clone())
+            .collect();
+        
+        println!();
+        
+        // 3. Folder protection
+        println!("{}", "3. FOLDER PROTECTION".bold());
+        println!("Which folders contain personal files?");
+        
+        // Without a home directory there are no usual folders to suggest
+        let mut default_folders = match dirs::home_dir() {
+            Some(home) => vec![
+                (home.join("Documents"), false),
+                (home.join("Desktop").join("Personal"), false),
+                (home.join("Pictures"), false),
+                (home.join("Projects"), false),
+            ],
+            None => Vec::new(),
+        };
+        
+        if default_folders.is_empty() {
+            println!("{} No home directory found - add folders by path instead", "ℹ️".cyan());
+        } else {
+            let selections = MultiSelect::with_theme(&theme)
+                .items(&["Documents", "Desktop/Personal", "Pictures", "Projects"])
+                .interact()?;
+            
+            for &idx in &selections {
+                default_folders[idx].1 = true;
+            }
         }
         
         // Ask for custom folders
@@ -224,8 +791,8 @@ impl Config {
         
         println!();
         
-        // 3. Protection type
-        println!("{}", "3. PROTECTION TYPE".bold());
+        // 4. Protection type
+        println!("{}", "4. PROTECTION TYPE".bold());
         let protection_items = &[
             "Hard - Never scan protected folders",
             "Soft - Scan but warn before any action",
@@ -243,8 +810,8 @@ impl Config {
         
         println!();
         
-        // 4. Exam monitoring
-        println!("{}", "4. EXAM MONITORING".bold());
+        // 5. Exam monitoring
+        println!("{}", "5. EXAM MONITORING".bold());
         let enable_monitoring = Confirm::with_theme(&theme)
             .with_prompt("Monitor Downloads/Desktop for exam periods?")
             .default(true)
@@ -256,8 +823,8 @@ impl Config {
         
         println!();
         
-        // 5. Reminder schedule
-        println!("{}", "5. REMINDER SCHEDULE".bold());
+        // 6. Reminder schedule
+        println!("{}", "6. REMINDER SCHEDULE".bold());
         let reminder_items = &["Never", "Weekly (Sundays)", "Monthly (1st of month)"];
         let reminder_idx = Select::with_theme(&theme)
             .items(reminder_items)
@@ -276,8 +843,9 @@ impl Config {
             .into_iter()
             .filter(|(_, selected)| *selected)
             .map(|(path, _)| ProtectedFolder {
-                path,
+                path: path.canonicalize().unwrap_or(path),
                 protection_type: protection_type.clone(),
+                glob: false,
             })
             .collect();
         
@@ -286,24 +854,101 @@ impl Config {
             protected_folders,
             reminder_schedule,
             enable_exam_monitoring: enable_monitoring,
-            last_cleanup: None,
-            last_reminder: None,
-            exam_tracking: None,
-            streaks: 0,
-            achievements: Vec::new(),
-            total_files_cleaned: 0,
-            total_space_freed_mb: 0,
+            default_scan_paths,
+            ..Self::defaults()
         })
     }
     
-    /// Check if a path is protected
+    /// Folders to scan when no path is given: the configured cluttered
+    /// folders, falling back to Downloads (or the current directory)
+    pub fn scan_paths(&self) -> Vec<PathBuf> {
+        if !self.default_scan_paths.is_empty() {
+            return self.default_scan_paths.clone();
+        }
+        vec![dirs::download_dir().unwrap_or_else(|| PathBuf::from("."))]
+    }
+    
+    /// The main folder to use when a command needs a single default
+    pub fn primary_scan_path(&self) -> PathBuf {
+        self.scan_paths().remove(0)
+    }
+    
+    /// Check if a path is protected. A hard match wins over a soft one;
+    /// otherwise exact folders win over patterns. Relative paths and
+    /// symlinked folders are resolved first.
     pub fn is_protected(&self, path: &Path) -> Option<&ProtectedFolder> {
-        for protected in &self.protected_folders {
-            if path.starts_with(&protected.path) {
-                return Some(protected);
+        if self.protected_folders.is_empty() {
+            return None;
+        }
+        
+        let path = resolved_location(path);
+        let mut matches = self.protected_folders.iter()
+            .filter(|p| !p.glob)
+            .chain(self.protected_folders.iter().filter(|p| p.glob))
+            .filter(|p| p.matches(&path));
+        let first = matches.next()?;
+        if matches!(first.protection_type, ProtectionType::Hard) {
+            return Some(first);
+        }
+        matches.find(|p| matches!(p.protection_type, ProtectionType::Hard))
+            .or(Some(first))
+    }
+    
+    /// Store protected folders the way `protect add` does: canonical, so
+    /// entries written by hand or by older versions still match scanned
+    /// paths. Folders that don't exist (yet) are kept as written.
+    fn resolve_protected_folders(&mut self) {
+        for protected in self.protected_folders.iter_mut().filter(|p| !p.glob) {
+            if let Ok(canonical) = protected.path.canonicalize() {
+                protected.path = canonical;
             }
         }
-        None
+    }
+    
+    /// Course the user assigned to this file, if any. Exact paths win over
+    /// globs; among globs the most recently added wins.
+    pub fn course_override(&self, path: &Path) -> Option<&str> {
+        if self.course_overrides.is_empty() {
+            return None;
+        }
+        
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.course_overrides.iter()
+            .filter(|o| !o.is_glob())
+            .chain(self.course_overrides.iter().rev().filter(|o| o.is_glob()))
+            .find(|o| o.matches(&path))
+            .map(|o| o.course.as_str())
+    }
+    
+    /// The `never_suggest` entry covering a file, if any. Bare names and globs
+    /// (`resume.pdf`, `transcript*`) match the file name, case-insensitively;
+    /// entries with a separator match the whole path.
+    pub fn never_suggest_match(&self, path: &Path) -> Option<&str> {
+        let options = glob::MatchOptions {
+            case_sensitive: false,
+            ..glob::MatchOptions::new()
+        };
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        
+        self.never_suggest.iter()
+            .find(|entry| {
+                let Ok(pattern) = glob::Pattern::new(entry) else {
+                    return false;
+                };
+                if entry.contains(['/', '\\']) {
+                    pattern.matches_path_with(path, options)
+                } else {
+                    pattern.matches_with(&name, options)
+                }
+            })
+            .map(String::as_str)
+    }
+    
+    /// The `exam_ignore` entry covering a file, if any
+    pub fn exam_ignore_match(&self, path: &Path) -> Option<&str> {
+        self.exam_ignore.iter()
+            .find(|pattern| exam_ignore_matches(pattern, path))
+            .map(String::as_str)
     }
     
     /// Check if a path is a system path
@@ -343,27 +988,11 @@ impl Config {
         }
     }
     
-    /// Increment streak counter
-    pub fn increment_streak(&mut self) {
-        self.streaks += 1;
-        
-        // Check for streak achievements
-        if self.streaks == 1 {
-            self.add_achievement("🧹 First Sweep");
-        } else if self.streaks >= 21 { // 3 weeks
-            self.add_achievement("📆 Consistency Cutie");
-        }
-    }
-    
     /// Update statistics after cleanup
     pub fn update_stats(&mut self, files_cleaned: usize, space_freed_bytes: u64) {
         self.total_files_cleaned += files_cleaned as u64;
         self.total_space_freed_mb += space_freed_bytes / (1024 * 1024);
-        
-        // Increment streak if criteria met (from blueprint)
-        if files_cleaned >= 5 || space_freed_bytes >= 50 * 1024 * 1024 {
-            self.increment_streak();
-        }
+        self.best_cleanup_mb = self.best_cleanup_mb.max(space_freed_bytes / (1024 * 1024));
         
         // Check for achievements
         if self.total_files_cleaned >= 10 {
@@ -395,6 +1024,10 @@ impl Config {
             CleanupAction::RecycleBin => "Move to Recycle Bin",
             CleanupAction::Archive => "Archive to organized folders",
         });
+        if matches!(self.default_action, CleanupAction::Archive) {
+            println!("{} Archive compression: {}", "•".cyan(),
+                if self.compress_archives { "Zip each archive" } else { "Off (plain files)" });
+        }
         
         println!("{} Exam monitoring: {}", "•".cyan(), 
             if self.enable_exam_monitoring { "Enabled" } else { "Disabled" });
@@ -405,6 +1038,43 @@ impl Config {
             ReminderSchedule::Monthly => "Monthly (1st)",
         });
         
+        println!("{} Confidence model: {}", "•".cyan(), match self.confidence_model {
+            ConfidenceModel::Max => "Max (strongest signal)",
+            ConfidenceModel::Combined => "Combined (signals compound)",
+        });
+        
+        println!("{} Interactive pre-select: confidence ≥ {:.2}", "•".cyan(), self.preselect_confidence);
+        let (high, medium) = self.confidence_tiers;
+        println!("{} Confidence colors: {} above {:.2}, {} above {:.2}", "•".cyan(),
+            "high".color(colors::HIGH_CONFIDENCE), high,
+            "medium".color(colors::MEDIUM_CONFIDENCE), medium);
+        if self.tiny_file_kb > 0 {
+            println!("{} Tiny files: under {} KB capped at confidence {:.2}", "•".cyan(), self.tiny_file_kb, crate::scanner::TINY_FILE_CONFIDENCE);
+        }
+        println!("{} Very large files: over {} MB (cost more in the score)", "•".cyan(), self.very_large_mb);
+        
+        if !self.never_suggest.is_empty() {
+            println!("{} Never suggested: {}", "•".cyan(), self.never_suggest.join(", "));
+        }
+        if !self.exam_ignore.is_empty() {
+            println!("{} Never exam-tracked: {}", "•".cyan(), self.exam_ignore.join(", "));
+        }
+        
+        println!("{} Confirmation: clean {}, delete {}, archive clean {}, exam end {}", "•".cyan(),
+            self.confirm_defaults.clean,
+            self.confirm_defaults.delete,
+            self.confirm_defaults.archive_clean,
+            self.confirm_defaults.exam_end);
+        
+        println!();
+        println!("{} Default scan folders ({}):", "•".cyan(), self.default_scan_paths.len());
+        if self.default_scan_paths.is_empty() {
+            println!("  - Downloads (not configured)");
+        }
+        for path in &self.default_scan_paths {
+            println!("  - {}", path.display());
+        }
+        
         println!();
         println!("{} Protected folders ({}):", "•".cyan(), self.protected_folders.len());
         for protected in &self.protected_folders {
@@ -412,15 +1082,137 @@ impl Config {
                 ProtectionType::Hard => "Hard (never scan)",
                 ProtectionType::Soft => "Soft (scan but warn)",
             };
-            println!("  - {} ({})", protected.path.display(), protection_type);
+            println!("  - {}{} ({})", 
+                protected.path.display(),
+                if protected.glob { " [pattern]" } else { "" },
+                protection_type);
+        }
+        
+        println!();
+        println!("{} Courses ({}):", "•".cyan(), self.courses.len());
+        for (course, keywords) in &self.courses {
+            println!("  - {}: {}", course, keywords.join(", "));
+        }
+        
+        if !self.course_overrides.is_empty() {
+            println!();
+            println!("{} Course overrides ({}):", "•".cyan(), self.course_overrides.len());
+            for course_override in &self.course_overrides {
+                println!("  - {} → {}", course_override.pattern, course_override.course);
+            }
         }
         
         if let Some(last) = &self.last_cleanup {
             println!("{} Last cleanup: {}", "•".cyan(), last);
         }
         
-        println!("{} Current streak: {} days", "•".cyan(), self.streaks);
+        println!("{} Current streak: {}", "•".cyan(), 
+            StreakWindow::for_schedule(&self.reminder_schedule).describe(self.streaks));
         println!("{} Total files cleaned: {}", "•".cyan(), self.total_files_cleaned);
         println!("{} Total space freed: {:.1} MB", "•".cyan(), self.total_space_freed_mb);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn folded_match_ignores_case_and_trailing_slashes() {
+        let file = Path::new("c:/users/me/documents/a.pdf");
+        assert!(starts_with_folded(file, Path::new("C:/Users/Me/Documents")));
+        assert!(starts_with_folded(file, Path::new("C:/Users/Me/Documents/")));
+        assert!(!starts_with_folded(file, Path::new("C:/Users/Me/Docs")));
+        assert!(!starts_with_folded(Path::new("c:/users"), Path::new("C:/Users/Me")));
+    }
+    
+    #[test]
+    fn trailing_slash_on_the_folder_does_not_matter() {
+        let file = Path::new("/home/me/Docs/a.pdf");
+        assert!(path_starts_with(file, Path::new("/home/me/Docs/")));
+        assert!(path_starts_with(file, Path::new("/home/me/Docs")));
+        assert!(!path_starts_with(Path::new("/home/me/Docs2/a.pdf"), Path::new("/home/me/Docs")));
+    }
+    
+    fn protected(path: &str, protection_type: ProtectionType, glob: bool) -> ProtectedFolder {
+        ProtectedFolder { path: PathBuf::from(path), protection_type, glob }
+    }
+    
+    #[test]
+    fn exact_folders_come_before_patterns_unless_a_pattern_is_hard() {
+        let mut config = Config::defaults();
+        let file = Path::new("/home/me/Docs/Personal/cv.pdf");
+        
+        // Both soft: the exact folder is the one reported
+        config.protected_folders = vec![
+            protected("Personal", ProtectionType::Soft, true),
+            protected("/home/me/Docs", ProtectionType::Soft, false),
+        ];
+        assert!(!config.is_protected(file).unwrap().glob);
+        
+        // A hard pattern beats a soft exact folder
+        config.protected_folders[0].protection_type = ProtectionType::Hard;
+        let found = config.is_protected(file).unwrap();
+        assert!(found.glob);
+        assert!(matches!(found.protection_type, ProtectionType::Hard));
+        
+        // A hard exact folder beats a soft pattern
+        config.protected_folders[0].protection_type = ProtectionType::Soft;
+        config.protected_folders[1].protection_type = ProtectionType::Hard;
+        assert!(!config.is_protected(file).unwrap().glob);
+    }
+    
+    #[test]
+    fn patterns_cover_folders_anywhere_including_new_ones() {
+        let mut config = Config::defaults();
+        config.protected_folders = vec![
+            protected("*/private/*", ProtectionType::Hard, true),
+            protected("Personal", ProtectionType::Hard, true),
+        ];
+        assert!(config.is_protected(Path::new("/home/me/later/private/diary.txt")).is_some());
+        assert!(config.is_protected(Path::new("/home/me/personal/taxes.pdf")).is_some());
+        assert!(config.is_protected(Path::new("/home/me/Personality/quiz.pdf")).is_none());
+        assert!(config.is_protected(Path::new("/home/me/Docs/notes.pdf")).is_none());
+    }
+    
+    #[test]
+    fn missing_home_falls_back_to_the_config_folder_or_temp() {
+        assert_eq!(fallback_base_dir(Some(PathBuf::from("/ci/state/cleancrush.json"))),
+            PathBuf::from("/ci/state"));
+        // A bare file name has no folder to use
+        assert_eq!(fallback_base_dir(Some(PathBuf::from("cleancrush.json"))),
+            std::env::temp_dir().join("cleancrush"));
+        assert_eq!(fallback_base_dir(None), std::env::temp_dir().join("cleancrush"));
+    }
+    
+    #[test]
+    fn confidence_models_combine_signals() {
+        let signals = [0.8, 0.5];
+        assert_eq!(ConfidenceModel::Max.combine(&signals), 0.8);
+        assert!((ConfidenceModel::Combined.combine(&signals) - 0.9).abs() < 1e-6);
+        assert_eq!(ConfidenceModel::Combined.combine(&[]), 0.0);
+    }
+    
+    #[test]
+    fn confidence_tiers_are_exclusive_at_the_cutoffs() {
+        let mut config = Config::defaults();
+        config.confidence_tiers = (0.7, 0.5);
+        assert_eq!(config.confidence_level(0.71), "high");
+        assert_eq!(config.confidence_level(0.7), "medium");
+        assert_eq!(config.confidence_level(0.51), "medium");
+        assert_eq!(config.confidence_level(0.5), "low");
+        
+        // Cutoffs given the other way round mean the same tiers
+        config.confidence_tiers = (0.5, 0.7);
+        assert_eq!(config.confidence_level(0.7), "medium");
+        assert_eq!(config.confidence_color(0.71), colors::HIGH_CONFIDENCE);
+    }
+    
+    #[cfg(windows)]
+    #[test]
+    fn drive_letters_match_in_any_case() {
+        let file = Path::new(r"c:\users\me\documents\a.pdf");
+        assert!(path_starts_with(file, Path::new(r"C:\Users\Me\Documents")));
+        assert!(path_starts_with(file, Path::new(r"C:\Users\Me\Documents\")));
+    }
+}
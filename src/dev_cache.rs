@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use colored::*;
+use anyhow::Result;
+use crate::colors;
+use crate::archive::ArchiveSystem;
+use crate::cli::format_size;
+use crate::config::{Config, ProtectionType};
+use crate::exam::ExamManager;
+use crate::installers::SweepSkips;
+
+/// Folders Jupyter and Python fill with regenerated copies
+const CACHE_DIRS: &[&str] = &[".ipynb_checkpoints", "__pycache__"];
+
+/// Compiled Python bytecode, rebuilt from the `.py` on the next import
+const BYTECODE_EXTENSIONS: &[&str] = &["pyc", "pyo"];
+
+/// Confidence for cache artifacts - they are rebuilt automatically
+pub const CACHE_CONFIDENCE: f32 = 0.98;
+
+/// What kind of clutter a cache folder holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    /// `.ipynb_checkpoints/*-checkpoint.*` autosaves
+    Checkpoints,
+    /// `__pycache__` folders and stray `.pyc` files
+    Bytecode,
+}
+
+impl CacheKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CacheKind::Checkpoints => "Jupyter checkpoints",
+            CacheKind::Bytecode => "Python bytecode",
+        }
+    }
+}
+
+/// Cache artifacts found in one folder
+#[derive(Debug, Clone)]
+pub struct CacheFolder {
+    pub path: PathBuf,
+    pub kind: CacheKind,
+    pub files: Vec<PathBuf>,
+    pub size_bytes: u64,
+    pub soft_protected: bool,
+}
+
+/// Whether a folder is a Jupyter or Python cache folder
+pub fn is_cache_dir(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| CACHE_DIRS.contains(&name))
+}
+
+/// Recognize a cache artifact. Only checkpoint copies inside
+/// `.ipynb_checkpoints` and bytecode files count - real notebooks and `.py`
+/// sources never do, wherever they are.
+pub fn cache_kind(path: &Path) -> Option<CacheKind> {
+    let extension = path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if BYTECODE_EXTENSIONS.contains(&extension.as_str()) {
+        return Some(CacheKind::Bytecode);
+    }
+
+    let in_checkpoints = path.parent()
+        .and_then(|parent| parent.file_name())
+        .is_some_and(|name| name == ".ipynb_checkpoints");
+    let is_checkpoint = path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| stem.ends_with("-checkpoint"));
+    if in_checkpoints && is_checkpoint {
+        return Some(CacheKind::Checkpoints);
+    }
+
+    None
+}
+
+/// Virtual environments manage their own caches
+fn is_virtualenv(path: &Path) -> bool {
+    path.join("pyvenv.cfg").is_file()
+}
+
+/// Find Jupyter checkpoints and Python bytecode under `root`, grouped by folder
+pub fn find_caches(
+    root: &Path,
+    config: &Config,
+    exam_manager: &ExamManager,
+) -> Result<(Vec<CacheFolder>, SweepSkips)> {
+    let archive_system = ArchiveSystem::new(config.clone())?;
+    let archive_roots = crate::archive::archive_roots();
+    let mut skips = SweepSkips::default();
+    let mut by_folder: BTreeMap<PathBuf, CacheFolder> = BTreeMap::new();
+
+    let walker = WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let path = e.path();
+            !(e.file_type().is_dir() && (is_virtualenv(path) || path.file_name().is_some_and(|n| n == ".git")))
+                && !archive_roots.iter().any(|root| path.starts_with(root))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+
+    for entry in walker {
+        let path = entry.path();
+        let Some(kind) = cache_kind(path) else {
+            continue;
+        };
+        let Some(folder) = path.parent() else {
+            continue;
+        };
+
+        // Never touch exam material, cloud-synced copies or hard-protected folders
+        if exam_manager.is_tracked(path) {
+            skips.exam_tracked += 1;
+            continue;
+        }
+        if archive_system.is_in_cloud_folder(path) {
+            skips.in_cloud += 1;
+            continue;
+        }
+        let soft_protected = match config.is_protected(path) {
+            Some(protected) if matches!(protected.protection_type, ProtectionType::Hard) => {
+                skips.protected += 1;
+                continue;
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        let size = fs::symlink_metadata(path).map(|m| m.len()).unwrap_or(0);
+        let group = by_folder.entry(folder.to_path_buf()).or_insert_with(|| CacheFolder {
+            path: folder.to_path_buf(),
+            kind,
+            files: Vec::new(),
+            size_bytes: 0,
+            soft_protected: false,
+        });
+        group.files.push(path.to_path_buf());
+        group.size_bytes += size;
+        group.soft_protected |= soft_protected;
+    }
+
+    // Biggest first - they're the most worthwhile
+    let mut folders: Vec<CacheFolder> = by_folder.into_values().collect();
+    folders.sort_by_key(|f| std::cmp::Reverse(f.size_bytes));
+
+    Ok((folders, skips))
+}
+
+/// Remove cache folders the sweep emptied. Folders still holding anything
+/// (files the sweep didn't recognize) are left alone.
+pub fn remove_empty_cache_dirs(folders: &[CacheFolder]) -> usize {
+    folders.iter()
+        .filter(|folder| is_cache_dir(&folder.path))
+        .filter(|folder| fs::remove_dir(&folder.path).is_ok())
+        .count()
+}
+
+/// Print the sweep results
+pub fn print_caches(folders: &[CacheFolder], skips: &SweepSkips) {
+    println!();
+    println!("{}", "🐍 NOTEBOOK & PYTHON CACHES".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+
+    if folders.is_empty() {
+        println!("{} No Jupyter checkpoints or Python caches found ✨", "✨".green());
+    } else {
+        let total: u64 = folders.iter().map(|f| f.size_bytes).sum();
+        let files: usize = folders.iter().map(|f| f.files.len()).sum();
+        println!("{} files in {} folders, {} - rebuilt automatically, safe to clear",
+            files.to_string().color(colors::SUCCESS),
+            folders.len(),
+            format_size(total).color(colors::SUCCESS));
+        println!("{} Your notebooks and .py files are never touched", "🛡️".cyan());
+        println!();
+
+        for (i, folder) in folders.iter().enumerate() {
+            println!("{:3}. [{}{:.2}{}] {} ({}, {} file{}, {})",
+                i + 1,
+                "⚡".color(colors::HIGH_CONFIDENCE),
+                CACHE_CONFIDENCE,
+                "⚡".color(colors::SUCCESS),
+                folder.path.display().to_string().color(colors::PATH),
+                folder.kind.label(),
+                folder.files.len(),
+                if folder.files.len() == 1 { "" } else { "s" },
+                format_size(folder.size_bytes));
+            if folder.soft_protected {
+                println!("     {} Protected folder (soft)", "🛡️".blue());
+            }
+        }
+    }
+
+    if skips.exam_tracked > 0 {
+        println!("{} {} skipped (tracked for exams)", "🎓".cyan(), skips.exam_tracked);
+    }
+    if skips.in_cloud > 0 {
+        println!("{} {} skipped (in cloud folders)", "☁️".cyan(), skips.in_cloud);
+    }
+    if skips.protected > 0 {
+        println!("{} {} skipped (hard protected)", "🛡️".cyan(), skips.protected);
+    }
+}
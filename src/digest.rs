@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use colored::*;
+use anyhow::{Result, Context};
+use crate::colors;
+use crate::cli::format_size;
+use crate::config::Config;
+
+const DIGEST_FILE: &str = ".cleancrush-digest.json";
+
+/// Shallow walk limits, so the reminder stays fast
+const DIGEST_MAX_DEPTH: usize = 3;
+const DIGEST_MAX_FILES: usize = 20_000;
+
+/// Folder size snapshot used to measure clutter growth between cleanups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderSnapshot {
+    pub root: PathBuf,
+    pub taken: DateTime<Utc>,
+    pub total_files: usize,
+    pub total_bytes: u64,
+    /// Bytes per top-level subfolder ("." for files directly in the root)
+    pub folders: BTreeMap<String, u64>,
+}
+
+impl FolderSnapshot {
+    /// Measure a folder without hashing or reading file contents
+    pub fn take(root: &Path) -> Self {
+        let mut snapshot = Self {
+            root: root.to_path_buf(),
+            taken: Utc::now(),
+            total_files: 0,
+            total_bytes: 0,
+            folders: BTreeMap::new(),
+        };
+
+        let walker = WalkDir::new(root)
+            .max_depth(DIGEST_MAX_DEPTH)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .take(DIGEST_MAX_FILES);
+
+        for entry in walker {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let folder = match entry.path().strip_prefix(root).ok().and_then(|p| p.components().next()) {
+                Some(first) if entry.depth() > 1 => first.as_os_str().to_string_lossy().to_string(),
+                _ => ".".to_string(),
+            };
+
+            snapshot.total_files += 1;
+            snapshot.total_bytes += size;
+            *snapshot.folders.entry(folder).or_insert(0) += size;
+        }
+
+        snapshot
+    }
+}
+
+/// Get the path to the digest baseline file
+fn digest_path() -> Result<PathBuf> {
+    Config::state_path(DIGEST_FILE)
+}
+
+/// Load the baseline recorded at the last cleanup
+pub fn load_baseline() -> Option<FolderSnapshot> {
+    let path = digest_path().ok()?;
+    let data = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Record the current state of a folder as the new baseline
+pub fn record_baseline(root: &Path) -> Result<FolderSnapshot> {
+    let snapshot = FolderSnapshot::take(root);
+    let path = digest_path()?;
+    let data = serde_json::to_string_pretty(&snapshot)?;
+    fs::write(&path, data)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(snapshot)
+}
+
+/// Refresh the baseline for the default folder after a cleanup, but only when
+/// that cleanup removed something from it; cleaning elsewhere shouldn't hide
+/// the folder's growth. Failures only cost a less accurate digest, so they're ignored.
+pub fn refresh_after_cleanup(cleaned: &[PathBuf]) {
+    if let Some(root) = dirs::download_dir() {
+        if cleanup_covers(&root, cleaned) {
+            let _ = record_baseline(&root);
+        }
+    }
+}
+
+/// Whether a cleanup removed anything from under `root`
+fn cleanup_covers(root: &Path, cleaned: &[PathBuf]) -> bool {
+    cleaned.iter().any(|path| crate::config::path_starts_with(path, root))
+}
+
+/// Print how much the default folder grew since the last cleanup.
+/// Returns false when there was nothing to compare against yet; a first
+/// baseline is only recorded when `record` is set, since commands that don't
+/// hold the instance lock must not write it.
+pub fn print_growth_summary(root: &Path, record: bool) -> Result<bool> {
+    let baseline = match load_baseline() {
+        Some(baseline) if baseline.root == root => baseline,
+        _ => {
+            if record {
+                record_baseline(root)?;
+            }
+            return Ok(false);
+        }
+    };
+
+    let current = FolderSnapshot::take(root);
+    let grown_bytes = current.total_bytes as i64 - baseline.total_bytes as i64;
+    let grown_files = current.total_files as i64 - baseline.total_files as i64;
+
+    let folder_name = root.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.display().to_string());
+
+    if grown_bytes <= 0 && grown_files <= 0 {
+        println!("{} {} hasn't grown since your last cleanup ✨", "📉".green(), folder_name);
+        return Ok(true);
+    }
+
+    println!("{} Your {} grew by {} and {} files since last cleanup",
+        "📈".yellow(),
+        folder_name,
+        format_size(grown_bytes.max(0) as u64).color(colors::WARNING),
+        grown_files.max(0).to_string().color(colors::WARNING));
+
+    // Folder with the biggest growth
+    let top = current.folders.iter()
+        .map(|(name, bytes)| {
+            let before = baseline.folders.get(name).copied().unwrap_or(0);
+            (name, *bytes as i64 - before as i64)
+        })
+        .filter(|(_, growth)| *growth > 0)
+        .max_by_key(|(_, growth)| *growth);
+
+    if let Some((name, growth)) = top {
+        let label = if name == "." { "(loose files)".to_string() } else { format!("{}/", name) };
+        println!("   Top offender: {} ({})",
+            label.color(colors::PATH),
+            format_size(growth as u64));
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_home;
+
+    #[test]
+    fn only_cleanups_inside_the_folder_reset_its_baseline() {
+        let root = Path::new("/home/me/Downloads");
+        let inside = PathBuf::from("/home/me/Downloads/cs/slides.pdf");
+        let elsewhere = PathBuf::from("/home/me/Desktop/slides.pdf");
+        let sibling = PathBuf::from("/home/me/Downloads-old/slides.pdf");
+
+        assert!(cleanup_covers(root, &[elsewhere.clone(), inside]));
+        assert!(!cleanup_covers(root, &[elsewhere, sibling]));
+        assert!(!cleanup_covers(root, &[]));
+    }
+
+    #[test]
+    fn read_only_reminders_never_write_the_baseline() {
+        let (_guard, _home) = isolated_home();
+        let _ = fs::remove_file(digest_path().unwrap());
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("notes.pdf"), "notes").unwrap();
+
+        assert!(!print_growth_summary(dir.path(), false).unwrap());
+        assert!(load_baseline().is_none());
+
+        assert!(!print_growth_summary(dir.path(), true).unwrap());
+        assert_eq!(load_baseline().unwrap().total_files, 1);
+        assert!(print_growth_summary(dir.path(), false).unwrap());
+    }
+}
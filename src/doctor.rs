@@ -0,0 +1,281 @@
+use std::path::PathBuf;
+use chrono::{DateTime, NaiveDate, Utc};
+use colored::*;
+use anyhow::Result;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use crate::colors;
+use crate::config::Config;
+use crate::gamification::Gamification;
+
+/// A single inconsistency found in the saved configuration
+#[derive(Debug, Clone)]
+pub enum ConfigIssue {
+    /// Achievement name that doesn't exist in the achievement set
+    OrphanAchievement(String),
+    /// Streak longer than the run of cleanup days on record
+    ImpossibleStreak { recorded: u32, supported: u32 },
+    /// Last cleanup timestamp that can't be parsed
+    InvalidLastCleanup(String),
+    /// Active exam tracking that can no longer be trusted
+    StaleExam(String),
+    /// Tracked exam files that no longer exist
+    MissingTrackedFiles(Vec<PathBuf>),
+    /// Protected folder that no longer exists
+    DanglingProtectedFolder(PathBuf),
+}
+
+impl ConfigIssue {
+    /// Describe the problem
+    pub fn describe(&self) -> String {
+        match self {
+            Self::OrphanAchievement(name) => format!("Unknown achievement '{}'", name),
+            Self::ImpossibleStreak { recorded, supported } => format!(
+                "Streak of {} is longer than your cleanup days support ({})",
+                recorded, supported
+            ),
+            Self::InvalidLastCleanup(value) => format!("Last cleanup date '{}' is invalid", value),
+            Self::StaleExam(reason) => format!("Exam tracking is stale: {}", reason),
+            Self::MissingTrackedFiles(paths) => format!(
+                "{} tracked exam file{} no longer exist{}",
+                paths.len(),
+                if paths.len() == 1 { "" } else { "s" },
+                if paths.len() == 1 { "s" } else { "" }
+            ),
+            Self::DanglingProtectedFolder(path) => format!(
+                "Protected folder no longer exists: {}", path.display()
+            ),
+        }
+    }
+
+    /// Describe what fixing the problem will do
+    pub fn fix_description(&self) -> &'static str {
+        match self {
+            Self::OrphanAchievement(_) => "Remove the unknown achievement",
+            Self::ImpossibleStreak { .. } => "Reset the streak to the supported value",
+            Self::InvalidLastCleanup(_) => "Clear the last cleanup date",
+            Self::StaleExam(_) => "End the stale exam tracking",
+            Self::MissingTrackedFiles(_) => "Stop tracking the missing files",
+            Self::DanglingProtectedFolder(_) => "Remove it from the protected list",
+        }
+    }
+
+    /// Apply the fix to the config and game state (caller saves)
+    pub fn apply(&self, config: &mut Config, gamification: &mut Gamification) {
+        match self {
+            Self::OrphanAchievement(name) => {
+                config.achievements.retain(|a| a != name);
+            }
+            Self::ImpossibleStreak { supported, .. } => {
+                config.streaks = *supported;
+                gamification.current_streak = *supported;
+            }
+            Self::InvalidLastCleanup(_) => {
+                config.last_cleanup = None;
+            }
+            Self::StaleExam(_) => {
+                if let Some(tracking) = &mut config.exam_tracking {
+                    tracking.active = false;
+                    tracking.end_date = Some(Utc::now().to_rfc3339());
+                }
+            }
+            Self::MissingTrackedFiles(paths) => {
+                if let Some(tracking) = &mut config.exam_tracking {
+                    tracking.tracked_files.retain(|p| !paths.contains(p));
+                }
+            }
+            Self::DanglingProtectedFolder(path) => {
+                config.protected_folders.retain(|p| p.glob || &p.path != path);
+            }
+        }
+    }
+}
+
+/// Streak the cleanup days on record allow, counted the way cleanups count
+/// it. None when there are no days to check against (progress saved before
+/// they were kept).
+fn supported_streak(config: &Config, gamification: &Gamification) -> Option<u32> {
+    let mut days: Vec<NaiveDate> = gamification.daily_stats.keys()
+        .filter_map(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+        .collect();
+    if days.is_empty() {
+        return config.last_cleanup.is_none().then_some(0);
+    }
+    days.sort();
+
+    let mut streak = 0;
+    let mut last = None;
+    for day in days {
+        streak = gamification.streak_window.next_streak(streak, last, day);
+        last = Some(day);
+    }
+    Some(streak)
+}
+
+/// Check config and gamification invariants
+pub fn diagnose(config: &Config, gamification: &Gamification) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    for name in &config.achievements {
+        if gamification.achievement_id(name).is_none() {
+            issues.push(ConfigIssue::OrphanAchievement(name.clone()));
+        }
+    }
+
+    let recorded = config.streaks.max(gamification.current_streak);
+    if let Some(supported) = supported_streak(config, gamification).filter(|&s| recorded > s) {
+        issues.push(ConfigIssue::ImpossibleStreak { recorded, supported });
+    }
+
+    if let Some(last) = &config.last_cleanup {
+        if last.parse::<DateTime<Utc>>().is_err() {
+            issues.push(ConfigIssue::InvalidLastCleanup(last.clone()));
+        }
+    }
+
+    if let Some(tracking) = &config.exam_tracking {
+        if tracking.active {
+            if tracking.start_date.parse::<DateTime<Utc>>().is_err() {
+                issues.push(ConfigIssue::StaleExam(
+                    format!("start date '{}' is invalid", tracking.start_date)
+                ));
+            } else if let Some(end) = tracking.end_date.as_ref()
+                .and_then(|d| d.parse::<DateTime<Utc>>().ok())
+            {
+                if end < Utc::now() {
+                    issues.push(ConfigIssue::StaleExam(
+                        format!("end date {} has passed", end.format("%Y-%m-%d"))
+                    ));
+                }
+            }
+        }
+
+        let missing: Vec<PathBuf> = tracking.tracked_files.iter()
+            .filter(|p| !p.exists())
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            issues.push(ConfigIssue::MissingTrackedFiles(missing));
+        }
+    }
+
+    // Patterns may name folders that don't exist yet
+    for protected in config.protected_folders.iter().filter(|p| !p.glob) {
+        if !protected.path.exists() {
+            issues.push(ConfigIssue::DanglingProtectedFolder(protected.path.clone()));
+        }
+    }
+
+    issues
+}
+
+/// Report problems and offer to fix each one
+pub fn run(config: &mut Config, gamification: &mut Gamification, fix_all: bool) -> Result<()> {
+    println!();
+    println!("{}", "🩺 CLEANCRUSH DOCTOR".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+
+    let issues = diagnose(config, gamification);
+
+    if issues.is_empty() {
+        println!("{} No problems found. Everything looks healthy ✨", "✅".green());
+        return Ok(());
+    }
+
+    println!("Found {} problem{}:",
+        issues.len().to_string().color(colors::WARNING),
+        if issues.len() == 1 { "" } else { "s" });
+
+    let mut fixed = 0;
+    for (i, issue) in issues.iter().enumerate() {
+        println!();
+        println!("{:3}. {} {}", i + 1, "⚠️".yellow(), issue.describe());
+        if let ConfigIssue::MissingTrackedFiles(paths) = issue {
+            for path in paths.iter().take(5) {
+                println!("     • {}", path.display().to_string().color(colors::PATH));
+            }
+            if paths.len() > 5 {
+                println!("     ... and {} more", paths.len() - 5);
+            }
+        }
+
+        let should_fix = fix_all || Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("{}?", issue.fix_description()))
+            .default(true)
+            .interact()?;
+
+        if should_fix {
+            issue.apply(config, gamification);
+            fixed += 1;
+            println!("     {} {}", "✅".green(), issue.fix_description());
+        }
+    }
+
+    if fixed > 0 {
+        config.save()?;
+        gamification.save()?;
+    }
+
+    println!();
+    println!("{} Fixed {} of {} problem{}",
+        "🩺".cyan(),
+        fixed,
+        issues.len(),
+        if issues.len() == 1 { "" } else { "s" });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamification::{CleanupType, DailyStats};
+
+    /// Game state with a cleanup on each day of June 2024 given
+    fn cleaned_on(days: &[u32]) -> Gamification {
+        let mut gamification = Gamification::new();
+        for day in days {
+            let date = format!("2024-06-{:02}", day);
+            gamification.daily_stats.insert(date.clone(), DailyStats {
+                date,
+                files_cleaned: 3,
+                space_freed_mb: 1,
+                cleanup_type: CleanupType::Normal,
+            });
+        }
+        gamification
+    }
+
+    fn streak_issue(streak: u32, gamification: &Gamification) -> Option<u32> {
+        let mut config = Config::defaults();
+        config.streaks = streak;
+        config.last_cleanup = Some("2024-06-10T12:00:00Z".to_string());
+        config.total_files_cleaned = 500;
+        diagnose(&config, gamification).into_iter().find_map(|issue| match issue {
+            ConfigIssue::ImpossibleStreak { supported, .. } => Some(supported),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn streaks_are_checked_against_cleanup_days() {
+        let consecutive = cleaned_on(&[8, 9, 10]);
+        assert_eq!(streak_issue(3, &consecutive), None);
+        // Lots of files cleaned don't make up for missing days
+        assert_eq!(streak_issue(10, &consecutive), Some(3));
+
+        // Only the run since the last gap counts
+        assert_eq!(streak_issue(3, &cleaned_on(&[5, 6, 8, 9])), Some(2));
+    }
+
+    #[test]
+    fn streaks_without_day_records_are_left_alone() {
+        // Progress from before daily records were kept can't be checked
+        assert_eq!(streak_issue(7, &Gamification::new()), None);
+
+        // No cleanup ever means no streak
+        let mut config = Config::defaults();
+        config.streaks = 2;
+        assert!(matches!(diagnose(&config, &Gamification::new())[..],
+            [ConfigIssue::ImpossibleStreak { recorded: 2, supported: 0 }]));
+    }
+}
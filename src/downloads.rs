@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use colored::*;
+use anyhow::Result;
+use crate::colors;
+use crate::archive::ArchiveSystem;
+use crate::cli::format_size;
+use crate::config::{Config, ProtectionType};
+use crate::exam::ExamManager;
+use crate::installers::SweepSkips;
+
+/// Copies within this fraction of the original's size may be the same
+/// download re-rendered (PDFs often differ by a few bytes between downloads)
+const SIMILAR_SIZE_RATIO: f64 = 0.10;
+
+/// Browsers number re-downloads from 1; bigger numbers are usually part of
+/// the name, like `Report (2023).pdf`
+const MAX_DOWNLOAD_COUNTER: u32 = 99;
+
+/// Confidence for copies with the same contents as the kept file
+const IDENTICAL_CONFIDENCE: f32 = 0.95;
+
+/// How a numbered copy compares to the file being kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyMatch {
+    /// Same hash
+    Identical,
+    /// Different contents, nearly the same size; reported, never removed
+    Similar,
+    /// Too different to call a re-download; left alone
+    Different,
+}
+
+/// A numbered copy like `notes (1).pdf`
+#[derive(Debug, Clone)]
+pub struct DownloadCopy {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub matched: CopyMatch,
+    pub soft_protected: bool,
+}
+
+impl DownloadCopy {
+    /// Confidence that removing this copy loses nothing
+    pub fn confidence(&self) -> Option<f32> {
+        match self.matched {
+            CopyMatch::Identical => Some(IDENTICAL_CONFIDENCE),
+            CopyMatch::Similar | CopyMatch::Different => None,
+        }
+    }
+}
+
+/// Files in one folder that share a name once the browser counter is removed
+#[derive(Debug, Clone)]
+pub struct DownloadGroup {
+    /// The canonical copy: the un-numbered file, else the lowest number
+    pub keep: PathBuf,
+    pub copies: Vec<DownloadCopy>,
+}
+
+impl DownloadGroup {
+    /// Copies safe to remove: only those identical to the kept file
+    pub fn removable(&self) -> impl Iterator<Item = &DownloadCopy> {
+        self.copies.iter().filter(|c| c.matched == CopyMatch::Identical)
+    }
+
+    /// Space freed by removing the removable copies
+    pub fn reclaimable(&self) -> u64 {
+        self.removable().map(|c| c.size_bytes).sum()
+    }
+}
+
+/// Folder, lowercase stem without the counter, and lowercase extension
+type NameKey = (PathBuf, String, String);
+
+/// Split a browser download counter off a file stem: `notes (2)` gives
+/// `("notes", Some(2))`. Stacked counters (`notes (1) (1)`) are all removed;
+/// only 1 to 99 count, so `Report (2023)` keeps its year.
+pub fn strip_counter(stem: &str) -> (&str, Option<u32>) {
+    let mut stem = stem;
+    let mut counter = None;
+
+    while let Some(open) = stem.strip_suffix(')').and_then(|s| s.rfind('(')) {
+        let number = match stem[open + 1..stem.len() - 1].parse::<u32>() {
+            Ok(number) if (1..=MAX_DOWNLOAD_COUNTER).contains(&number) => number,
+            _ => break,
+        };
+        let rest = stem[..open].trim_end();
+        if rest.is_empty() {
+            break;
+        }
+        counter.get_or_insert(number);
+        stem = rest;
+    }
+
+    (stem, counter)
+}
+
+/// Compare a numbered copy to the kept file, hashing only when sizes match
+fn compare(keep: &Path, keep_size: u64, copy: &Path, copy_size: u64) -> CopyMatch {
+    if keep_size == copy_size {
+        let same = match (crate::scanner::hash_file(keep), crate::scanner::hash_file(copy)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        };
+        if same {
+            return CopyMatch::Identical;
+        }
+    }
+
+    let larger = keep_size.max(copy_size).max(1) as f64;
+    if keep_size.abs_diff(copy_size) as f64 / larger <= SIMILAR_SIZE_RATIO {
+        CopyMatch::Similar
+    } else {
+        CopyMatch::Different
+    }
+}
+
+/// Find browser re-downloads (`name (1).ext`) directly in `root` (and one level down)
+pub fn find_download_duplicates(
+    root: &Path,
+    config: &Config,
+    exam_manager: &ExamManager,
+) -> Result<(Vec<DownloadGroup>, SweepSkips)> {
+    let archive_system = ArchiveSystem::new(config.clone())?;
+    let mut skips = SweepSkips::default();
+
+    let mut by_name: HashMap<NameKey, Vec<(PathBuf, Option<u32>, u64)>> = HashMap::new();
+
+    let walker = WalkDir::new(root)
+        .max_depth(2)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+
+    for entry in walker {
+        let path = entry.path();
+        let Some(parent) = path.parent() else {
+            continue;
+        };
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let extension = path.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+        let (base, counter) = strip_counter(&stem);
+        let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+        by_name.entry((parent.to_path_buf(), base.to_lowercase(), extension))
+            .or_default()
+            .push((path.to_path_buf(), counter, size));
+    }
+
+    let mut groups = Vec::new();
+    for (_, mut files) in by_name {
+        if files.len() < 2 || files.iter().all(|(_, counter, _)| counter.is_none()) {
+            continue;
+        }
+
+        // Un-numbered first, then by counter
+        files.sort_by_key(|(path, counter, _)| (counter.is_some(), *counter, path.clone()));
+        let (keep, _, keep_size) = files.remove(0);
+
+        let mut copies = Vec::new();
+        for (path, _, size) in files {
+            // Never touch exam material, cloud-synced copies or hard-protected folders
+            if exam_manager.is_tracked(&path) {
+                skips.exam_tracked += 1;
+                continue;
+            }
+            if archive_system.is_in_cloud_folder(&path) {
+                skips.in_cloud += 1;
+                continue;
+            }
+            let soft_protected = match config.is_protected(&path) {
+                Some(protected) if matches!(protected.protection_type, ProtectionType::Hard) => {
+                    skips.protected += 1;
+                    continue;
+                }
+                Some(_) => true,
+                None => false,
+            };
+
+            copies.push(DownloadCopy {
+                matched: compare(&keep, keep_size, &path, size),
+                path,
+                size_bytes: size,
+                soft_protected,
+            });
+        }
+
+        if !copies.is_empty() {
+            groups.push(DownloadGroup { keep, copies });
+        }
+    }
+
+    // Biggest payoff first
+    groups.sort_by_key(|g| std::cmp::Reverse(g.reclaimable()));
+
+    Ok((groups, skips))
+}
+
+/// Print the groups with the space each would free
+pub fn print_download_duplicates(groups: &[DownloadGroup], skips: &SweepSkips) {
+    println!();
+    println!("{}", "📥 RE-DOWNLOADED FILES".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+
+    if groups.is_empty() {
+        println!("{} No numbered re-downloads like \"notes (1).pdf\" found ✨", "✨".green());
+    } else {
+        let total: u64 = groups.iter().map(DownloadGroup::reclaimable).sum();
+        let removable: usize = groups.iter().map(|g| g.removable().count()).sum();
+        println!("{} copies in {} groups, {} reclaimable",
+            removable.to_string().color(colors::SUCCESS),
+            groups.len(),
+            format_size(total).color(colors::SUCCESS));
+
+        let mut index = 0;
+        for group in groups {
+            println!();
+            println!("{} Keep {} ({} reclaimable)",
+                "📌".cyan(),
+                group.keep.display().to_string().color(colors::PATH),
+                format_size(group.reclaimable()));
+
+            for copy in &group.copies {
+                let name = copy.path.file_name().unwrap_or_default().to_string_lossy();
+                match (copy.matched, copy.confidence()) {
+                    (_, Some(confidence)) => {
+                        index += 1;
+                        println!("{:5}. [{}{:.2}{}] {} ({}, identical)",
+                            index,
+                            "⚡".color(colors::HIGH_CONFIDENCE),
+                            confidence,
+                            "⚡".color(colors::SUCCESS),
+                            name.color(colors::PATH),
+                            format_size(copy.size_bytes));
+                    }
+                    (CopyMatch::Similar, None) => {
+                        println!("       {} {} ({}, similar size but contents differ - check it yourself)",
+                            "🔍".cyan(),
+                            name.color(colors::PATH),
+                            format_size(copy.size_bytes));
+                    }
+                    (_, None) => {
+                        println!("       {} {} ({}, different contents - left alone)",
+                            "⚠️".yellow(),
+                            name.dimmed(),
+                            format_size(copy.size_bytes));
+                    }
+                }
+                if copy.soft_protected {
+                    println!("       {} Protected folder (soft)", "🛡️".blue());
+                }
+            }
+        }
+    }
+
+    if skips.exam_tracked > 0 {
+        println!("{} {} skipped (tracked for exams)", "🎓".cyan(), skips.exam_tracked);
+    }
+    if skips.in_cloud > 0 {
+        println!("{} {} skipped (in cloud folders)", "☁️".cyan(), skips.in_cloud);
+    }
+    if skips.protected > 0 {
+        println!("{} {} skipped (hard protected)", "🛡️".cyan(), skips.protected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_home;
+
+    #[test]
+    fn only_small_counters_are_stripped() {
+        assert_eq!(strip_counter("notes (2)"), ("notes", Some(2)));
+        assert_eq!(strip_counter("notes (1) (1)"), ("notes", Some(1)));
+        assert_eq!(strip_counter("notes"), ("notes", None));
+        assert_eq!(strip_counter("(3)"), ("(3)", None));
+        // Years and other big numbers are part of the name
+        assert_eq!(strip_counter("Report (2023)"), ("Report (2023)", None));
+        assert_eq!(strip_counter("Report (2023) (1)"), ("Report (2023)", Some(1)));
+        assert_eq!(strip_counter("notes (0)"), ("notes (0)", None));
+    }
+
+    #[test]
+    fn only_identical_copies_are_removable() {
+        let (_guard, _home) = isolated_home();
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("notes.pdf"), "lecture notes, week 1").unwrap();
+        fs::write(root.join("notes (1).pdf"), "lecture notes, week 1").unwrap();
+        fs::write(root.join("notes (2).pdf"), "lecture notes, week 2").unwrap();
+        fs::write(root.join("Report.pdf"), "final").unwrap();
+        fs::write(root.join("Report (2023).pdf"), "draft").unwrap();
+
+        let config = Config::defaults();
+        let (groups, _) = find_download_duplicates(root, &config, &ExamManager::new(config.clone())).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(group.keep, root.join("notes.pdf"));
+        let matched: Vec<(PathBuf, CopyMatch)> = group.copies.iter()
+            .map(|c| (c.path.clone(), c.matched))
+            .collect();
+        assert!(matched.contains(&(root.join("notes (1).pdf"), CopyMatch::Identical)));
+        assert!(matched.contains(&(root.join("notes (2).pdf"), CopyMatch::Similar)));
+        let removable: Vec<&Path> = group.removable().map(|c| c.path.as_path()).collect();
+        assert_eq!(removable, vec![root.join("notes (1).pdf").as_path()]);
+    }
+}
@@ -5,15 +5,69 @@ use anyhow::{Result, Context};
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use colored::*;
-use dialoguer::{theme::ColorfulTheme, Select, Confirm};
+use dialoguer::{theme::ColorfulTheme, Select, Confirm, MultiSelect};
 use crate::colors;
-use crate::config::Config;
+use crate::config::{Config, CleanupAction, ExamTrackingState, FileFingerprint, TrackedFileDetails};
 
 pub const DEFAULT_EXAM_DETECTION_FILES: usize = 15;
 pub const DEFAULT_EXAM_DETECTION_DAYS: u64 = 7;
 
+const LAST_EXAM_FILE: &str = ".cleancrush_last_exam.json";
+
+/// The most recently ended exam period, kept so `exam undo` can restore
+/// what its cleanup removed and start tracking again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndedExam {
+    pub ended_at: DateTime<Utc>,
+    pub choice: PostExamChoice,
+    /// Tracking state from just before the exam ended
+    pub tracking: ExamTrackingState,
+    /// Files the post-exam cleanup removed
+    pub cleaned_files: Vec<PathBuf>,
+    /// Where they went (Recycle Bin or archive)
+    pub action: CleanupAction,
+}
+
+impl EndedExam {
+    fn path() -> Result<PathBuf> {
+        Config::state_path(LAST_EXAM_FILE)
+    }
+    
+    /// Load the last ended exam, if there is one to undo
+    pub fn load() -> Result<Option<Self>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        
+        let data = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let ended = serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(ended))
+    }
+    
+    /// Save as the exam `exam undo` would bring back (replacing any older one)
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let data = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, data)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+    
+    /// Forget the ended exam once it has been undone
+    pub fn clear() -> Result<()> {
+        let path = Self::path()?;
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExamTracker {
     pub active: bool,
@@ -31,6 +85,9 @@ pub struct FileTrackingInfo {
     pub file_type: String,
     pub course: String,
     pub category: FileCategory,
+    /// Size + mtime when last seen; None for entries from older versions
+    #[serde(default)]
+    pub fingerprint: Option<FileFingerprint>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -41,6 +98,17 @@ pub enum FileCategory {
     Other,
 }
 
+impl FileCategory {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileCategory::Lecture => "📚 Lectures",
+            FileCategory::Assignment => "📝 Assignments",
+            FileCategory::Reference => "📖 References",
+            FileCategory::Other => "🎫 Other",
+        }
+    }
+}
+
 impl ExamTracker {
     /// Create a new exam tracker
     pub fn new(auto_detected: bool, exam_name: Option<String>) -> Self {
@@ -125,19 +193,52 @@ impl ExamTracker {
         }
     }
     
-    /// Add a file to tracking
+    /// Add a file to tracking. A file that's already tracked keeps the
+    /// fingerprint from when it was first tracked, so a re-scan can't make a
+    /// replaced file look like the one the exam period saw
     pub fn add_file(&mut self, path: PathBuf, size_bytes: u64, file_type: String, course: String, category: FileCategory) {
+        let existing = self.tracked_files.get(&path);
+        let added_date = existing
+            .map(|existing| existing.added_date)
+            .unwrap_or_else(Utc::now);
+        let fingerprint = existing
+            .and_then(|existing| existing.fingerprint.clone())
+            .or_else(|| FileFingerprint::of(&path));
+        
         let info = FileTrackingInfo {
-            added_date: Utc::now(),
+            added_date,
             size_bytes,
             file_type,
             course,
             category,
+            fingerprint,
         };
         
         self.tracked_files.insert(path, info);
     }
     
+    /// Split files into those that still match their tracked fingerprint and
+    /// those that don't (with the reason), so cleanup never removes whatever
+    /// now happens to sit at a tracked path
+    pub fn verify_files(&self, files: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<(PathBuf, String)>) {
+        let mut verified = Vec::new();
+        let mut mismatched = Vec::new();
+        
+        for path in files {
+            let recorded = self.tracked_files.get(&path).and_then(|info| info.fingerprint.as_ref());
+            match (recorded, FileFingerprint::of(&path)) {
+                (_, None) => mismatched.push((path, "no longer exists".to_string())),
+                (None, Some(_)) => mismatched.push((path, "no fingerprint was recorded".to_string())),
+                (Some(recorded), Some(current)) if *recorded != current => {
+                    mismatched.push((path, "changed since it was tracked".to_string()));
+                }
+                (Some(_), Some(_)) => verified.push(path),
+            }
+        }
+        
+        (verified, mismatched)
+    }
+    
     /// End exam tracking
     pub fn end_exam(&mut self) {
         self.active = false;
@@ -298,15 +399,98 @@ impl ExamTracker {
         Ok(choice)
     }
     
-    /// Get files for post-exam cleanup based on choice
+    /// Get files for post-exam cleanup based on choice. Quick and Smart clean
+    /// leave out files edited since they were tracked (see `actively_used_files`).
     pub fn get_files_for_cleanup(&self, choice: PostExamChoice) -> Vec<PathBuf> {
+        let keep_edited = !matches!(choice, PostExamChoice::SelectiveClean);
+        self.cleanup_candidates(choice).into_iter()
+            .filter(|path| !(keep_edited && self.modified_since_tracked(path)))
+            .collect()
+    }
+    
+    /// Files the choice would clean that were edited during the exam, e.g. a
+    /// cheat-sheet kept up to date - probably still wanted, so only cleaned
+    /// if the user opts in
+    pub fn actively_used_files(&self, choice: PostExamChoice) -> Vec<PathBuf> {
+        if matches!(choice, PostExamChoice::SelectiveClean) {
+            return Vec::new();
+        }
+        let mut files: Vec<PathBuf> = self.cleanup_candidates(choice).into_iter()
+            .filter(|path| self.modified_since_tracked(path))
+            .collect();
+        files.sort();
+        files
+    }
+    
+    /// Let the user pick, category by category, which of `files` to clean.
+    /// Everything starts checked; unchecked files are kept. Without a
+    /// terminal to ask, everything stays checked.
+    pub fn select_by_category(&self, files: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+        if crate::config::non_interactive() {
+            return Ok(files);
+        }
+        
+        let categories = [
+            FileCategory::Lecture,
+            FileCategory::Assignment,
+            FileCategory::Reference,
+            FileCategory::Other,
+        ];
+        
+        let mut selected = Vec::new();
+        let mut counts = Vec::new();
+        for category in categories {
+            let mut in_category: Vec<PathBuf> = files.iter()
+                .filter(|path| self.tracked_files.get(*path)
+                    .map_or(FileCategory::Other, |info| info.category.clone()) == category)
+                .cloned()
+                .collect();
+            if in_category.is_empty() {
+                continue;
+            }
+            in_category.sort();
+            
+            let labels: Vec<String> = in_category.iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            let picked = MultiSelect::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("{} - uncheck files to keep (space to toggle, enter to continue)", category.label()))
+                .items(&labels)
+                .defaults(&vec![true; labels.len()])
+                .interact()
+                .context("Failed to get selection")?;
+            
+            counts.push((category, picked.len(), in_category.len() - picked.len()));
+            selected.extend(picked.into_iter().map(|i| in_category[i].clone()));
+        }
+        
+        println!();
+        for (category, cleaned, kept) in &counts {
+            println!("{}: {} to clean, {} kept", 
+                category.label(),
+                cleaned.to_string().color(colors::SUCCESS),
+                kept.to_string().color(colors::PATH));
+        }
+        
+        Ok(selected)
+    }
+    
+    /// Whether a tracked file's mtime is newer than when it was first tracked
+    fn modified_since_tracked(&self, path: &Path) -> bool {
+        let Some(info) = self.tracked_files.get(path) else {
+            return false;
+        };
+        FileFingerprint::of(path).is_some_and(|current| current.modified > info.added_date)
+    }
+    
+    fn cleanup_candidates(&self, choice: PostExamChoice) -> Vec<PathBuf> {
         match choice {
             PostExamChoice::QuickClean => {
                 // All files
                 self.tracked_files.keys().cloned().collect()
             }
             PostExamChoice::SelectiveClean => {
-                // All files (user will select in UI)
+                // All files (narrowed down in `select_by_category`)
                 self.tracked_files.keys().cloned().collect()
             }
             PostExamChoice::SmartClean => {
@@ -354,7 +538,7 @@ impl PostExamOption {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PostExamChoice {
     QuickClean,
     SelectiveClean,
@@ -362,7 +546,7 @@ pub enum PostExamChoice {
 }
 
 impl PostExamChoice {
-    fn display_name(&self) -> &'static str {
+    pub fn display_name(&self) -> &'static str {
         match self {
             Self::QuickClean => "Quick Clean",
             Self::SelectiveClean => "Selective Clean",
@@ -482,8 +666,36 @@ pub fn stop(&mut self) -> Result<()> {
     Ok(())
 }
     
-    /// End exam and show cleanup options
-    pub fn end_exam(&mut self) -> Result<Option<PostExamChoice>> {
+    /// Move the active exam's end date later; tracked files stay as they are
+    pub fn extend(&mut self, new_end: DateTime<Utc>) -> Result<()> {
+        let Some(tracker) = self.tracker.as_mut().filter(|t| t.active) else {
+            return Err(anyhow::anyhow!("No active exam to extend - start one with `cleancrush exam on` or `exam set`"));
+        };
+        if let Some(end_date) = tracker.end_date {
+            if new_end <= end_date {
+                return Err(anyhow::anyhow!("The exam already ends on {} - pick a later date", end_date.format("%Y-%m-%d")));
+            }
+        }
+        
+        tracker.end_date = Some(new_end);
+        self.config.exam_tracking = Some(tracker.clone().into());
+        self.config.save()?;
+        
+        println!("{} {} now ends on {} ({} files still tracked)", 
+            "✅".green(),
+            tracker.exam_period_name.as_deref().map_or("Exam".to_string(), |name| format!("Exam '{}'", name)),
+            new_end.format("%Y-%m-%d"),
+            tracker.total_files());
+        if new_end.date_naive() < Utc::now().date_naive() {
+            println!("   {} That date has already passed", "⚠️".yellow());
+        }
+        
+        Ok(())
+    }
+    
+    /// End exam and show cleanup options. Returns the choice together with
+    /// the ended tracker, which holds the files to clean up.
+    pub fn end_exam(&mut self) -> Result<Option<(PostExamChoice, ExamTracker)>> {
         if let Some(tracker) = &mut self.tracker {
             if tracker.has_ended() {
                 println!("{} Exam already ended", "ℹ️".cyan());
@@ -500,25 +712,72 @@ pub fn stop(&mut self) -> Result<()> {
             self.config.exam_tracking = Some(tracker.clone().into());
             self.config.save()?;
             
+            let ended = tracker.clone();
             self.tracker = None;
 
-            Ok(Some(choice))
+            Ok(Some((choice, ended)))
         } else {
             println!("{} No active exam to end", "⚠️".yellow());
             Ok(None)
         }
     }
     
+    /// Make an ended exam active again with the files it tracked
+    pub fn reinstate(&mut self, tracking: ExamTrackingState) -> Result<()> {
+        if self.is_active() || self.config.exam_tracking.as_ref().is_some_and(|t| t.active) {
+            return Err(anyhow::anyhow!("Exam tracking is already active - end it before undoing the last one"));
+        }
+        
+        self.config.exam_tracking = Some(ExamTrackingState { active: true, ..tracking });
+        self.config.save()?;
+        self.load_from_config()
+    }
+    
     /// Get current tracker
     pub fn get_tracker(&self) -> Option<&ExamTracker> {
         self.tracker.as_ref()
     }
     
+    /// Check if a file is (or was) tracked for the current exam period
+    pub fn is_tracked(&self, path: &Path) -> bool {
+        self.tracker.as_ref()
+            .is_some_and(|t| t.tracked_files.contains_key(path))
+    }
+    
+    /// Files among `paths` the active exam is tracking for post-exam cleanup
+    pub fn tracked_among(&self, paths: &[PathBuf]) -> Vec<PathBuf> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        paths.iter().filter(|path| self.is_tracked(path)).cloned().collect()
+    }
+    
+    /// Stop tracking files that were cleaned outside post-exam cleanup,
+    /// returning how many were dropped
+    pub fn untrack(&mut self, paths: &[PathBuf]) -> usize {
+        let Some(tracker) = &mut self.tracker else {
+            return 0;
+        };
+        let before = tracker.tracked_files.len();
+        for path in paths {
+            tracker.tracked_files.remove(path);
+        }
+        before - tracker.tracked_files.len()
+    }
+    
     /// Check if exam mode is active
     pub fn is_active(&self) -> bool {
         self.tracker.as_ref().map_or(false, |t| t.active)
     }
     
+    /// The end date set with `exam set`, once that day is over and tracking
+    /// is still on
+    pub fn overdue_end_date(&self) -> Option<DateTime<Utc>> {
+        let tracker = self.tracker.as_ref().filter(|t| t.active)?;
+        let end_date = tracker.end_date?;
+        (Utc::now().date_naive() > end_date.date_naive()).then_some(end_date)
+    }
+    
     /// Add file to tracking if exam mode is active
     pub fn track_file_if_active(
         &mut self, 
@@ -530,11 +789,27 @@ pub fn stop(&mut self) -> Result<()> {
     ) {
         if let Some(tracker) = &mut self.tracker {
             if tracker.active {
+                // Tracked paths are saved as JSON, which needs UTF-8 names
+                if path.to_str().is_none() {
+                    println!("{} Can't track {} for exam mode: file name isn't valid UTF-8", 
+                        "⚠️".yellow(), path.display());
+                    return;
+                }
+                // Calendar invites, installers and the like the user never wants tracked
+                if let Some(pattern) = self.config.exam_ignore_match(&path) {
+                    log::info!("Not exam-tracked: {} (exam ignore '{}')", path.display(), pattern);
+                    return;
+                }
                 tracker.add_file(path, size_bytes, file_type, course, category);
             }
         }
     }
     
+    /// Tracking state to persist in the config
+    pub fn tracking_state(&self) -> Option<crate::config::ExamTrackingState> {
+        self.tracker.clone().map(Into::into)
+    }
+    
     /// Show current status
     pub fn show_status(&self) {
         if let Some(tracker) = &self.tracker {
@@ -556,13 +831,31 @@ pub fn load_from_config(&mut self) -> Result<()> {
                 end_date: tracking_state.end_date.as_ref().and_then(|d| d.parse().ok()),
                 auto_detected: false,
                 tracked_files: tracking_state.tracked_files.iter()
-                    .map(|path| (path.clone(), FileTrackingInfo {
-                        added_date: Utc::now(),
-                        size_bytes: 0,
-                        file_type: "unknown".to_string(),
-                        course: "general".to_string(),
-                        category: FileCategory::Other,
-                    }))
+                    .map(|path| {
+                        let fingerprint = tracking_state.fingerprints.get(path).cloned();
+                        // Older configs have no added date; the last-seen mtime is the
+                        // closest stand-in that won't flag every file as edited
+                        let added_date = tracking_state.added_dates.get(path).copied()
+                            .or_else(|| fingerprint.as_ref().map(|f| f.modified))
+                            .unwrap_or_else(Utc::now);
+                        // Configs from before details were saved only know the size
+                        // from the fingerprint
+                        let details = tracking_state.details.get(path).cloned()
+                            .unwrap_or_else(|| TrackedFileDetails {
+                                size_bytes: fingerprint.as_ref().map_or(0, |f| f.size_bytes),
+                                file_type: "unknown".to_string(),
+                                course: "general".to_string(),
+                                category: FileCategory::Other,
+                            });
+                        (path.clone(), FileTrackingInfo {
+                            added_date,
+                            size_bytes: details.size_bytes,
+                            file_type: details.file_type,
+                            course: details.course,
+                            category: details.category,
+                            fingerprint,
+                        })
+                    })
                     .collect(),
                 exam_period_name: tracking_state.exam_period_name.clone(),
             };
@@ -586,6 +879,93 @@ impl From<ExamTracker> for crate::config::ExamTrackingState {
             end_date: tracker.end_date.map(|d| d.to_rfc3339()),
             tracked_files: tracker.tracked_files.keys().cloned().collect(),
             exam_period_name: tracker.exam_period_name.clone(),
+            fingerprints: tracker.tracked_files.iter()
+                .filter_map(|(path, info)| info.fingerprint.clone().map(|f| (path.clone(), f)))
+                .collect(),
+            added_dates: tracker.tracked_files.iter()
+                .map(|(path, info)| (path.clone(), info.added_date))
+                .collect(),
+            details: tracker.tracked_files.iter()
+                .map(|(path, info)| (path.clone(), TrackedFileDetails {
+                    size_bytes: info.size_bytes,
+                    file_type: info.file_type.clone(),
+                    course: info.course.clone(),
+                    category: info.category.clone(),
+                }))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+    
+    fn track(tracker: &mut ExamTracker, path: &Path) {
+        tracker.add_file(path.to_path_buf(), 10, "pdf".to_string(), "general".to_string(), FileCategory::Lecture);
+    }
+    
+    #[test]
+    fn files_edited_after_tracking_are_kept_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let untouched = dir.path().join("slides.pdf");
+        let edited = dir.path().join("cheat-sheet.pdf");
+        for path in [&untouched, &edited] {
+            std::fs::write(path, "notes").unwrap();
+            std::fs::File::options().write(true).open(path).unwrap()
+                .set_modified(SystemTime::now() - std::time::Duration::from_secs(2 * 86_400)).unwrap();
         }
+        
+        let mut tracker = ExamTracker::new(false, None);
+        track(&mut tracker, &untouched);
+        track(&mut tracker, &edited);
+        // Tracked a day ago, edited since
+        tracker.tracked_files.get_mut(&edited).unwrap().added_date = Utc::now() - Duration::days(1);
+        std::fs::File::options().write(true).open(&edited).unwrap()
+            .set_modified(SystemTime::now()).unwrap();
+        
+        for choice in [PostExamChoice::QuickClean, PostExamChoice::SmartClean] {
+            assert_eq!(tracker.get_files_for_cleanup(choice.clone()), vec![untouched.clone()]);
+            assert_eq!(tracker.actively_used_files(choice), vec![edited.clone()]);
+        }
+        // Selective clean shows everything and lets the user decide
+        assert_eq!(tracker.get_files_for_cleanup(PostExamChoice::SelectiveClean).len(), 2);
+    }
+    
+    #[test]
+    fn added_date_survives_the_config_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("slides.pdf");
+        std::fs::write(&path, "notes").unwrap();
+        let mut tracker = ExamTracker::new(false, None);
+        track(&mut tracker, &path);
+        let added_date = Utc::now() - Duration::days(3);
+        tracker.tracked_files.get_mut(&path).unwrap().added_date = added_date;
+        
+        let mut config = Config::defaults();
+        config.exam_tracking = Some(tracker.into());
+        let mut manager = ExamManager::new(config);
+        manager.load_from_config().unwrap();
+        
+        let loaded = manager.get_tracker().unwrap();
+        assert_eq!(loaded.tracked_files[&path].added_date, added_date);
+    }
+    
+    #[test]
+    fn rescans_keep_the_fingerprint_from_first_tracking() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("slides.pdf");
+        std::fs::write(&path, "notes").unwrap();
+        let mut tracker = ExamTracker::new(false, None);
+        track(&mut tracker, &path);
+        
+        // Something else now sits at the tracked path, and a re-scan sees it
+        std::fs::write(&path, "a different, longer file").unwrap();
+        track(&mut tracker, &path);
+        
+        let (verified, mismatched) = tracker.verify_files(vec![path.clone()]);
+        assert!(verified.is_empty());
+        assert_eq!(mismatched, vec![(path, "changed since it was tracked".to_string())]);
     }
 }
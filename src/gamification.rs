@@ -6,7 +6,87 @@ use colored::*;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use anyhow::{Result, Context};
+use chrono::NaiveDate;
 use crate::{colors, ENCOURAGEMENTS, Config};
+use crate::config::ReminderSchedule;
+
+const GAME_FILE: &str = ".cleancrush_game.json";
+
+/// Cleanliness score points lost per file of each kind
+pub const DUPLICATE_PENALTY: usize = 2;
+pub const OLD_PENALTY: usize = 1;
+pub const LARGE_PENALTY: usize = 1;
+pub const VERY_LARGE_PENALTY: usize = 3;
+
+/// How often cleanups have to happen to keep a streak going. Weekly and
+/// monthly reminder schedules count calendar weeks (Monday to Sunday) and
+/// months; otherwise every day counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StreakWindow {
+    #[default]
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl StreakWindow {
+    pub fn for_schedule(schedule: &ReminderSchedule) -> Self {
+        match schedule {
+            ReminderSchedule::Never => StreakWindow::Daily,
+            ReminderSchedule::Weekly => StreakWindow::Weekly,
+            ReminderSchedule::Monthly => StreakWindow::Monthly,
+        }
+    }
+    
+    /// Number of the calendar period a date falls in; consecutive periods
+    /// get consecutive numbers
+    fn period(&self, date: NaiveDate) -> i64 {
+        match self {
+            StreakWindow::Daily => date.num_days_from_ce() as i64,
+            StreakWindow::Weekly => {
+                let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                monday.num_days_from_ce() as i64 / 7
+            }
+            StreakWindow::Monthly => date.year() as i64 * 12 + date.month0() as i64,
+        }
+    }
+    
+    /// Streak after a cleanup on `today`, following one on `last`: unchanged
+    /// within the same period, one more in the next, and back to 1 once a
+    /// whole period was skipped
+    pub fn next_streak(&self, current: u32, last: Option<NaiveDate>, today: NaiveDate) -> u32 {
+        let Some(last) = last else {
+            return 1;
+        };
+        match self.period(today) - self.period(last) {
+            gap if gap <= 0 => current.max(1),
+            1 => current + 1,
+            _ => 1,
+        }
+    }
+    
+    /// Days one step of the streak stands for
+    fn days(&self) -> u32 {
+        match self {
+            StreakWindow::Daily => 1,
+            StreakWindow::Weekly => 7,
+            StreakWindow::Monthly => 30,
+        }
+    }
+    
+    /// A streak with its unit, e.g. "3 days" or "1 week"
+    pub fn describe(&self, streak: u32) -> String {
+        let unit = match self {
+            StreakWindow::Daily => "day",
+            StreakWindow::Weekly => "week",
+            StreakWindow::Monthly => "month",
+        };
+        format!("{} {}{}", streak, unit, if streak == 1 { "" } else { "s" })
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Gamification {
@@ -18,6 +98,12 @@ pub struct Gamification {
     pub total_files_cleaned: u64,
     pub total_space_freed_mb: u64,
     pub daily_stats: HashMap<String, DailyStats>,
+    /// Most space freed by a single cleanup
+    #[serde(default)]
+    pub best_cleanup_mb: u64,
+    /// Follows the reminder schedule, so it's never saved
+    #[serde(skip)]
+    pub streak_window: StreakWindow,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +125,34 @@ pub struct DailyStats {
     pub cleanup_type: CleanupType,
 }
 
+/// Space freed in one cleanup for ⚡ Speed Cleaner
+pub const SPEED_CLEANER_BYTES: u64 = 1024 * 1024 * 1024;
+/// Every cleaned file needs at least this confidence for 🎯 Sharpshooter
+pub const SHARPSHOOTER_CONFIDENCE: f32 = 0.9;
+
+/// Confidence of the files a cleanup actually removed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfidenceSummary {
+    /// Files with a known confidence
+    pub files: usize,
+    pub lowest: f32,
+}
+
+impl ConfidenceSummary {
+    /// Summarize the confidences of the cleaned files
+    pub fn from_confidences(confidences: impl IntoIterator<Item = f32>) -> Self {
+        confidences.into_iter().fold(Self::default(), |summary, confidence| Self {
+            files: summary.files + 1,
+            lowest: if summary.files == 0 { confidence } else { summary.lowest.min(confidence) },
+        })
+    }
+    
+    /// Whether every cleaned file (and at least one) was at or above `threshold`
+    pub fn all_at_least(&self, threshold: f32) -> bool {
+        self.files > 0 && self.lowest >= threshold
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CleanupType {
     Normal,
@@ -117,6 +231,24 @@ impl Gamification {
                 unlocked_date: None,
                 progress: 0.0,
             },
+            Achievement {
+                id: "speed_cleaner".to_string(),
+                name: "⚡ Speed Cleaner".to_string(),
+                description: "Free 1 GB in a single cleanup".to_string(),
+                icon: "⚡".to_string(),
+                unlocked: false,
+                unlocked_date: None,
+                progress: 0.0,
+            },
+            Achievement {
+                id: "sharpshooter".to_string(),
+                name: "🎯 Sharpshooter".to_string(),
+                description: "Clean only files with 0.9+ confidence".to_string(),
+                icon: "🎯".to_string(),
+                unlocked: false,
+                unlocked_date: None,
+                progress: 0.0,
+            },
         ];
         
         for achievement in achievement_list {
@@ -132,7 +264,62 @@ impl Gamification {
             total_files_cleaned: 0,
             total_space_freed_mb: 0,
             daily_stats: HashMap::new(),
+            best_cleanup_mb: 0,
+            streak_window: StreakWindow::Daily,
+        }
+    }
+    
+    /// Get the path to the saved game state
+    pub fn path() -> Result<PathBuf> {
+        Config::state_path(GAME_FILE)
+    }
+    
+    /// Load the saved game state, or seed it from the config when there is
+    /// none yet (older versions only kept streaks, totals and achievements there)
+    pub fn load(config: &Config) -> Self {
+        let mut gamification = match Self::load_saved() {
+            Ok(Some(saved)) => saved.with_current_achievements(),
+            Ok(None) => Self::load_from_config(config),
+            Err(e) => {
+                println!("{} Couldn't read your saved progress, rebuilding it from the config: {:#}", "⚠️".yellow(), e);
+                Self::load_from_config(config)
+            }
+        };
+        gamification.streak_window = StreakWindow::for_schedule(&config.reminder_schedule);
+        gamification
+    }
+    
+    fn load_saved() -> Result<Option<Self>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        
+        let data = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&data)
+            .map(Some)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+    
+    /// Achievements added since the state was saved start out locked
+    fn with_current_achievements(mut self) -> Self {
+        for (id, achievement) in Self::new().achievements {
+            self.achievements.entry(id).or_insert(achievement);
         }
+        self
+    }
+    
+    /// Save the game state (written to a temp file first so it's never half-written)
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let tmp_path = path.with_extension("json.tmp");
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(&tmp_path, data)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
     }
     
     /// Load gamification from config
@@ -140,12 +327,19 @@ impl Gamification {
         let mut gamification = Self::new();
         
         gamification.current_streak = config.streaks;
+        gamification.last_cleanup_date = config.last_cleanup.as_deref()
+            .and_then(|last| last.parse().ok());
+        gamification.streak_window = StreakWindow::for_schedule(&config.reminder_schedule);
         gamification.total_files_cleaned = config.total_files_cleaned;
         gamification.total_space_freed_mb = config.total_space_freed_mb;
+        gamification.best_cleanup_mb = config.best_cleanup_mb;
         
-        // Update achievements from config
+        // Update achievements from config (stored by display name or id)
         for achievement_name in &config.achievements {
-            if let Some(achievement) = gamification.achievements.get_mut(achievement_name) {
+            let Some(id) = gamification.achievement_id(achievement_name) else {
+                continue;
+            };
+            if let Some(achievement) = gamification.achievements.get_mut(&id) {
                 achievement.unlocked = true;
                 achievement.progress = 1.0;
             }
@@ -158,14 +352,35 @@ impl Gamification {
         
         gamification
     }
-    
-    /// Update gamification after cleanup
+
+    /// Start over: streaks, totals and daily history go back to zero, and
+    /// achievements are locked again unless `keep_achievements`
+    pub fn reset(&mut self, keep_achievements: bool) {
+        let achievements = std::mem::take(&mut self.achievements);
+        let streak_window = self.streak_window;
+        *self = Self::new();
+        self.streak_window = streak_window;
+        if keep_achievements {
+            self.achievements = achievements;
+        }
+    }
+
+    /// Resolve an achievement id from either its id or its display name
+    pub fn achievement_id(&self, name_or_id: &str) -> Option<String> {
+        self.achievements.values()
+            .find(|a| a.id == name_or_id || a.name == name_or_id)
+            .map(|a| a.id.clone())
+    }
+
+    /// Update gamification after cleanup. `confidence` covers the files that
+    /// were cleaned (default when unknown, e.g. exam files).
     pub fn update_after_cleanup(
         &mut self, 
         files_cleaned: usize, 
         space_freed_bytes: u64,
         cleanup_type: CleanupType,
         is_exam_cleanup: bool,
+        confidence: ConfidenceSummary,
     ) -> Vec<AchievementUnlock> {
         let today = Utc::now();
         let today_str = today.format("%Y-%m-%d").to_string();
@@ -175,6 +390,7 @@ impl Gamification {
         self.total_cleanups += 1;
         self.total_files_cleaned += files_cleaned as u64;
         self.total_space_freed_mb += space_freed_mb;
+        self.best_cleanup_mb = self.best_cleanup_mb.max(space_freed_mb);
         
         // Update daily stats
         let daily_stat = DailyStats {
@@ -192,28 +408,19 @@ impl Gamification {
         let mut unlocks = Vec::new();
         
         // Check each achievement
-        unlocks.extend(self.check_achievements(files_cleaned, space_freed_mb, is_exam_cleanup));
+        unlocks.extend(self.check_achievements(
+            files_cleaned, space_freed_bytes, is_exam_cleanup, confidence));
         
         unlocks
     }
     
     /// Update streak counter
     fn update_streak(&mut self, cleanup_date: chrono::DateTime<Utc>) {
-        if let Some(last_date) = self.last_cleanup_date {
-            let days_since = (cleanup_date - last_date).num_days();
-            
-            if days_since == 1 {
-                // Consecutive day
-                self.current_streak += 1;
-            } else if days_since > 1 {
-                // Streak broken
-                self.current_streak = 1;
-            }
-            // days_since == 0 means same day, don't increment
-        } else {
-            // First cleanup
-            self.current_streak = 1;
-        }
+        self.current_streak = self.streak_window.next_streak(
+            self.current_streak,
+            self.last_cleanup_date.map(|last| last.date_naive()),
+            cleanup_date.date_naive(),
+        );
         
         // Update longest streak
         if self.current_streak > self.longest_streak {
@@ -227,8 +434,9 @@ impl Gamification {
     fn check_achievements(
         &mut self, 
         _files_cleaned: usize, 
-        _space_freed_mb: u64,
+        space_freed_bytes: u64,
         is_exam_cleanup: bool,
+        confidence: ConfidenceSummary,
     ) -> Vec<AchievementUnlock> {
         let mut unlocks = Vec::new();
         let today = Utc::now();
@@ -289,16 +497,40 @@ impl Gamification {
         // Consistency Cutie
         if !self.achievements["consistency_cutie"].unlocked {
             let achievement = self.achievements.get_mut("consistency_cutie").unwrap();
-            let progress = (self.current_streak as f32 / 21.0).min(1.0); // 3 weeks = 21 days
-            achievement.progress = progress;
+            // 3 weeks = 21 days, whatever the streak counts in
+            let streak_days = self.current_streak * self.streak_window.days();
+            achievement.progress = (streak_days as f32 / 21.0).min(1.0);
+            
+            if streak_days >= 21 {
+                achievement.unlocked = true;
+                achievement.unlocked_date = Some(today);
+                unlocks.push(AchievementUnlock::new(achievement));
+            }
+        }
+        
+        // Speed Cleaner (progress is the best single cleanup so far)
+        if !self.achievements["speed_cleaner"].unlocked {
+            let achievement = self.achievements.get_mut("speed_cleaner").unwrap();
+            let best_bytes = self.best_cleanup_mb * 1024 * 1024;
+            achievement.progress = (best_bytes as f32 / SPEED_CLEANER_BYTES as f32).min(1.0);
             
-            if self.current_streak >= 21 {
+            if space_freed_bytes >= SPEED_CLEANER_BYTES {
                 achievement.unlocked = true;
                 achievement.unlocked_date = Some(today);
+                achievement.progress = 1.0;
                 unlocks.push(AchievementUnlock::new(achievement));
             }
         }
         
+        // Sharpshooter
+        if !self.achievements["sharpshooter"].unlocked && confidence.all_at_least(SHARPSHOOTER_CONFIDENCE) {
+            let achievement = self.achievements.get_mut("sharpshooter").unwrap();
+            achievement.unlocked = true;
+            achievement.unlocked_date = Some(today);
+            achievement.progress = 1.0;
+            unlocks.push(AchievementUnlock::new(achievement));
+        }
+        
         unlocks
     }
     
@@ -314,10 +546,10 @@ impl Gamification {
         let mut breakdown = Vec::new();
         
         // Penalties
-        let duplicate_penalty = duplicates * 2;
-        let old_penalty = old_files * 1;
-        let large_penalty = large_files * 1;
-        let very_large_penalty = very_large_files * 3;
+        let duplicate_penalty = duplicates * DUPLICATE_PENALTY;
+        let old_penalty = old_files * OLD_PENALTY;
+        let large_penalty = large_files * LARGE_PENALTY;
+        let very_large_penalty = very_large_files * VERY_LARGE_PENALTY;
         
         score = score.saturating_sub(duplicate_penalty as u32);
         score = score.saturating_sub(old_penalty as u32);
@@ -369,14 +601,12 @@ impl Gamification {
         println!("{}", "📊 YOUR STATISTICS".bold().color(colors::HEADER));
         println!("{}", "─".repeat(50).color(colors::PATH));
         
-        println!("🔥 Current streak: {} day{}", 
-            self.current_streak.to_string().color(colors::SUCCESS),
-            if self.current_streak == 1 { "" } else { "s" });
+        println!("🔥 Current streak: {}", 
+            self.streak_window.describe(self.current_streak).color(colors::SUCCESS));
         
         if self.longest_streak > self.current_streak {
-            println!("🏆 Longest streak: {} day{}", 
-                self.longest_streak.to_string().color(colors::SUCCESS),
-                if self.longest_streak == 1 { "" } else { "s" });
+            println!("🏆 Longest streak: {}", 
+                self.streak_window.describe(self.longest_streak).color(colors::SUCCESS));
         }
         
         println!("🧹 Total cleanups: {}", 
@@ -385,6 +615,10 @@ impl Gamification {
             self.total_files_cleaned.to_string().color(colors::PATH));
         println!("💾 Total space freed: {:.1} MB", 
             self.total_space_freed_mb.to_string().color(colors::PATH));
+        if self.best_cleanup_mb > 0 {
+            println!("⚡ Biggest single cleanup: {} MB", 
+                self.best_cleanup_mb.to_string().color(colors::PATH));
+        }
         
         // Show recent activity
         self.display_recent_activity();
@@ -424,6 +658,54 @@ impl Gamification {
         }
     }
     
+    /// Totals for the last `days` days (today included), with a bar per day
+    /// scaled to the busiest one
+    pub fn display_period(&self, days: i64) {
+        const BAR_WIDTH: u32 = 20;
+        
+        let today = Utc::now().date_naive();
+        let window: Vec<_> = (0..days).rev()
+            .map(|offset| today - Duration::days(offset))
+            .map(|date| (date, self.daily_stats.get(&date.format("%Y-%m-%d").to_string())))
+            .collect();
+        let active: Vec<&DailyStats> = window.iter().filter_map(|(_, stat)| *stat).collect();
+        
+        println!();
+        println!("{}", format!("📅 LAST {} DAYS", days).bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        
+        println!("🧹 Cleanup days: {} of {}", 
+            active.len().to_string().color(colors::SUCCESS), days);
+        println!("📁 Files cleaned: {}", 
+            active.iter().map(|s| s.files_cleaned).sum::<u32>().to_string().color(colors::SUCCESS));
+        println!("💾 Space freed: {:.1} MB", 
+            active.iter().map(|s| s.space_freed_mb).sum::<u32>() as f32);
+        
+        if active.is_empty() {
+            println!();
+            println!("{} No cleanups in this stretch yet - {} is a good start", 
+                "💡".cyan(), "cleancrush suggest ~/Downloads".bold());
+            return;
+        }
+        
+        println!();
+        let busiest = active.iter().map(|s| s.files_cleaned).max().unwrap_or(0).max(1);
+        for (date, stat) in &window {
+            let label = date.format("%a %m-%d");
+            match stat {
+                Some(stat) => {
+                    let width = (stat.files_cleaned * BAR_WIDTH).div_ceil(busiest) as usize;
+                    println!("   {} {} {} files, {:.1} MB",
+                        label,
+                        "█".repeat(width.max(1)).color(colors::SUCCESS),
+                        stat.files_cleaned,
+                        stat.space_freed_mb as f32);
+                }
+                None => println!("   {} {}", label, "·".dimmed()),
+            }
+        }
+    }
+    
     /// Display achievements
     pub fn display_achievements(&self) {
         let unlocked: Vec<_> = self.achievements.values()
@@ -486,15 +768,21 @@ impl Gamification {
     ) {
         println!();
         
+        // `--quiet` keeps just the totals
+        if crate::progress::quiet() {
+            println!("Cleaned {} files, freed {:.1} MB", files_cleaned, space_freed_mb as f32);
+            return;
+        }
+        
         // Show main encouragement
         let message = self.get_encouragement_message();
         println!("{} {}", "💖".color(colors::HIGH_CONFIDENCE), message);
         
         // Show streak update if applicable
         if self.current_streak > 1 {
-            println!("{} Streak: {} days in a row!", 
+            println!("{} Streak: {} in a row!", 
                 "🔥".color(colors::WARNING), 
-                self.current_streak);
+                self.streak_window.describe(self.current_streak));
         }
         
         // Show achievement unlocks
@@ -538,4 +826,98 @@ impl AchievementUnlock {
             icon: achievement.icon.clone(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn day(d: u32) -> NaiveDate {
+        // 2024-06-03 is a Monday
+        NaiveDate::from_ymd_opt(2024, 6, d).unwrap()
+    }
+    
+    fn cleanup_on(game: &mut Gamification, d: u32) {
+        game.update_streak(day(d).and_hms_opt(12, 0, 0).unwrap().and_utc());
+    }
+    
+    fn unlocked(unlocks: &[AchievementUnlock]) -> Vec<&str> {
+        unlocks.iter().map(|u| u.name.as_str()).collect()
+    }
+    
+    #[test]
+    fn speed_cleaner_needs_a_gigabyte_in_one_cleanup() {
+        let mut game = Gamification::new();
+        let summary = ConfidenceSummary::default();
+        
+        game.update_after_cleanup(3, SPEED_CLEANER_BYTES - 1, CleanupType::Normal, false, summary);
+        game.update_after_cleanup(3, SPEED_CLEANER_BYTES - 1, CleanupType::Normal, false, summary);
+        assert!(!game.achievements["speed_cleaner"].unlocked);
+        
+        let unlocks = game.update_after_cleanup(3, SPEED_CLEANER_BYTES, CleanupType::Normal, false, summary);
+        assert!(game.achievements["speed_cleaner"].unlocked);
+        assert!(unlocked(&unlocks).contains(&game.achievements["speed_cleaner"].name.as_str()));
+    }
+    
+    #[test]
+    fn sharpshooter_needs_every_file_at_high_confidence() {
+        let mut game = Gamification::new();
+        
+        let mixed = ConfidenceSummary::from_confidences([0.95, 0.89]);
+        game.update_after_cleanup(2, 1024, CleanupType::Normal, false, mixed);
+        assert!(!game.achievements["sharpshooter"].unlocked);
+        
+        // Nothing known about the files isn't a perfect cleanup either
+        game.update_after_cleanup(2, 1024, CleanupType::Exam, true, ConfidenceSummary::default());
+        assert!(!game.achievements["sharpshooter"].unlocked);
+        
+        let sure = ConfidenceSummary::from_confidences([0.99, SHARPSHOOTER_CONFIDENCE]);
+        game.update_after_cleanup(2, 1024, CleanupType::Normal, false, sure);
+        assert!(game.achievements["sharpshooter"].unlocked);
+    }
+    
+    #[test]
+    fn same_day_cleanups_keep_the_streak() {
+        let mut game = Gamification::new();
+        cleanup_on(&mut game, 3);
+        cleanup_on(&mut game, 3);
+        assert_eq!(game.current_streak, 1);
+    }
+    
+    #[test]
+    fn consecutive_days_grow_the_streak() {
+        let mut game = Gamification::new();
+        for d in 3..=5 {
+            cleanup_on(&mut game, d);
+        }
+        assert_eq!(game.current_streak, 3);
+        assert_eq!(game.longest_streak, 3);
+    }
+    
+    #[test]
+    fn a_skipped_day_starts_over() {
+        let mut game = Gamification::new();
+        cleanup_on(&mut game, 3);
+        cleanup_on(&mut game, 4);
+        cleanup_on(&mut game, 6);
+        assert_eq!(game.current_streak, 1);
+        assert_eq!(game.longest_streak, 2);
+    }
+    
+    #[test]
+    fn weekly_streaks_count_calendar_weeks() {
+        let weekly = StreakWindow::Weekly;
+        // Monday and Sunday of the same week
+        assert_eq!(weekly.next_streak(2, Some(day(3)), day(9)), 2);
+        // Sunday, then the next Monday
+        assert_eq!(weekly.next_streak(2, Some(day(9)), day(10)), 3);
+        // A whole week skipped
+        assert_eq!(weekly.next_streak(2, Some(day(3)), day(17)), 1);
+    }
+    
+    #[test]
+    fn the_first_cleanup_starts_a_streak() {
+        assert_eq!(StreakWindow::Monthly.next_streak(0, None, day(3)), 1);
+        assert_eq!(StreakWindow::Monthly.next_streak(4, Some(day(3)), day(30)), 4);
+    }
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::{Result, Context};
+use crate::config::{CleanupAction, Config};
+
+const HISTORY_FILE: &str = ".cleancrush_history.json";
+
+/// How many cleanups the journal remembers
+pub const HISTORY_LIMIT: usize = 10;
+
+/// One cleanup: which files it removed and where they went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupRecord {
+    pub cleaned_at: DateTime<Utc>,
+    /// e.g. "duplicates" or "post-exam cleanup"
+    pub operation: String,
+    pub action: CleanupAction,
+    pub files: Vec<PathBuf>,
+    /// Already put back with `restore-last`
+    #[serde(default)]
+    pub restored: bool,
+}
+
+/// Journal of recent cleanups, newest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    pub cleanups: Vec<CleanupRecord>,
+}
+
+impl History {
+    /// Get the path to the journal file
+    pub fn path() -> Result<PathBuf> {
+        Config::state_path(HISTORY_FILE)
+    }
+
+    /// Load the journal, or start an empty one
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save the journal (written to a temp file first so it's never half-written)
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let tmp_path = path.with_extension("json.tmp");
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(&tmp_path, data)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Add a cleanup that just finished
+    pub fn record(operation: &str, action: CleanupAction, files: &[PathBuf]) -> Result<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        let mut history = Self::load()?;
+        history.cleanups.insert(0, CleanupRecord {
+            cleaned_at: Utc::now(),
+            operation: operation.to_string(),
+            action,
+            files: files.to_vec(),
+            restored: false,
+        });
+        history.cleanups.truncate(HISTORY_LIMIT);
+        history.save()
+    }
+
+    /// Mark the cleanup that removed exactly `files` as restored, once another
+    /// command (like `exam undo`) has put them back
+    pub fn mark_restored(files: &[PathBuf]) -> Result<()> {
+        let mut history = Self::load()?;
+        let Some(record) = history.cleanups.iter_mut().find(|r| !r.restored && r.files == files) else {
+            return Ok(());
+        };
+        record.restored = true;
+        history.save()
+    }
+
+    /// The most recent cleanup that hasn't been restored yet, so repeated
+    /// undos step back through the journal
+    pub fn last_cleanup(&mut self) -> Option<&mut CleanupRecord> {
+        self.cleanups.iter_mut().find(|record| !record.restored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_home;
+
+    #[test]
+    fn repeated_undos_walk_back_through_the_journal() {
+        let (_guard, home) = isolated_home();
+        let _ = fs::remove_file(History::path().unwrap());
+        History::record("old files", CleanupAction::RecycleBin, &[home.join("old.pdf")]).unwrap();
+        History::record("duplicates", CleanupAction::Archive, &[home.join("copy.pdf")]).unwrap();
+
+        for expected in ["duplicates", "old files"] {
+            let mut history = History::load().unwrap();
+            let last = history.last_cleanup().unwrap();
+            assert_eq!(last.operation, expected);
+            last.restored = true;
+            history.save().unwrap();
+        }
+
+        assert!(History::load().unwrap().last_cleanup().is_none());
+    }
+
+    #[test]
+    fn cleanups_put_back_elsewhere_are_skipped_by_undo() {
+        let (_guard, home) = isolated_home();
+        let _ = fs::remove_file(History::path().unwrap());
+        let exam_files = vec![home.join("slides.pdf"), home.join("notes.pdf")];
+        History::record("old files", CleanupAction::RecycleBin, &[home.join("old.pdf")]).unwrap();
+        History::record("post-exam cleanup", CleanupAction::RecycleBin, &exam_files).unwrap();
+
+        History::mark_restored(&exam_files).unwrap();
+
+        let mut history = History::load().unwrap();
+        assert_eq!(history.last_cleanup().unwrap().operation, "old files");
+    }
+}
@@ -0,0 +1,158 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use chrono::{DateTime, Utc};
+use walkdir::WalkDir;
+use colored::*;
+use anyhow::Result;
+use crate::colors;
+use crate::archive::ArchiveSystem;
+use crate::cli::format_size;
+use crate::config::{Config, ProtectionType};
+use crate::exam::ExamManager;
+
+/// Installers and downloaded archives that are usually dead weight once used
+const INSTALLER_EXTENSIONS: &[&str] = &[
+    "zip", "tar", "gz", "tgz", "bz2", "xz", "7z", "rar",
+    "dmg", "pkg", "exe", "msi", "deb", "rpm", "appimage", "iso",
+];
+
+/// Confidence given to installers past the age cutoff
+const INSTALLER_CONFIDENCE: f32 = 0.95;
+
+/// An old installer or archive found by the sweep
+#[derive(Debug, Clone)]
+pub struct InstallerFile {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub days_old: i64,
+    pub confidence: f32,
+    pub soft_protected: bool,
+}
+
+/// Files the sweep deliberately left alone
+#[derive(Debug, Clone, Default)]
+pub struct SweepSkips {
+    pub in_cloud: usize,
+    pub exam_tracked: usize,
+    pub protected: usize,
+}
+
+/// Check if a file looks like an installer or downloaded archive
+pub fn is_installer(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| INSTALLER_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Find installers older than `min_days` directly in `root` (and one level down)
+pub fn find_installers(
+    root: &Path,
+    min_days: u64,
+    config: &Config,
+    exam_manager: &ExamManager,
+) -> Result<(Vec<InstallerFile>, SweepSkips)> {
+    let archive_system = ArchiveSystem::new(config.clone())?;
+    let now = Utc::now();
+    let mut found = Vec::new();
+    let mut skips = SweepSkips::default();
+    
+    let walker = WalkDir::new(root)
+        .max_depth(2)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+    
+    for entry in walker {
+        let path = entry.path();
+        if !is_installer(path) {
+            continue;
+        }
+        
+        let metadata = match fs::metadata(path) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+        let modified: DateTime<Utc> = metadata.modified()
+            .unwrap_or_else(|_| SystemTime::now())
+            .into();
+        let days_old = (now - modified).num_days();
+        if days_old < min_days as i64 {
+            continue;
+        }
+        
+        // Never touch exam material, cloud-synced copies or hard-protected folders
+        if exam_manager.is_tracked(path) {
+            skips.exam_tracked += 1;
+            continue;
+        }
+        if archive_system.is_in_cloud_folder(path) {
+            skips.in_cloud += 1;
+            continue;
+        }
+        let soft_protected = match config.is_protected(path) {
+            Some(protected) if matches!(protected.protection_type, ProtectionType::Hard) => {
+                skips.protected += 1;
+                continue;
+            }
+            Some(_) => true,
+            None => false,
+        };
+        
+        found.push(InstallerFile {
+            path: path.to_path_buf(),
+            size_bytes: metadata.len(),
+            days_old,
+            confidence: INSTALLER_CONFIDENCE,
+            soft_protected,
+        });
+    }
+    
+    // Biggest first - they're the most worthwhile
+    found.sort_by_key(|f| std::cmp::Reverse(f.size_bytes));
+    
+    Ok((found, skips))
+}
+
+/// Print the sweep results
+pub fn print_installers(files: &[InstallerFile], skips: &SweepSkips, min_days: u64) {
+    println!();
+    println!("{}", "📦 OLD INSTALLERS & ARCHIVES".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+    
+    if files.is_empty() {
+        println!("{} No installers or archives older than {} days ✨", "✨".green(), min_days);
+    } else {
+        let total: u64 = files.iter().map(|f| f.size_bytes).sum();
+        println!("{} files, {} - already used, safe to clear", 
+            files.len().to_string().color(colors::SUCCESS),
+            format_size(total).color(colors::SUCCESS));
+        println!();
+        
+        for (i, file) in files.iter().enumerate() {
+            println!("{:3}. [{}{:.2}{}] {} ({}, {} days old)",
+                i + 1,
+                "⚡".color(colors::HIGH_CONFIDENCE),
+                file.confidence,
+                "⚡".color(colors::SUCCESS),
+                file.path.display().to_string().color(colors::PATH),
+                format_size(file.size_bytes),
+                file.days_old);
+            if file.soft_protected {
+                println!("     {} Protected folder (soft)", "🛡️".blue());
+            }
+        }
+    }
+    
+    if skips.exam_tracked > 0 {
+        println!("{} {} skipped (tracked for exams)", "🎓".cyan(), skips.exam_tracked);
+    }
+    if skips.in_cloud > 0 {
+        println!("{} {} skipped (in cloud folders)", "☁️".cyan(), skips.in_cloud);
+    }
+    if skips.protected > 0 {
+        println!("{} {} skipped (hard protected)", "🛡️".cyan(), skips.protected);
+    }
+}
@@ -6,9 +6,24 @@ pub mod exam;
 pub mod archive;
 pub mod gamification;
 pub mod cli;
+pub mod doctor;
+pub mod lock;
+pub mod digest;
+pub mod installers;
+pub mod progress;
+pub mod trash_manifest;
+pub mod report;
+pub mod logging;
+pub mod calibration;
+pub mod history;
+pub mod score_history;
+pub mod downloads;
+pub mod dev_cache;
+#[cfg(test)]
+mod test_support;
 
 // Re-exports for easy access
-pub use config::{Config, CleanupAction, ProtectedFolder, ProtectionType, ReminderSchedule, ExamTrackingState};
+pub use config::{Config, CleanupAction, ConfidenceModel, ProtectedFolder, ProtectionType, ReminderSchedule, ExamTrackingState};
 pub use scanner::{FileInfo, ScanResult, Scanner};
 pub use exam::{ExamManager, ExamTracker, PostExamChoice};
 pub use archive::{ArchiveSystem, ArchiveInfo};
@@ -79,14 +94,16 @@ pub const SYSTEM_PATHS: &[&str] = &[
     "/bin", "/sbin", "/etc", "/var", "/lib",
 ];
 
-/// Course detection patterns
+/// Built-in course keywords, the default `courses` in the config
 pub const COURSE_PATTERNS: &[(&str, &[&str])] = &[
-    ("cs", &["cs", "computer", "programming", "algorithm", "software"]),
+    ("cs", &["cs", "computer", "programming", "algorithm", "software", "data structures"]),
     ("math", &["math", "calculus", "algebra", "statistics", "geometry"]),
-    ("science", &["physics", "chemistry", "biology", "science", "lab"]),
+    ("science", &["physics", "chemistry", "biology", "science", "lab",
+        "mechanics", "quantum", "organic", "genetics", "ecology"]),
     ("engineering", &["engineer", "mechanical", "electrical", "civil", "robotics"]),
     ("business", &["business", "management", "finance", "economics", "marketing"]),
-    ("humanities", &["history", "literature", "philosophy", "art", "psychology"]),
+    ("humanities", &["history", "literature", "philosophy", "art", "psychology",
+        "english", "novel"]),
 ];
 
 /// Cute encouragement messages
@@ -102,7 +119,7 @@ pub const ENCOURAGEMENTS: &[&str] = &[
 ];
 
 /// Unified FileCategory enum
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum FileCategory {
     Lecture,
     Assignment,
@@ -111,4 +128,5 @@ pub enum FileCategory {
     Duplicate,
     Old,
     Large,
+    Empty,
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+use anyhow::{Result, Context};
+use colored::*;
+use fs4::fs_std::FileExt;
+use crate::config::Config;
+
+/// How long a second instance waits before giving up
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+const LOCK_FILE: &str = ".cleancrush.lock";
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Exclusive lock held while a command may change saved state.
+///
+/// This is an OS file lock, so it is released when the process exits or
+/// crashes; the lock file itself is left behind and is harmless.
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Get the path to the lock file
+    pub fn lock_path() -> Result<PathBuf> {
+        Config::state_path(LOCK_FILE)
+    }
+    
+    /// Take the lock, waiting up to `timeout` for another instance to finish
+    pub fn acquire(timeout: Duration) -> Result<Self> {
+        let path = Self::lock_path()?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+        
+        let start = Instant::now();
+        let mut announced = false;
+        
+        loop {
+            let acquired = file.try_lock_exclusive()
+                .with_context(|| format!("Failed to lock {}", path.display()))?;
+            
+            if acquired {
+                // Record who holds the lock, for anyone inspecting the file
+                file.set_len(0)?;
+                write!(file, "{}", std::process::id())?;
+                return Ok(Self { _file: file });
+            }
+            
+            if start.elapsed() >= timeout {
+                anyhow::bail!(
+                    "Another CleanCrush instance is running (waited {}s). Try again when it finishes.",
+                    timeout.as_secs()
+                );
+            }
+            
+            if !announced {
+                println!("{} Another CleanCrush instance is running, waiting for it to finish...", 
+                    "⏳".yellow());
+                announced = true;
+            }
+            
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
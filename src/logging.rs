@@ -0,0 +1,43 @@
+use std::fs::OpenOptions;
+use std::path::Path;
+use anyhow::{Result, Context};
+use crate::cli::LogLevel;
+
+/// Start the diagnostic log. Nothing is logged unless `--log-level` or
+/// `--log-file` is given; the log goes to the file if there is one, else stderr.
+pub fn init(level: Option<LogLevel>, file: Option<&Path>) -> Result<()> {
+    let level = match (level, file) {
+        (Some(level), _) => level,
+        (None, Some(_)) => LogLevel::Info,
+        (None, None) => return Ok(()),
+    };
+    
+    let filter = match level {
+        LogLevel::Off => log::LevelFilter::Off,
+        LogLevel::Error => log::LevelFilter::Error,
+        LogLevel::Warn => log::LevelFilter::Warn,
+        LogLevel::Info => log::LevelFilter::Info,
+        LogLevel::Debug => log::LevelFilter::Debug,
+        LogLevel::Trace => log::LevelFilter::Trace,
+    };
+    
+    // Dependencies only get to add warnings and errors
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(filter.min(log::LevelFilter::Warn))
+        .filter_module("cleancrush", filter);
+    
+    if let Some(file) = file {
+        // Appended, so runs (e.g. scheduled ones) build up a history
+        let log_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(file)
+            .with_context(|| format!("Failed to open log file {}", file.display()))?;
+        builder.target(env_logger::Target::Pipe(Box::new(log_file)));
+    }
+    
+    builder.try_init().context("Failed to start logging")?;
+    log::info!("cleancrush {} started: {}", env!("CARGO_PKG_VERSION"),
+        std::env::args().collect::<Vec<_>>().join(" "));
+    Ok(())
+}
@@ -4,23 +4,49 @@ mod exam;
 mod archive;
 mod gamification;
 mod cli;
+mod doctor;
+mod lock;
+mod digest;
+mod installers;
+mod progress;
+mod trash_manifest;
+mod report;
+mod logging;
+mod calibration;
+mod history;
+mod score_history;
+mod downloads;
+mod dev_cache;
+#[cfg(test)]
+mod test_support;
 
 use anyhow::{Result, Context};
 use clap::Parser;
 use colored::*;
 use chrono::Utc;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::fs;
 use dirs;
 use crate::cli::{Cli, Commands};
-use crate::config::{Config, ProtectedFolder, ProtectionType, ReminderSchedule};
-use crate::scanner::Scanner;
-use crate::exam::{ExamManager, PostExamChoice};
-use crate::archive::ArchiveSystem;
-use crate::gamification::{Gamification, CleanupType};
+use crate::config::{Config, CleanupAction, ConfidenceModel, CourseOverride, ProtectedFolder, ProtectionType, ReminderSchedule};
+use crate::scanner::{Scanner, ScanOptions, ScanResult, SavedScan};
+use crate::exam::{EndedExam, ExamManager, PostExamChoice};
+use crate::archive::{ArchiveSystem, OutputLevel, UntidyReason};
+use crate::gamification::{Gamification, CleanupType, ConfidenceSummary};
+use crate::lock::{InstanceLock, LOCK_TIMEOUT};
+use crate::trash_manifest::{TrashManifest, RestoreOutcome};
+use crate::history::History;
+use crate::score_history::ScoreHistory;
+use cleancrush::COURSE_PATTERNS;
 
 const DEFAULT_OLD_DAYS: u64 = 60;
 const DEFAULT_LARGE_MB: u64 = 100;
+/// `scan --stream` shows (and `--stream-clean` cleans) suggestions above this,
+/// the same bar as `clean --mode confidence`
+const STREAM_CONFIDENCE: f32 = 0.8;
+/// `--stream-clean` cleans in batches of this many files
+const STREAM_CLEAN_BATCH: usize = 25;
 const ENCOURAGEMENTS: &[&str] = &[
     "✨ Your folder is 72% cleaner than last week!",
     "💖 Small steps beat big chaos. You've got this!",
@@ -32,7 +58,7 @@ const ENCOURAGEMENTS: &[&str] = &[
     "🌟 Organized space, organized mind. Great job!",
 ];
 /// Unified FileCategory enum
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum FileCategory {
     Lecture,
     Assignment,
@@ -41,6 +67,7 @@ pub enum FileCategory {
     Duplicate,
     Old,
     Large,
+    Empty,
 }
 pub mod colors {
     use colored::Color;
@@ -63,14 +90,29 @@ fn main() -> Result<()> {
         colored::control::set_override(false);
     }
     
+    progress::set_mode(cli.progress);
+    // `scan --json` keeps stdout to the JSON itself
+    let json_scan = matches!(&cli.command, Commands::Scan(args) if args.json);
+    progress::set_quiet(cli.quiet || json_scan);
+    logging::init(cli.log_level, cli.log_file.as_deref())?;
+    
+    let command = cli.command.name();
+    run(cli).inspect_err(|e| log::error!("{} failed: {:#}", command, e))
+}
+
+fn run(cli: Cli) -> Result<()> {    
     // Handle help and version commands first
     match cli.command {
         Commands::ShowHelp => {
             Cli::print_help();
             return Ok(());
         }
-        Commands::Version => {
-            Cli::print_version();
+        Commands::Version(args) => {
+            if args.json {
+                Cli::print_version_json();
+            } else {
+                Cli::print_version();
+            }
             return Ok(());
         }
         _ => {}
@@ -89,16 +131,31 @@ fn main() -> Result<()> {
         println!();
     }
     
+    // Only one instance may change saved state at a time
+    let _lock = if cli.command.mutates_state() {
+        Some(InstanceLock::acquire(LOCK_TIMEOUT)?)
+    } else {
+        None
+    };
+    
+    // Setup writes the config itself
+    if let Commands::Init(args) = &cli.command {
+        return handle_init(args, cli.safe);
+    }
+    
     // Load or create config WITH CONTEXT
     let mut config = Config::load().context("Failed to load configuration")?;
     
+    // `status` reports these itself, without prompting
+    let nag = !cli.safe && !progress::quiet() && !matches!(cli.command, Commands::Status);
+    
     // Check for reminders
-    if !cli.safe && config.is_reminder_due() {
-        show_reminder(&config);
+    if nag && config.is_reminder_due() {
+        show_reminder(&config, !cli.no_scan, cli.command.mutates_state());
     }
     
     // Check for archive reminders
-    if !cli.safe {
+    if nag {
         let archive_system = ArchiveSystem::new(config.clone())
             .context("Failed to create archive system")?;
         
@@ -114,20 +171,31 @@ fn main() -> Result<()> {
     }
 
     // Create gamification system
-    let mut gamification = Gamification::load_from_config(&config);
+    let mut gamification = Gamification::load(&config);
     
     // Create exam manager
     let mut exam_manager = ExamManager::new(config.clone());
     exam_manager.load_from_config()?;
     
+    // A period set with `exam set` doesn't end by itself; exam commands and
+    // `status` report it their own way. Read-only commands don't hold the
+    // instance lock, so they only mention it.
+    if !progress::quiet() && !matches!(cli.command, Commands::Exam(_) | Commands::Status) {
+        if let Some(end_date) = exam_manager.overdue_end_date() {
+            let notify_only = cli.safe || !cli.command.mutates_state();
+            offer_exam_end(&mut config, &mut exam_manager, end_date, notify_only, &mut gamification)?;
+        }
+    }
+    
     // Handle command
     match cli.command {
         Commands::Scan(args) => handle_scan(
-            &config, 
+            &mut config, 
             &mut exam_manager, 
             &args, 
             cli.safe, 
             cli.verbose,
+            &mut gamification,
         )?,
         
         Commands::Suggest(args) => handle_suggest(
@@ -139,17 +207,19 @@ fn main() -> Result<()> {
         
         Commands::Clean(args) => handle_clean(
             &mut config, 
-            &exam_manager, 
+            &mut exam_manager, 
             &args, 
             cli.safe,
+            cli.verbose,
             &mut gamification,
         )?,
         
         Commands::Delete(args) => handle_delete(
             &mut config, 
-            &exam_manager, 
+            &mut exam_manager, 
             &args, 
             cli.safe, 
+            cli.verbose,
             &mut gamification,
         )?,
         
@@ -164,37 +234,179 @@ fn main() -> Result<()> {
         Commands::Protect(subcommand) => handle_protect(&mut config, subcommand)?,
         
         Commands::Archive(subcommand) => handle_archive(&config, subcommand, cli.safe)?,
+        Commands::Trash(subcommand) => handle_trash(subcommand, cli.safe)?,
+        
+        Commands::RestoreLast(args) => handle_restore_last(&config, &args, cli.safe)?,
+        
+        Commands::IgnoreFile(args) => handle_ignore_file(&mut config, &args)?,
+        
+        Commands::Course(subcommand) => handle_course(&mut config, subcommand)?,
+        
+        Commands::SweepInstallers(args) => handle_sweep_installers(
+            &mut config,
+            &exam_manager,
+            &args,
+            cli.safe,
+            &mut gamification,
+        )?,
+        
+        Commands::DedupeDownloads(args) => handle_dedupe_downloads(
+            &mut config,
+            &exam_manager,
+            &args,
+            cli.safe,
+            &mut gamification,
+        )?,
+        
+        Commands::SweepCache(args) => handle_sweep_cache(
+            &mut config,
+            &exam_manager,
+            &args,
+            cli.safe,
+            &mut gamification,
+        )?,
+        
+        Commands::Schedule(subcommand) => handle_schedule(&mut config, &exam_manager, subcommand, cli.safe)?,
+        
+        Commands::Stats(args) => handle_stats(&mut config, &mut gamification, &args, cli.safe)?,
         
-        Commands::Schedule(subcommand) => handle_schedule(&mut config, subcommand)?,
+        Commands::Status => handle_status(&config, &exam_manager, &gamification)?,
         
-        Commands::Stats => handle_stats(&config, &gamification)?,
+        Commands::Score(args) => handle_score(&config, &args, cli.safe)?,
         
-        Commands::Score(args) => handle_score(&config, &args)?,
+        Commands::Top(args) => handle_top(&config, &args)?,
         
-        Commands::Config => config.display(),
+        Commands::Config(args) => handle_config(&mut config, args, cli.safe)?,
         
         Commands::Achievements => handle_achievements(&gamification)?,
 
-        Commands::ShowHelp | Commands::Version => unreachable!(),
+        Commands::Doctor(args) => doctor::run(&mut config, &mut gamification, args.yes)?,
+        
+        Commands::Calibrate(args) => handle_calibrate(&config, &exam_manager, &args, cli.safe)?,
+
+        Commands::ShowHelp | Commands::Version(_) | Commands::Init(_) => unreachable!(),
+    }
+    
+    Ok(())
+}
+
+fn handle_init(args: &cli::InitArgs, safe_mode: bool) -> Result<()> {
+    let config_path = Config::config_path()?;
+    let preview = args.dry_run || safe_mode;
+    
+    if config_path.exists() && !args.force && !preview {
+        anyhow::bail!("Config already exists at {} (use --force to replace it)", config_path.display());
     }
     
+    let mut config = if args.non_interactive || config::non_interactive() {
+        Config::defaults()
+    } else {
+        Config::first_time_setup()?
+    };
+    
+    // Replacing a config keeps the streaks and totals earned with it, like `config reset`
+    if config_path.exists() {
+        match Config::load() {
+            Ok(old) => config.keep_progress_from(&old),
+            Err(e) => println!("{} Couldn't read the old config, so progress starts over: {:#}", "⚠️".yellow(), e),
+        }
+    }
+    
+    if preview {
+        println!("{}", serde_json::to_string_pretty(&config)?);
+        return Ok(());
+    }
+    
+    config.save()?;
+    log::info!("Saved config to {}", config_path.display());
+    println!("{} Config saved to {}", "✅".green(), config_path.display().to_string().color(colors::PATH));
+    println!("{} Try: {}", "💡".cyan(), "cleancrush scan ~/Downloads".bold());
     Ok(())
 }
 
 fn handle_scan(
-    config: &Config,
+    config: &mut Config,
     exam_manager: &mut ExamManager,
     args: &cli::ScanArgs,
     safe_mode: bool,
     verbose: bool,
+    gamification: &mut Gamification,
 ) -> Result<()> {
-    let path = args.path.canonicalize().unwrap_or(args.path.clone());
+    // No path: scan the folders that usually get cluttered
+    let paths = match &args.path {
+        Some(path) => vec![path.clone()],
+        None => config.scan_paths(),
+    };
     
-    let scanner = Scanner::new(config.clone(), exam_manager.is_active());
-    let result = scanner.scan(&path, args.days, args.large)
-        .context("Failed to scan directory")?;
+    if paths.len() > 1 && (args.report.is_some() || args.save.is_some() || args.json) {
+        anyhow::bail!("--report, --save and --json need a single folder, e.g. cleancrush scan {} --save scan.json", 
+            paths[0].display());
+    }
     
-    scanner.print_results(&result, args.detailed);
+    for path in &paths {
+        refuse_broad_path(path, args.i_know_what_im_doing)?;
+    }
+    
+    for path in &paths {
+        scan_folder(config, exam_manager, args, path, safe_mode, verbose, gamification)?;
+    }
+    
+    Ok(())
+}
+
+/// Scan one folder for `cleancrush scan`
+fn scan_folder(
+    config: &mut Config,
+    exam_manager: &mut ExamManager,
+    args: &cli::ScanArgs,
+    path: &Path,
+    safe_mode: bool,
+    verbose: bool,
+    gamification: &mut Gamification,
+) -> Result<()> {
+    let path = path.canonicalize().unwrap_or(path.to_path_buf());
+    
+    let options = ScanOptions {
+        max_files: Some(args.limit),
+        max_depth: Some(args.depth),
+        pretend_full: args.pretend_full,
+        inspect_archives: args.inspect_archives,
+        verbose,
+        ..ScanOptions::default()
+    }.with_extensions(&args.ext)?
+        .with_excluded_extensions(&args.exclude_ext)
+        .with_excludes(&args.exclude)?
+        .with_size_range(args.min_size, args.max_size)?;
+    let scanner = Scanner::new(config.clone(), exam_manager.is_active())
+        .with_options(options);
+    let result = if args.stream {
+        stream_scan(config, exam_manager, &scanner, args, &path, safe_mode, gamification)?
+    } else {
+        let result = scanner.scan(&path, args.days, args.large)
+            .context("Failed to scan directory")?;
+        if args.json {
+            print_scan_json(&result, args.out.as_deref())?;
+        } else {
+            scanner.print_results(&result, args.detailed);
+        }
+        result
+    };
+    if args.histogram {
+        scanner.print_age_histogram(&result);
+    }
+    
+    if let Some(report_path) = &args.report {
+        write_scan_report(config, &path, &result, report_path)?;
+    }
+    
+    SavedScan::save_last(&path, &result);
+    if let Some(save_path) = &args.save {
+        SavedScan::save(&path, &result, save_path)?;
+        println!("{} Scan saved to {} - reuse it with {}", 
+            "💾".green(),
+            save_path.display().to_string().color(colors::PATH),
+            format!("--from {}", save_path.display()).bold());
+    }
     
     // AUTO-DETECTION FOR EXAM MODE
     if !exam_manager.is_active() && config.enable_exam_monitoring {
@@ -238,8 +450,14 @@ fn handle_scan(
         }
     }
     
+    // Persist tracked files (and their fingerprints) for `exam end`
+    if exam_manager.is_active() && !safe_mode {
+        config.exam_tracking = exam_manager.tracking_state();
+        config.save().context("Failed to save exam tracking")?;
+    }
+    
     // Show exam mode status if active
-    if exam_manager.is_active() {
+    if exam_manager.is_active() && !progress::quiet() {
         if let Some(tracker) = exam_manager.get_tracker() {
             println!();
             println!("{} Exam mode active: tracking {} files", 
@@ -250,7 +468,7 @@ fn handle_scan(
     }
     
     // Show gamification
-    if !safe_mode && !result.files.is_empty() && !verbose {
+    if !safe_mode && !result.files.is_empty() && !verbose && !progress::quiet() {
         println!("{}", "💖".color(colors::HIGH_CONFIDENCE));
         println!("{}", ENCOURAGEMENTS[rand::random::<usize>() % ENCOURAGEMENTS.len()]);
     }
@@ -258,74 +476,414 @@ fn handle_scan(
     Ok(())
 }
 
+/// `scan --stream`: print high-confidence suggestions as they're analyzed
+/// and, with `--stream-clean`, clean them in small batches along the way.
+/// Returns the scan without the files that were cleaned.
+fn stream_scan(
+    config: &mut Config,
+    exam_manager: &mut ExamManager,
+    scanner: &Scanner,
+    args: &cli::ScanArgs,
+    path: &Path,
+    safe_mode: bool,
+    gamification: &mut Gamification,
+) -> Result<ScanResult> {
+    let cleaning = args.stream_clean && !safe_mode;
+    if args.stream_clean && safe_mode {
+        println!("{} SAFE MODE: streaming suggestions only, nothing will be cleaned", "🔒".yellow());
+    }
+    
+    // The file count isn't known up front, so any policy that might ask does
+    let policy = config.confirm_defaults.clean.resolve(args.yes, false);
+    if cleaning && policy.should_confirm(usize::MAX) {
+        let action = match config.default_action {
+            CleanupAction::RecycleBin => "moved to the Recycle Bin",
+            CleanupAction::Archive => "archived",
+        };
+        println!("{} Files with confidence above {:.1} in {} will be {} as they're found",
+            "🌊".cyan(), STREAM_CONFIDENCE, path.display(), action);
+        let proceed = dialoguer::Confirm::new()
+            .with_prompt("Start streaming cleanup?")
+            .default(false)
+            .interact()
+            .context("Failed to get confirmation")?;
+        if !proceed {
+            println!("{} Cleanup cancelled", "ℹ️".cyan());
+            return Ok(ScanResult::empty());
+        }
+    }
+    
+    let archive_system = if cleaning {
+        // The batches are journaled as one cleanup below
+        Some(ArchiveSystem::new(config.clone()).context("Failed to create archive system")?.without_history())
+    } else {
+        None
+    };
+    // Suggestions are judged against the settings as the run started; each
+    // batch gets the same exam checks as `clean`
+    let settings = config.clone();
+    let mut flush = |batch: &mut Vec<PathBuf>, cleaned: &mut archive::CleanupResult| -> Result<()> {
+        if let Some(archive_system) = &archive_system {
+            let files = check_exam_tracked(exam_manager, std::mem::take(batch), args.yes, false)?;
+            if !files.is_empty() {
+                let result = archive_system.clean_files(&files, false, false, "streamed suggestions")?;
+                untrack_cleaned(config, exam_manager, &result.successful_files)?;
+                cleaned.files_processed += result.files_processed;
+                cleaned.total_size_bytes += result.total_size_bytes;
+                cleaned.successful_files.extend(result.successful_files);
+            }
+        }
+        batch.clear();
+        Ok(())
+    };
+    
+    println!("{} Streaming suggestions above {:.1} in discovery order (not ranked)",
+        "🌊".cyan(), STREAM_CONFIDENCE);
+    println!();
+    
+    let mut shown = 0;
+    let mut batch = Vec::new();
+    let mut cleaned = archive::CleanupResult::empty();
+    let mut confidences = HashMap::new();
+    // The first copy of each duplicate group seen is always kept
+    let mut kept_hashes = std::collections::HashSet::new();
+    
+    let result = scanner.scan_streaming(path, args.days, args.large, |file| {
+        if file.confidence <= STREAM_CONFIDENCE {
+            return Ok(());
+        }
+        shown += 1;
+        print_suggestion(&settings, shown, file);
+        
+        if !cleaning {
+            return Ok(());
+        }
+        if let Some(hash) = file.hash.as_ref().filter(|_| file.duplicate_of.is_none()) {
+            if file.category == FileCategory::Duplicate && kept_hashes.insert(hash.clone()) {
+                println!("     {} Kept as the copy of this duplicate group", "📌".cyan());
+                return Ok(());
+            }
+        }
+        if file.symlink_target.is_some() || settings.is_protected(&file.path).is_some() {
+            return Ok(());
+        }
+        
+        confidences.insert(file.path.clone(), file.confidence);
+        batch.push(file.path.clone());
+        if batch.len() >= STREAM_CLEAN_BATCH {
+            flush(&mut batch, &mut cleaned)?;
+        }
+        Ok(())
+    }).context("Failed to scan directory")
+        .and_then(|result| flush(&mut batch, &mut cleaned).map(|_| result));
+    
+    // One `restore-last` entry for the whole run, even one that stopped partway
+    if let Err(e) = History::record("streamed suggestions", config.default_action.clone(), &cleaned.successful_files) {
+        println!("{} Couldn't record this cleanup for restore-last: {:#}", "⚠️".yellow(), e);
+    }
+    let mut result = result?;
+    
+    println!();
+    println!("{} {} high-confidence suggestion{} of {} ({} files scanned)",
+        "🌊".cyan(),
+        shown,
+        if shown == 1 { "" } else { "s" },
+        result.files.len(),
+        result.total_files_scanned);
+    println!("{} Run {} for the full list ranked by confidence",
+        "💡".cyan(),
+        format!("cleancrush suggest {}", path.display()).bold());
+    
+    if cleaned.files_processed > 0 {
+        result.files.retain(|f| !cleaned.successful_files.contains(&f.path));
+        
+        config.update_stats(cleaned.files_processed, cleaned.total_size_bytes);
+        config.update_last_cleanup()?;
+        digest::refresh_after_cleanup(&cleaned.successful_files);
+        
+        let unlocks = gamification.update_after_cleanup(
+            cleaned.files_processed,
+            cleaned.total_size_bytes,
+            CleanupType::Normal,
+            exam_manager.is_active(),
+            cleaned_confidence(&confidences, &cleaned.successful_files),
+        );
+        record_unlocks(config, gamification, &unlocks)?;
+        
+        gamification.show_encouragement(
+            cleaned.files_processed,
+            cleaned.total_size_bytes / (1024 * 1024),
+            &unlocks,
+        );
+    }
+    
+    Ok(result)
+}
+
+/// Load a scan saved with `scan --save`, warning about files that went
+/// missing or changed since. Returns the scan and those drifted paths,
+/// which must not be acted on.
+fn load_saved_scan(file: &Path) -> Result<(ScanResult, Vec<PathBuf>)> {
+    Ok(use_saved_scan(SavedScan::load(file)?))
+}
+
+/// Announce a saved scan and warn about files that drifted since
+fn use_saved_scan(saved: SavedScan) -> (ScanResult, Vec<PathBuf>) {
+    let hours_ago = (Utc::now() - saved.scanned_at).num_hours();
+    
+    println!("{} Using saved scan of {} from {} ({})", 
+        "📂".cyan(),
+        saved.root.display().to_string().color(colors::PATH),
+        saved.scanned_at.format("%Y-%m-%d %H:%M"),
+        if hours_ago < 1 { "less than an hour ago".to_string() } else { format!("{} hours ago", hours_ago) });
+    
+    let drifted = saved.drifted_files();
+    if !drifted.is_empty() {
+        println!("{} {} file{} changed since this scan and will be skipped:", 
+            "⚠️".yellow(),
+            drifted.len(),
+            if drifted.len() == 1 { "" } else { "s" });
+        for (path, reason) in drifted.iter().take(10) {
+            println!("   • {} ({})", path.display().to_string().color(colors::PATH), reason);
+        }
+        if drifted.len() > 10 {
+            println!("   ... and {} more", drifted.len() - 10);
+        }
+        println!("   {} Run {} again for fresh results", "💡".cyan(), "cleancrush scan --save".bold());
+    }
+    
+    (saved.result, drifted.into_iter().map(|(path, _)| path).collect())
+}
+
+/// The last `scan`/`suggest` of `path`, so `delete` numbers match what was
+/// shown. Warns (and returns None) if there isn't one.
+fn last_scan_of(path: &Path) -> Option<(ScanResult, Vec<PathBuf>)> {
+    let root = path.canonicalize().unwrap_or(path.to_path_buf());
+    let last = SavedScan::last_scan_path().and_then(|file| SavedScan::load(&file));
+    match last {
+        Ok(saved) if saved.root == root => Some(use_saved_scan(saved)),
+        _ => {
+            println!("{} No recent scan of {} - scanning again, so numbers may differ from the last listing", 
+                "⚠️".yellow(), root.display().to_string().color(colors::PATH));
+            None
+        }
+    }
+}
+
+/// `scan --json`: the scan to stdout, or to `out` with a one-line confirmation
+fn print_scan_json(result: &scanner::ScanResult, out: Option<&Path>) -> Result<()> {
+    // The same confidence buckets the summary draws, as data
+    let mut value = serde_json::to_value(result)?;
+    value["confidence_histogram"] = serde_json::to_value(result.confidence_histogram())?;
+    let json = serde_json::to_string_pretty(&value)?;
+    let Some(out) = out else {
+        println!("{}", json);
+        return Ok(());
+    };
+    
+    if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(out, json)
+        .with_context(|| format!("Failed to write scan to {}", out.display()))?;
+    println!("{} Scan written to {}", "💾".green(), out.display().to_string().color(colors::PATH));
+    Ok(())
+}
+
+/// Write a Markdown report of a scan, asking before overwriting
+fn write_scan_report(
+    config: &Config,
+    root: &Path,
+    result: &scanner::ScanResult,
+    report_path: &Path,
+) -> Result<()> {
+    if report_path.exists() {
+        use dialoguer::{theme::ColorfulTheme, Confirm};
+        let overwrite = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("{} already exists. Overwrite?", report_path.display()))
+            .default(false)
+            .interact()
+            .context("Failed to get confirmation")?;
+        
+        if !overwrite {
+            println!("{} Report not written", "ℹ️".cyan());
+            return Ok(());
+        }
+    }
+    
+    let gamification = Gamification::load(config);
+    let (duplicates, old, large, very_large) = result.cleanliness_counts(config.very_large_bytes());
+    let (score, breakdown) = gamification.calculate_cleanliness_score(duplicates, old, large, very_large);
+    
+    let markdown = report::render_markdown(root, result, score, &breakdown);
+    fs::write(report_path, markdown)
+        .with_context(|| format!("Failed to write report to {}", report_path.display()))?;
+    
+    println!();
+    println!("{} Report written to {}", "📝".green(), report_path.display().to_string().color(colors::PATH));
+    
+    Ok(())
+}
+
+/// Grouping key for `suggest --group-by`
+fn suggestion_group(file: &scanner::FileInfo, group_by: cli::GroupBy) -> String {
+    match group_by {
+        cli::GroupBy::Course => file.course.clone(),
+        cli::GroupBy::Category => format!("{:?}", file.category),
+        cli::GroupBy::Folder => file.path.parent()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| ".".to_string()),
+    }
+}
+
+/// Print one numbered suggestion
+fn print_suggestion(config: &Config, index: usize, file: &scanner::FileInfo) {
+    let confidence_color = config.confidence_color(file.confidence);
+    
+    let size_mb = file.size_bytes as f32 / (1024.0 * 1024.0);
+    
+    println!("{:3}. [{}{:.2}{}] {}",
+        index,
+        "⚡".color(confidence_color),
+        file.confidence,
+        "⚡".color(colors::SUCCESS),
+        file.display_path().color(colors::PATH)
+    );
+    
+    println!("     {} ({:.1} MB, {} days old, {})",
+        file.reason.dimmed(),
+        size_mb,
+        file.days_old,
+        file.course_label().color(colors::HEADER)
+    );
+    println!("     {}", archive::destination_label(config, &file.path));
+    
+    if file.is_in_cloud {
+        println!("     {} In cloud folder", "☁️".yellow());
+    }
+    if file.is_locked {
+        println!("     {} File may be open", "⚠️".yellow());
+    }
+    if let Some(protected) = config.is_protected(&file.path) {
+        println!("     {} Protected folder ({})", 
+            "🛡️".blue(),
+            match protected.protection_type {
+                ProtectionType::Hard => "hard",
+                ProtectionType::Soft => "soft",
+            }
+        );
+    }
+    println!();
+}
+
+/// Reorder suggestion indices; the scan already ranks by confidence, and the
+/// stable sorts keep that order among ties
+fn sort_suggestions(indices: &mut [usize], files: &[scanner::FileInfo], sort: cli::SortBy) {
+    match sort {
+        cli::SortBy::Confidence => {}
+        cli::SortBy::Size => indices.sort_by_key(|&i| std::cmp::Reverse(files[i].size_bytes)),
+        cli::SortBy::Age => indices.sort_by_key(|&i| std::cmp::Reverse(files[i].days_old)),
+        cli::SortBy::Name => indices.sort_by_cached_key(|&i| {
+            files[i].path.file_name().unwrap_or_default().to_string_lossy().to_lowercase()
+        }),
+    }
+}
+
 fn handle_suggest(
     config: &Config,
     exam_manager: &ExamManager,
     args: &cli::SuggestArgs,
     safe_mode: bool,
 ) -> Result<()> {
-    let path = args.path.canonicalize().unwrap_or(args.path.clone());
-    
-    let scanner = Scanner::new(config.clone(), exam_manager.is_active());
-    let result = scanner.scan(&path, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
-        .context("Failed to scan directory for suggestions")?;
+    let options = ScanOptions {
+        inspect_archives: args.inspect_archives,
+        ..ScanOptions::default()
+    }.with_excludes(&args.exclude)?
+        .with_size_range(args.min_size, args.max_size)?;
+    let result = if let Some(from) = &args.from {
+        load_saved_scan(from)?.0
+    } else {
+        let path = args.path.canonicalize().unwrap_or(args.path.clone());
+        let scanner = Scanner::new(config.clone(), exam_manager.is_active())
+            .with_options(options.clone());
+        let result = scanner.scan(&path, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
+            .context("Failed to scan directory for suggestions")?;
+        SavedScan::save_last(&path, &result);
+        result
+    };
     
     if result.files.is_empty() {
         println!("{} No suggestions found. Your files look clean! ✨", "✨".green());
         return Ok(());
     }
     
+    // Filtered and re-sorted files keep their scan position so numbers still
+    // work with `cleancrush delete`
+    let category = args.category.as_ref().and_then(cli::FileCategory::to_core);
+    let mut shown: Vec<usize> = result.files.iter()
+        .enumerate()
+        .filter(|(_, file)| file.confidence >= args.confidence)
+        // A saved scan wasn't size-filtered when it was taken
+        .filter(|(_, file)| options.in_size_range(file.size_bytes))
+        .filter(|(_, file)| category.as_ref().is_none_or(|category| file.category == *category))
+        .map(|(i, _)| i)
+        .collect();
+    if shown.is_empty() {
+        match category {
+            _ if args.min_size.is_some() || args.max_size.is_some() => println!(
+                "{} No files in that size range above {:.2} confidence ✨", "✨".green(), args.confidence),
+            Some(category) => println!("{} No {} files above {:.2} confidence ✨",
+                "✨".green(), format!("{:?}", category).to_lowercase(), args.confidence),
+            None => println!("{} No files above {:.2} confidence - try a lower {} ✨",
+                "✨".green(), args.confidence, "--confidence".bold()),
+        }
+        return Ok(());
+    }
+    sort_suggestions(&mut shown, &result.files, args.sort);
+    
     println!();
     println!("{}", "🎯 CLEANUP SUGGESTIONS".bold().color(colors::HEADER));
     println!("{}", "─".repeat(50).color(colors::PATH));
     println!("{} files found - use numbers with {}", 
-        result.files.len().to_string().color(colors::SUCCESS),
+        shown.len().to_string().color(colors::SUCCESS),
         "cleancrush delete".bold()
     );
     println!();
     
-    for (i, file) in result.files.iter().enumerate() {
-        let confidence_color = if file.confidence > 0.8 {
-            colors::HIGH_CONFIDENCE
-        } else if file.confidence > 0.6 {
-            colors::MEDIUM_CONFIDENCE
-        } else {
-            colors::LOW_CONFIDENCE
-        };
-        
-        let size_mb = file.size_bytes as f32 / (1024.0 * 1024.0);
-        
-        println!("{:3}. [{}{:.2}{}] {}",
-            i + 1,
-            "⚡".color(confidence_color),
-            file.confidence,
-            "⚡".color(colors::SUCCESS),
-            file.path.display().to_string().color(colors::PATH)
-        );
-        
-        println!("     {} ({:.1} MB, {} days old, {})",
-            file.reason.dimmed(),
-            size_mb,
-            file.days_old,
-            file.course.color(colors::HEADER)
-        );
-        
-        if file.is_in_cloud {
-            println!("     {} In cloud folder", "☁️".yellow());
-        }
-        if file.is_locked {
-            println!("     {} File may be open", "⚠️".yellow());
+    match args.group_by {
+        None => {
+            for &i in &shown {
+                print_suggestion(config, i + 1, &result.files[i]);
+            }
         }
-        if let Some(protected) = config.is_protected(&file.path) {
-            println!("     {} Protected folder ({})", 
-                "🛡️".blue(),
-                match protected.protection_type {
-                    ProtectionType::Hard => "hard",
-                    ProtectionType::Soft => "soft",
+        Some(group_by) => {
+            // Groups appear in order of their first suggestion; indices stay
+            // global so they still work with `cleancrush delete`
+            let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+            for &i in &shown {
+                let key = suggestion_group(&result.files[i], group_by);
+                match groups.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, indices)) => indices.push(i),
+                    None => groups.push((key, vec![i])),
                 }
-            );
+            }
+            
+            for (key, indices) in &groups {
+                let group_bytes: u64 = indices.iter().map(|&i| result.files[i].size_bytes).sum();
+                println!("{} {} ({} file{}, {:.1} MB)",
+                    "📂".cyan(),
+                    key.bold().color(colors::HEADER),
+                    indices.len(),
+                    if indices.len() == 1 { "" } else { "s" },
+                    group_bytes as f64 / (1024.0 * 1024.0));
+                println!("{}", "─".repeat(50).color(colors::PATH));
+                
+                for &i in indices {
+                    print_suggestion(config, i + 1, &result.files[i]);
+                }
+            }
         }
-        println!();
     }
     
     // Show quick action options
@@ -337,7 +895,7 @@ fn handle_suggest(
     println!();
     
     // Show gamification
-    if !safe_mode {
+    if !safe_mode && !progress::quiet() {
         println!("{}", "💖".color(colors::HIGH_CONFIDENCE));
         println!("{}", ENCOURAGEMENTS[rand::random::<usize>() % ENCOURAGEMENTS.len()]);
     }
@@ -345,112 +903,336 @@ fn handle_suggest(
     Ok(())
 }
 
-fn handle_clean(
-    config: &mut Config,
-    exam_manager: &ExamManager,
-    args: &cli::CleanArgs,
-    safe_mode: bool,
-    gamification: &mut Gamification,
-) -> Result<()> {
-    let path = args.path.canonicalize().unwrap_or(args.path.clone());
-    
-    // Create scanner to get file list
-    let scanner = Scanner::new(config.clone(), exam_manager.is_active());
-    let scan_result = scanner.scan(&path, args.days, DEFAULT_LARGE_MB)
-        .context("Failed to scan directory for cleanup")?;
-    
-    if scan_result.files.is_empty() {
-        println!("{} No files to clean", "ℹ️".cyan());
-        return Ok(());
+/// Every copy in each duplicate group except the ones to keep: copies matching
+/// a keep pattern (all of them, if several match), otherwise the oldest or
+/// newest copy. Name-only duplicates differ in contents, so their group
+/// always keeps the newest copy the scan picked (plus any matching a pattern).
+fn duplicates_to_clean(
+    duplicates: &[&scanner::FileInfo],
+    keep_patterns: &[glob::Pattern],
+    keep: cli::KeepCopy,
+) -> Vec<PathBuf> {
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        ..glob::MatchOptions::new()
+    };
+    // Patterns with a separator match the whole path, others the file name
+    let matches_pattern = |path: &Path| keep_patterns.iter().any(|pattern| {
+        if pattern.as_str().contains(['/', '\\']) {
+            pattern.matches_path_with(path, options)
+        } else {
+            path.file_name()
+                .is_some_and(|name| pattern.matches_with(&name.to_string_lossy(), options))
+        }
+    });
+    
+    let mut to_clean = Vec::new();
+    let mut groups: HashMap<&str, Vec<&scanner::FileInfo>> = HashMap::new();
+    for file in duplicates {
+        if file.duplicate_of.is_some() {
+            if !matches_pattern(&file.path) {
+                to_clean.push(file.path.clone());
+            }
+            continue;
+        }
+        // Every other duplicate has a hash; a missing one is never grouped with others
+        let key = file.hash.as_deref().unwrap_or_else(|| file.path.to_str().unwrap_or_default());
+        groups.entry(key).or_default().push(file);
     }
     
-    // Determine which files to clean based on mode
-    let files_to_clean: Vec<PathBuf> = match args.mode {
-        cli::CleanMode::All => {
-            scan_result.files.iter().map(|f| f.path.clone()).collect()
-        }
-        cli::CleanMode::Duplicates => {
-            scan_result.files_by_category(FileCategory::Duplicate)
-                .iter()
-                .map(|f| f.path.clone())
-                .collect()
-        }
-        cli::CleanMode::Old => {
-            scan_result.files.iter()
-                .filter(|f| f.category == FileCategory::Old)
-                .map(|f| f.path.clone())
-                .collect()
-        }
-        cli::CleanMode::Large => {
-            scan_result.files.iter()
-                .filter(|f| f.category == FileCategory::Large)
-                .map(|f| f.path.clone())
-                .collect()
-        }
-        cli::CleanMode::Confidence => {
-            scan_result.files.iter()
-                .filter(|f| f.confidence > 0.8)
-                .map(|f| f.path.clone())
-                .collect()
-        }
-        cli::CleanMode::Interactive => {
-            // Show interactive selection
-            let choices: Vec<String> = scan_result.files.iter()
-                .enumerate()
-                .map(|(i, f)| format!("{:3}. {} ({:.1} MB, {:.2} confidence)", 
-                    i + 1, 
-                    f.path.file_name().unwrap_or_default().to_string_lossy(),
-                    f.size_bytes as f64 / (1024.0 * 1024.0),
-                    f.confidence))
-                .collect();
-            
-            use dialoguer::{theme::ColorfulTheme, MultiSelect};
-            let selected = MultiSelect::with_theme(&ColorfulTheme::default())
-                .items(&choices)
-                .interact()
-                .context("Failed to get user selection")?;
-            
-            selected.iter()
-                .map(|&idx| scan_result.files[idx].path.clone())
-                .collect()
+    let mut kept_by_pattern = 0;
+    for mut group in groups.into_values() {
+        if group.iter().any(|f| matches_pattern(&f.path)) {
+            kept_by_pattern += 1;
+            to_clean.extend(group.iter()
+                .filter(|f| !matches_pattern(&f.path))
+                .map(|f| f.path.clone()));
+            continue;
         }
-    };
+        
+        group.sort_by(|a, b| a.modified.cmp(&b.modified).then_with(|| a.path.cmp(&b.path)));
+        let kept = match keep {
+            cli::KeepCopy::Oldest => 0,
+            cli::KeepCopy::Newest => group.len() - 1,
+        };
+        to_clean.extend(group.iter()
+            .enumerate()
+            .filter(|(i, _)| *i != kept)
+            .map(|(_, f)| f.path.clone()));
+    }
     
-    if files_to_clean.is_empty() {
-        println!("{} No files match the criteria for mode {:?}", "ℹ️".cyan(), args.mode);
+    to_clean.sort();
+    
+    if kept_by_pattern > 0 {
+        println!("{} {} duplicate group{} kept the copies matching --keep-pattern", 
+            "📌".cyan(),
+            kept_by_pattern,
+            if kept_by_pattern == 1 { "" } else { "s" });
+    }
+    to_clean
+}
+
+/// The duplicate copies a cleanup may remove: all but the oldest copy of each
+/// file, as `clean --mode duplicates` picks them by default
+fn removable_duplicates(files: &[scanner::FileInfo]) -> HashSet<PathBuf> {
+    let duplicates: Vec<&scanner::FileInfo> = files.iter()
+        .filter(|f| f.category == FileCategory::Duplicate)
+        .collect();
+    duplicates_to_clean(&duplicates, &[], cli::KeepCopy::default())
+        .into_iter()
+        .collect()
+}
+
+/// The files a `clean --mode` sweep of `category` removes; for duplicates
+/// that leaves out the copy kept of each file
+fn sweep(files: &[scanner::FileInfo], category: FileCategory) -> Vec<&scanner::FileInfo> {
+    let removable = match category {
+        FileCategory::Duplicate => Some(removable_duplicates(files)),
+        _ => None,
+    };
+    files.iter()
+        .filter(|f| f.category == category)
+        .filter(|f| removable.as_ref().is_none_or(|removable| removable.contains(&f.path)))
+        .collect()
+}
+
+/// Which files interactive cleanup starts checked: confident suggestions,
+/// except the copy of each duplicate that a duplicate cleanup would keep
+fn preselected(files: &[scanner::FileInfo], threshold: f32) -> Vec<bool> {
+    let removable = removable_duplicates(files);
+    files.iter()
+        .map(|f| f.confidence >= threshold
+            && (f.category != FileCategory::Duplicate || removable.contains(&f.path)))
+        .collect()
+}
+
+/// Whether a file modified at `modified` falls in the `--since`/`--until`
+/// window, both inclusive and in local dates as the user typed them
+fn in_date_window(
+    modified: chrono::DateTime<Utc>,
+    since: Option<chrono::NaiveDate>,
+    until: Option<chrono::NaiveDate>,
+) -> bool {
+    let day = modified.with_timezone(&chrono::Local).date_naive();
+    since.is_none_or(|since| day >= since) && until.is_none_or(|until| day <= until)
+}
+
+/// Pick suggestions with the most impact (size × confidence) until `target`
+/// bytes are covered. One copy of each duplicated file is always left out.
+/// Prints why and returns None if the goal can't be met.
+fn select_for_target(files: &[scanner::FileInfo], target: u64) -> Option<Vec<PathBuf>> {
+    let removable = removable_duplicates(files);
+    let mut ranked: Vec<&scanner::FileInfo> = files.iter()
+        .filter(|f| f.category != FileCategory::Duplicate || removable.contains(&f.path))
+        .collect();
+    let available = ranked.len();
+    ranked.sort_by(|a, b| {
+        let impact_a = a.size_bytes as f64 * a.confidence as f64;
+        let impact_b = b.size_bytes as f64 * b.confidence as f64;
+        impact_b.partial_cmp(&impact_a).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    
+    let mut selected = Vec::new();
+    let mut freed = 0u64;
+    for file in ranked {
+        if freed >= target {
+            break;
+        }
+        freed += file.size_bytes;
+        selected.push(file);
+    }
+    
+    if freed < target {
+        println!("{} Can't free {} - all {} suggestions add up to only {} ({:.0}% of the goal)",
+            "⚠️".yellow(),
+            cli::format_size(target).bold(),
+            available,
+            cli::format_size(freed).color(colors::WARNING),
+            freed as f64 / target as f64 * 100.0);
+        println!("{} Try scanning a bigger folder or lowering the target", "💡".cyan());
+        return None;
+    }
+    
+    println!("{} Selected {} files to free {}", 
+        "🎯".cyan(),
+        selected.len().to_string().color(colors::SUCCESS),
+        cli::format_size(freed).color(colors::SUCCESS));
+    for file in &selected {
+        println!("   • {} ({}, {:.2} confidence)", 
+            file.path.display().to_string().color(colors::PATH),
+            cli::format_size(file.size_bytes),
+            file.confidence);
+    }
+    
+    Some(selected.iter().map(|f| f.path.clone()).collect())
+}
+
+fn handle_clean(
+    config: &mut Config,
+    exam_manager: &mut ExamManager,
+    args: &cli::CleanArgs,
+    safe_mode: bool,
+    verbose: bool,
+    gamification: &mut Gamification,
+) -> Result<()> {
+    if let (Some(since), Some(until)) = (args.since, args.until) {
+        if since > until {
+            anyhow::bail!("--since {} is after --until {}", since, until);
+        }
+    }
+    
+    let duplicates_mode = matches!(args.mode, cli::CleanMode::Duplicates) && args.target_free.is_none();
+    if !duplicates_mode && (!args.keep_pattern.is_empty() || args.keep.is_some()) {
+        anyhow::bail!("--keep-pattern and --keep only apply to --mode duplicates");
+    }
+    
+    if args.from.is_none() {
+        refuse_broad_path(&args.path, args.i_know_what_im_doing)?;
+    }
+    
+    // Use a saved scan, or scan now to get the file list
+    let (mut scan_result, drifted) = if let Some(from) = &args.from {
+        load_saved_scan(from)?
+    } else {
+        let path = args.path.canonicalize().unwrap_or(args.path.clone());
+        let options = ScanOptions {
+            inspect_archives: args.inspect_archives,
+            ..ScanOptions::default()
+        }.with_extensions(&args.ext)?
+            .with_excludes(&args.exclude)?;
+        let scanner = Scanner::new(config.clone(), exam_manager.is_active())
+            .with_options(options);
+        let scan_result = scanner.scan(&path, args.days, DEFAULT_LARGE_MB)
+            .context("Failed to scan directory for cleanup")?;
+        (scan_result, Vec::new())
+    };
+    // Drop drifted files first, so a duplicate group never keeps one of them
+    scan_result.files.retain(|f| !drifted.contains(&f.path));
+    
+    // An absolute date window narrows the files every mode picks from
+    if args.since.is_some() || args.until.is_some() {
+        let before = scan_result.files.len();
+        scan_result.files.retain(|f| in_date_window(f.modified, args.since, args.until));
+        println!("{} {} of {} files modified {}{}", 
+            "📆".cyan(),
+            scan_result.files.len(),
+            before,
+            args.since.map(|d| format!("from {} ", d)).unwrap_or_default(),
+            args.until.map(|d| format!("through {}", d)).unwrap_or_else(|| "onwards".to_string()));
+    }
+    
+    if scan_result.files.is_empty() {
+        println!("{} No files to clean", "ℹ️".cyan());
         return Ok(());
     }
     
-    // Confirm if not auto-yes
-    if !args.yes && !args.dry_run && !safe_mode {
-        println!("{} Found {} files to clean", "📊".cyan(), files_to_clean.len());
-        let total_size: u64 = files_to_clean.iter()
-            .map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0))
-            .sum();
-        println!("Total size: {:.1} MB", total_size as f64 / (1024.0 * 1024.0));
-        
-        use dialoguer::{theme::ColorfulTheme, Confirm};
-        let confirm = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Proceed with cleanup?")
-            .default(false)
-            .interact()
-            .context("Failed to get confirmation")?;
-        
-        if !confirm {
-            println!("{} Cleanup cancelled", "ℹ️".cyan());
-            return Ok(());
+    // Determine which files to clean based on the space goal or mode
+    let files_to_clean: Vec<PathBuf> = if let Some(target) = args.target_free {
+        match select_for_target(&scan_result.files, target) {
+            Some(selected) => selected,
+            None => return Ok(()),
+        }
+    } else {
+        match args.mode {
+            cli::CleanMode::All => {
+                scan_result.files.iter().map(|f| f.path.clone()).collect()
+            }
+            cli::CleanMode::Duplicates => {
+                duplicates_to_clean(
+                    &scan_result.files_by_category(FileCategory::Duplicate),
+                    &args.keep_pattern,
+                    args.keep.unwrap_or_default(),
+                )
+            }
+            cli::CleanMode::Old => {
+                scan_result.files.iter()
+                    .filter(|f| f.category == FileCategory::Old)
+                    .map(|f| f.path.clone())
+                    .collect()
+            }
+            cli::CleanMode::Large => {
+                scan_result.files.iter()
+                    .filter(|f| f.category == FileCategory::Large)
+                    .map(|f| f.path.clone())
+                    .collect()
+            }
+            cli::CleanMode::Empty => {
+                scan_result.files.iter()
+                    .filter(|f| f.category == FileCategory::Empty)
+                    .map(|f| f.path.clone())
+                    .collect()
+            }
+            cli::CleanMode::Confidence => {
+                scan_result.files.iter()
+                    .filter(|f| config.confidence_level(f.confidence) == "high")
+                    .map(|f| f.path.clone())
+                    .collect()
+            }
+            cli::CleanMode::Interactive => {
+                // Show interactive selection
+                let choices: Vec<String> = scan_result.files.iter()
+                    .enumerate()
+                    .map(|(i, f)| format!("{:3}. {} ({:.1} MB, {:.2} confidence)", 
+                        i + 1, 
+                        f.path.file_name().unwrap_or_default().to_string_lossy(),
+                        f.size_bytes as f64 / (1024.0 * 1024.0),
+                        f.confidence))
+                    .collect();
+            
+                // Pre-check the obvious ones so the common case is a quick review
+                let defaults = preselected(&scan_result.files, config.preselect_confidence);
+                let preselected = defaults.iter().filter(|&&d| d).count();
+                if preselected > 0 {
+                    println!("{} {} file{} with confidence ≥ {:.2} pre-selected", 
+                        "✅".green(),
+                        preselected,
+                        if preselected == 1 { "" } else { "s" },
+                        config.preselect_confidence);
+                }
+            
+                use dialoguer::{theme::ColorfulTheme, MultiSelect};
+                let selected = MultiSelect::with_theme(&ColorfulTheme::default())
+                    .items(&choices)
+                    .defaults(&defaults)
+                    .interact()
+                    .context("Failed to get user selection")?;
+            
+                selected.iter()
+                    .map(|&idx| scan_result.files[idx].path.clone())
+                    .collect()
+            }
         }
+    };
+    
+    let files_to_clean = skip_symlinks(files_to_clean, args.include_symlinks);
+    let files_to_clean = check_exam_tracked(exam_manager, files_to_clean, args.yes, args.dry_run || safe_mode)?;
+    
+    if files_to_clean.is_empty() {
+        println!("{} No files match the criteria for mode {:?}", "ℹ️".cyan(), args.mode);
+        return Ok(());
+    }
+    
+    // Confirm unless --yes or the configured policy says not to
+    let policy = config.confirm_defaults.clean.resolve(args.yes, args.confirm);
+    if !args.dry_run && !safe_mode && policy.should_confirm(files_to_clean.len())
+        && !confirm_files(&files_to_clean, "Proceed with cleanup?")?
+    {
+        println!("{} Cleanup cancelled", "ℹ️".cyan());
+        return Ok(());
     }
     
     // Create archive system and clean files
     let archive_system = ArchiveSystem::new(config.clone())
-        .context("Failed to create archive system")?;
+        .context("Failed to create archive system")?
+        .with_output(output_level(args.summary_only, verbose));
     
     let operation_name = match args.mode {
+        _ if args.target_free.is_some() => "files for space goal",
         cli::CleanMode::All => "all suggestions",
         cli::CleanMode::Duplicates => "duplicates",
         cli::CleanMode::Old => "old files",
         cli::CleanMode::Large => "large files",
+        cli::CleanMode::Empty => "empty files",
         cli::CleanMode::Confidence => "high confidence files",
         cli::CleanMode::Interactive => "selected files",
     };
@@ -464,28 +1246,23 @@ fn handle_clean(
     
     // Update stats if not in safe/dry mode
     if !safe_mode && !args.dry_run && cleanup_result.files_processed > 0 {
+        untrack_cleaned(config, exam_manager, &cleanup_result.successful_files)?;
+        
         // Update config stats
         config.total_files_cleaned += cleanup_result.files_processed as u64;
         config.total_space_freed_mb += cleanup_result.total_size_bytes / (1024 * 1024);
+        config.best_cleanup_mb = config.best_cleanup_mb.max(cleanup_result.total_size_bytes / (1024 * 1024));
         
-        // Check for streak
-        if cleanup_result.files_processed >= 5 || 
-           cleanup_result.total_size_bytes >= 50 * 1024 * 1024 {
-            config.streaks += 1;
-            
-            // Check for achievements
-            if cleanup_result.files_processed >= 10 {
-                config.add_achievement("🔁 Duplicate Slayer");
-            }
-            if config.total_space_freed_mb >= 500 {
-                config.add_achievement("💾 Space Hero");
-            }
-            if config.streaks >= 21 {
-                config.add_achievement("📆 Consistency Cutie");
-            }
+        // Check for achievements (the streak is kept by gamification below)
+        if cleanup_result.files_processed >= 10 {
+            config.add_achievement("🔁 Duplicate Slayer");
+        }
+        if config.total_space_freed_mb >= 500 {
+            config.add_achievement("💾 Space Hero");
         }
         
         config.update_last_cleanup()?;
+        digest::refresh_after_cleanup(&cleanup_result.successful_files);
         
         // Update gamification WITH CleanupType
         let cleanup_type = match args.mode {
@@ -493,16 +1270,22 @@ fn handle_clean(
             cli::CleanMode::Duplicates => CleanupType::Duplicate,
             cli::CleanMode::Old => CleanupType::Normal,
             cli::CleanMode::Large => CleanupType::Normal,
+            cli::CleanMode::Empty => CleanupType::Normal,
             cli::CleanMode::Confidence => CleanupType::Normal,
             cli::CleanMode::Interactive => CleanupType::Normal,
         };
         
+        let confidences = scan_result.files.iter()
+            .map(|f| (f.path.clone(), f.confidence))
+            .collect();
         let unlocks = gamification.update_after_cleanup(
             cleanup_result.files_processed,
             cleanup_result.total_size_bytes,
             cleanup_type,  // USING CleanupType
             exam_manager.is_active(),
+            cleaned_confidence(&confidences, &cleanup_result.successful_files),
         );
+        record_unlocks(config, gamification, &unlocks)?;
         
         // Show encouragement
         gamification.show_encouragement(
@@ -515,35 +1298,221 @@ fn handle_clean(
     Ok(())
 }
 
-fn handle_delete(
+/// Confidence summary of the files a cleanup removed (unknown files count as 0)
+fn cleaned_confidence(confidences: &HashMap<PathBuf, f32>, cleaned: &[PathBuf]) -> ConfidenceSummary {
+    ConfidenceSummary::from_confidences(
+        cleaned.iter().map(|path| confidences.get(path).copied().unwrap_or(0.0))
+    )
+}
+
+/// Save newly unlocked achievements and the streak with the rest of the progress
+fn record_unlocks(
     config: &mut Config,
+    gamification: &Gamification,
+    unlocks: &[gamification::AchievementUnlock],
+) -> Result<()> {
+    for unlock in unlocks {
+        config.add_achievement(&unlock.name);
+    }
+    config.streaks = gamification.current_streak;
+    config.save()?;
+    gamification.save().context("Failed to save progress")
+}
+
+/// Refuse the home folder, the filesystem root and top-level folders such
+/// as `/home` or `C:\Users` unless `--i-know-what-im-doing` was given: a
+/// slip there would sweep far more than one messy folder
+fn refuse_broad_path(path: &Path, allowed: bool) -> Result<()> {
+    if allowed {
+        return Ok(());
+    }
+    
+    if let Some((path, what)) = broad_path(path) {
+        anyhow::bail!("{} is {} - pick a narrower folder such as ~/Downloads, or pass --i-know-what-im-doing",
+            path.display(), what);
+    }
+    Ok(())
+}
+
+/// The resolved path and what it is, if it's too broad to sweep
+fn broad_path(path: &Path) -> Option<(PathBuf, &'static str)> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let depth = path.components()
+        .filter(|c| matches!(c, std::path::Component::Normal(_)))
+        .count();
+    let is_home = dirs::home_dir()
+        .is_some_and(|home| home.canonicalize().unwrap_or(home) == path);
+    
+    let what = if is_home {
+        "your home folder"
+    } else if depth == 0 {
+        "the filesystem root"
+    } else if depth == 1 {
+        "a top-level folder"
+    } else {
+        return None;
+    };
+    Some((path, what))
+}
+
+/// Leave symlinks out unless `--include-symlinks` was given: removing a link
+/// frees no space and is rarely what was meant
+fn skip_symlinks(files: Vec<PathBuf>, include_symlinks: bool) -> Vec<PathBuf> {
+    if include_symlinks {
+        return files;
+    }
+    
+    let (links, files): (Vec<PathBuf>, Vec<PathBuf>) = files.into_iter()
+        .partition(|p| fs::symlink_metadata(p).is_ok_and(|m| m.file_type().is_symlink()));
+    if !links.is_empty() {
+        println!("{} Skipping {} symlink{} (pass {} to remove the link{} - targets are never touched)", 
+            "🔗".cyan(),
+            links.len(),
+            if links.len() == 1 { "" } else { "s" },
+            "--include-symlinks".bold(),
+            if links.len() == 1 { "" } else { "s" });
+    }
+    files
+}
+
+/// Warn before a normal cleanup removes files the active exam is tracking
+/// for its post-exam cleanup. Unless `--yes` was given, they're left out
+/// when the user declines or there's no terminal to ask on.
+fn check_exam_tracked(
     exam_manager: &ExamManager,
+    files: Vec<PathBuf>,
+    assume_yes: bool,
+    dry_run: bool,
+) -> Result<Vec<PathBuf>> {
+    let tracked = exam_manager.tracked_among(&files);
+    if tracked.is_empty() {
+        return Ok(files);
+    }
+    
+    println!("{} {} of these files are tracked for your current exam period:", 
+        "🎓".yellow(), tracked.len());
+    for path in tracked.iter().take(5) {
+        println!("   • {}", path.display().to_string().color(colors::PATH));
+    }
+    if tracked.len() > 5 {
+        println!("   ... and {} more", tracked.len() - 5);
+    }
+    
+    if dry_run || assume_yes {
+        return Ok(files);
+    }
+    
+    let clean_anyway = !config::non_interactive() && {
+        use dialoguer::{theme::ColorfulTheme, Confirm};
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Clean them anyway?")
+            .default(false)
+            .interact()
+            .context("Failed to get confirmation")?
+    };
+    if clean_anyway {
+        return Ok(files);
+    }
+    
+    println!("{} Leaving them for post-exam cleanup", "ℹ️".cyan());
+    Ok(files.into_iter().filter(|path| !tracked.contains(path)).collect())
+}
+
+/// Drop cleaned files from the exam's tracked set, so post-exam cleanup
+/// doesn't go looking for them
+fn untrack_cleaned(config: &mut Config, exam_manager: &mut ExamManager, cleaned: &[PathBuf]) -> Result<()> {
+    let dropped = exam_manager.untrack(cleaned);
+    if dropped > 0 {
+        config.exam_tracking = exam_manager.tracking_state();
+        config.save()?;
+        println!("{} {} cleaned files no longer tracked for your exam", "🎓".cyan(), dropped);
+    }
+    Ok(())
+}
+
+/// `--summary-only` trims cleanup output to the totals, unless `--verbose`
+/// asks for the details back
+fn output_level(summary_only: bool, verbose: bool) -> OutputLevel {
+    if summary_only && !verbose {
+        OutputLevel::Summary
+    } else {
+        OutputLevel::Detailed
+    }
+}
+
+/// Show how much is about to be touched and ask before going ahead
+fn confirm_files(files: &[PathBuf], prompt: &str) -> Result<bool> {
+    println!("{} Found {} files to clean", "📊".cyan(), files.len());
+    let total_size: u64 = files.iter()
+        .map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0))
+        .sum();
+    println!("Total size: {:.1} MB", total_size as f64 / (1024.0 * 1024.0));
+    
+    use dialoguer::{theme::ColorfulTheme, Confirm};
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(false)
+        .interact()
+        .context("Failed to get confirmation")
+}
+
+fn handle_delete(
+    config: &mut Config,
+    exam_manager: &mut ExamManager,
     args: &cli::DeleteArgs,
     safe_mode: bool,
+    verbose: bool,
     gamification: &mut Gamification,
 ) -> Result<()> {
     // Get context path
-    let context_path = if let Some(path) = &args.path {
-        path.clone()
-    } else {
-        dirs::download_dir().unwrap_or_else(|| PathBuf::from("."))
+    let context_path = match &args.path {
+        Some(path) => path.clone(),
+        None => config.primary_scan_path(),
     };
     
+    if args.from.is_none() {
+        refuse_broad_path(&context_path, args.i_know_what_im_doing)?;
+    }
+    
     // If indices provided, we need a previous scan context
-    if !args.indices.is_empty() && !args.all && !args.duplicates && args.old.is_none() && args.large.is_none() {
-        println!("{} Please specify a path with --path when using indices", "⚠️".yellow());
+    if !args.indices.is_empty() && args.path.is_none() && args.from.is_none() {
+        println!("{} Please specify a path with --path (or a saved scan with --from) when using indices", "⚠️".yellow());
         println!("Example: cleancrush delete 1 3 5 --path ~/Downloads");
         return Ok(());
     }
     
+    // A saved scan stands in for the scan below, so indices match what was
+    // reviewed; without --from, numbers refer to the last scan or suggest
+    let mut saved = match &args.from {
+        Some(from) => Some(load_saved_scan(from)?),
+        None if !args.indices.is_empty() => last_scan_of(&context_path),
+        None => None,
+    };
+    let drifted = saved.as_ref().map(|(_, drifted)| drifted.clone()).unwrap_or_default();
+    
     // Create scanner
-    let scanner = Scanner::new(config.clone(), exam_manager.is_active());
+    let options = ScanOptions {
+        inspect_archives: args.inspect_archives,
+        ..ScanOptions::default()
+    };
+    let scanner = Scanner::new(config.clone(), exam_manager.is_active())
+        .with_options(options);
+    // Confidences of everything scanned, for the cleanup achievements
+    let mut confidences = HashMap::new();
+    let mut scan = |old_days: u64, large_mb: u64| -> Result<ScanResult> {
+        let scan_result = match saved.take() {
+            Some((scan_result, _)) => scan_result,
+            None => scanner.scan(&context_path, old_days, large_mb)
+                .context("Failed to scan directory")?,
+        };
+        confidences.extend(scan_result.files.iter().map(|f| (f.path.clone(), f.confidence)));
+        Ok(scan_result)
+    };
     
     // Determine which files to delete
-    let files_to_delete = if !args.indices.is_empty() {
+    let files_to_delete: Vec<PathBuf> = if !args.indices.is_empty() {
         // Need to scan to get files for indices
-        let scan_result = scanner.scan(&context_path, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
-            .context("Failed to scan directory")?;
+        let scan_result = scan(DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)?;
         
         args.indices.iter()
             .filter_map(|&idx| {
@@ -556,26 +1525,22 @@ fn handle_delete(
             })
             .collect()
     } else if args.all {
-        let scan_result = scanner.scan(&context_path, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
-            .context("Failed to scan directory")?;
+        let scan_result = scan(DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)?;
         scan_result.files.iter().map(|f| f.path.clone()).collect()
     } else if args.duplicates {
-        let scan_result = scanner.scan(&context_path, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
-            .context("Failed to scan directory")?;
-        scan_result.files_by_category(FileCategory::Duplicate)
+        let scan_result = scan(DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)?;
+        sweep(&scan_result.files, FileCategory::Duplicate)
             .iter()
             .map(|f| f.path.clone())
             .collect()
     } else if let Some(days) = args.old {
-        let scan_result = scanner.scan(&context_path, days, DEFAULT_LARGE_MB)
-            .context("Failed to scan directory")?;
+        let scan_result = scan(days, DEFAULT_LARGE_MB)?;
         scan_result.files.iter()
             .filter(|f| f.category == FileCategory::Old || f.days_old > days as i64)
             .map(|f| f.path.clone())
             .collect()
     } else if let Some(size_mb) = args.large {
-        let scan_result = scanner.scan(&context_path, DEFAULT_OLD_DAYS, size_mb)
-            .context("Failed to scan directory")?;
+        let scan_result = scan(DEFAULT_OLD_DAYS, size_mb)?;
         scan_result.files.iter()
             .filter(|f| f.category == FileCategory::Large)
             .map(|f| f.path.clone())
@@ -584,14 +1549,30 @@ fn handle_delete(
         Vec::new()
     };
     
+    let files_to_delete: Vec<PathBuf> = files_to_delete.into_iter()
+        .filter(|p| !drifted.contains(p))
+        .collect();
+    let files_to_delete = skip_symlinks(files_to_delete, args.include_symlinks);
+    let dry_run = args.dry_run || safe_mode;
+    let files_to_delete = check_exam_tracked(exam_manager, files_to_delete, args.yes, dry_run)?;
+    
     if files_to_delete.is_empty() {
         println!("{} No files to delete", "ℹ️".cyan());
         return Ok(());
     }
     
+    let policy = config.confirm_defaults.delete.resolve(args.yes, args.confirm);
+    if !dry_run && policy.should_confirm(files_to_delete.len())
+        && !confirm_files(&files_to_delete, "Delete these files?")?
+    {
+        println!("{} Delete cancelled", "ℹ️".cyan());
+        return Ok(());
+    }
+    
     // Create archive system and clean files
     let archive_system = ArchiveSystem::new(config.clone())
-        .context("Failed to create archive system")?;
+        .context("Failed to create archive system")?
+        .with_output(output_level(args.summary_only, verbose));
     
     let operation_name = if !args.indices.is_empty() {
         "selected indices"
@@ -609,13 +1590,15 @@ fn handle_delete(
     
     let cleanup_result = archive_system.clean_files(
         &files_to_delete, 
-        safe_mode, // Use safe mode for dry-run effect
+        dry_run,
         safe_mode,
         operation_name,
     )?;
     
-    // Update stats if not in safe mode
-    if !safe_mode && cleanup_result.files_processed > 0 {
+    // Update stats if not in safe/dry mode
+    if !dry_run && cleanup_result.files_processed > 0 {
+        untrack_cleaned(config, exam_manager, &cleanup_result.successful_files)?;
+        
         config.update_stats(
             cleanup_result.files_processed,
             cleanup_result.total_size_bytes,
@@ -630,6 +1613,7 @@ fn handle_delete(
         }
         
         config.update_last_cleanup()?;
+        digest::refresh_after_cleanup(&cleanup_result.successful_files);
         
         // Update gamification
         let is_exam_cleanup = exam_manager.is_active() && (args.all || args.duplicates);
@@ -638,11 +1622,12 @@ fn handle_delete(
             cleanup_result.total_size_bytes,
             CleanupType::Normal,
             is_exam_cleanup,
+            cleaned_confidence(&confidences, &cleanup_result.successful_files),
         );
-        
         if is_exam_cleanup {
             config.add_achievement("🎓 Exam Reset");
         }
+        record_unlocks(config, gamification, &unlocks)?;
         
         // Show encouragement
         gamification.show_encouragement(
@@ -677,26 +1662,32 @@ fn handle_exam(
                 .context("Failed to stop exam tracking")?;
         }
         cli::ExamArgs::Set { start_date, end_date, name } => {
-            use chrono::NaiveDate;
-    
-            let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
-                .context("Invalid start date format (use YYYY-MM-DD)")?
-                .and_hms_opt(0, 0, 0)
-                .unwrap();
-            let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
-                .context("Invalid end date format (use YYYY-MM-DD)")?
-                .and_hms_opt(0, 0, 0)
-                .unwrap();
-            
-            let start_utc = chrono::DateTime::from_naive_utc_and_offset(start, Utc);
-            let end_utc = chrono::DateTime::from_naive_utc_and_offset(end, Utc);
+            // Dates are checked by `cli::parse_date` when the arguments are parsed
+            let start_utc = start_date.and_time(chrono::NaiveTime::MIN).and_utc();
+            let end_utc = end_date.and_time(chrono::NaiveTime::MIN).and_utc();
     
             exam_manager.set_dates(start_utc, end_utc, name)
                 .context("Failed to set exam dates")?;
         }
+        cli::ExamArgs::Extend { days, to } => {
+            let new_end = match (to, days) {
+                (Some(date), _) => date.and_time(chrono::NaiveTime::MIN).and_utc(),
+                (None, Some(days)) => {
+                    let from = exam_manager.get_tracker()
+                        .and_then(|tracker| tracker.end_date)
+                        .unwrap_or_else(Utc::now);
+                    from + chrono::Duration::days(days.into())
+                }
+                (None, None) => unreachable!("clap requires --days or --to"),
+            };
+            exam_manager.extend(new_end)?;
+            // Keep our copy of the config in step with the new end date
+            config.exam_tracking = exam_manager.tracking_state();
+        }
         cli::ExamArgs::Status => {
             exam_manager.show_status();
         }
+        cli::ExamArgs::Ignore(action) => handle_exam_ignore(config, action)?,
         cli::ExamArgs::List => {
             if let Some(tracker) = exam_manager.get_tracker() {
                 println!();
@@ -715,8 +1706,24 @@ fn handle_exam(
                 println!("{} No active exam tracking", "ℹ️".cyan());
             }
         }
-        cli::ExamArgs::End => {
-            if let Some(choice) = exam_manager.end_exam()? {
+        cli::ExamArgs::End { yes, confirm } => {
+            let before_end = exam_manager.tracking_state();
+            if let Some((choice, tracker)) = exam_manager.end_exam()? {
+                // Keep our copy of the config in step with the ended exam
+                config.exam_tracking = Some(tracker.clone().into());
+                
+                // Remember the period so `exam undo` can bring it back
+                let mut ended = before_end.map(|tracking| EndedExam {
+                    ended_at: Utc::now(),
+                    choice: choice.clone(),
+                    tracking,
+                    cleaned_files: Vec::new(),
+                    action: config.default_action.clone(),
+                });
+                if let Some(ended) = &ended {
+                    ended.save().context("Failed to save the ended exam")?;
+                }
+                
                 // Log which PostExamChoice was selected
                 match &choice {
                     PostExamChoice::QuickClean => println!("{} Quick clean selected", "🚀".green()),
@@ -724,11 +1731,66 @@ fn handle_exam(
                     PostExamChoice::SmartClean => println!("{} Smart clean selected", "🤖".blue()),
                 }
                 
-                // Get files for cleanup
-                if let Some(tracker) = exam_manager.get_tracker() {
-                    let files_to_clean = tracker.get_files_for_cleanup(choice.clone());
+                // Get files for cleanup, keeping only the exact files that were tracked
+                {
+                    let (mut files_to_clean, mismatched) = tracker.verify_files(
+                        tracker.get_files_for_cleanup(choice.clone())
+                    );
+                    if matches!(choice, PostExamChoice::SelectiveClean) && !files_to_clean.is_empty() {
+                        files_to_clean = tracker.select_by_category(files_to_clean)?;
+                    }
+                    
+                    // Files edited during the exam are kept unless picked here
+                    let edited = tracker.actively_used_files(choice.clone());
+                    if !edited.is_empty() {
+                        println!();
+                        println!("{} Keeping {} file{} you edited during the exam:", 
+                            "✏️".cyan(),
+                            edited.len(),
+                            if edited.len() == 1 { "" } else { "s" });
+                        let labels: Vec<String> = edited.iter()
+                            .map(|path| path.display().to_string())
+                            .collect();
+                        
+                        // The exam has already ended, so never fail here for want of a terminal
+                        if config::non_interactive() {
+                            for label in &labels {
+                                println!("   • {}", label.color(colors::PATH));
+                            }
+                        } else {
+                            use dialoguer::{theme::ColorfulTheme, MultiSelect};
+                            let picked = MultiSelect::with_theme(&ColorfulTheme::default())
+                                .with_prompt("Clean any of these too? (space to select, enter to continue)")
+                                .items(&labels)
+                                .interact()
+                                .context("Failed to get selection")?;
+                            files_to_clean.extend(picked.into_iter()
+                                .map(|i| edited[i].clone())
+                                .filter(|path| path.exists()));
+                        }
+                    }
+                    
+                    if !mismatched.is_empty() {
+                        println!();
+                        println!("{} Skipping {} tracked file{} that no longer match{}:", 
+                            "⚠️".yellow(),
+                            mismatched.len(),
+                            if mismatched.len() == 1 { "" } else { "s" },
+                            if mismatched.len() == 1 { "es" } else { "" });
+                        for (path, reason) in &mismatched {
+                            println!("   • {} ({})", path.display().to_string().color(colors::PATH), reason);
+                        }
+                    }
+                    
+                    let policy = config.confirm_defaults.exam_end.resolve(yes, confirm);
+                    let proceed = files_to_clean.is_empty()
+                        || !policy.should_confirm(files_to_clean.len())
+                        || confirm_files(&files_to_clean, "Clean these exam files?")?;
+                    if !proceed {
+                        println!("{} Exam cleanup cancelled - the exam is still ended", "ℹ️".cyan());
+                    }
                     
-                    if !files_to_clean.is_empty() {
+                    if proceed && !files_to_clean.is_empty() {
                         println!();
                         println!("{} Cleaning {} exam files...", 
                             "🧹".color(colors::SUCCESS),
@@ -743,6 +1805,15 @@ fn handle_exam(
                             "post-exam cleanup",
                         )?;
                         
+                        if let Some(ended) = &mut ended {
+                            ended.cleaned_files = cleanup_result.successful_files.clone();
+                            ended.save().context("Failed to save the ended exam")?;
+                        }
+                        if !cleanup_result.successful_files.is_empty() {
+                            println!("{} Picked the wrong option? {} puts these files back", 
+                                "💡".cyan(), "cleancrush exam undo".bold());
+                        }
+                        
                         // Update stats
                         if cleanup_result.files_processed > 0 {
                             config.update_stats(
@@ -751,16 +1822,19 @@ fn handle_exam(
                             );
                             
                             config.add_achievement("🎓 Exam Reset");
-                            config.streaks += 1;
                             config.update_last_cleanup()?;
+                            digest::refresh_after_cleanup(&cleanup_result.successful_files);
                             
                             // Update gamification
+                            // Exam files aren't scored, so no confidence to go on
                             let unlocks = gamification.update_after_cleanup(
                                 cleanup_result.files_processed,
                                 cleanup_result.total_size_bytes,
                                 CleanupType::Exam,  // USING CleanupType::Exam
                                 true,
+                                ConfidenceSummary::default(),
                             );
+                            record_unlocks(config, gamification, &unlocks)?;
                             
                             // Show encouragement
                             gamification.show_encouragement(
@@ -773,23 +1847,128 @@ fn handle_exam(
                 }
             }
         }
+        cli::ExamArgs::Undo => {
+            let Some(ended) = EndedExam::load()? else {
+                println!("{} No ended exam to undo", "ℹ️".cyan());
+                return Ok(());
+            };
+            
+            exam_manager.reinstate(ended.tracking.clone())
+                .context("Failed to resume exam tracking")?;
+            config.exam_tracking = exam_manager.tracking_state();
+            
+            println!("{} Exam tracking resumed{} ({} files, ended {})", 
+                "✅".green(),
+                ended.tracking.exam_period_name.as_ref()
+                    .map(|name| format!(" for '{}'", name))
+                    .unwrap_or_default(),
+                ended.tracking.tracked_files.len(),
+                ended.ended_at.format("%Y-%m-%d %H:%M"));
+            
+            if !ended.cleaned_files.is_empty() {
+                println!("{} Restoring {} file{} removed by {}...", 
+                    "↩️".cyan(),
+                    ended.cleaned_files.len(),
+                    if ended.cleaned_files.len() == 1 { "" } else { "s" },
+                    ended.choice.display_name());
+                restore_cleaned_files(config, &ended.action, &ended.cleaned_files)?;
+                // So `restore-last` doesn't try to put them back again
+                History::mark_restored(&ended.cleaned_files)
+                    .context("Failed to update cleanup history")?;
+            }
+            
+            EndedExam::clear()?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Put back files a cleanup removed, from wherever it sent them, listing
+/// where each one ends up
+fn restore_cleaned_files(config: &Config, action: &CleanupAction, files: &[PathBuf]) -> Result<()> {
+    match action {
+        CleanupAction::RecycleBin => {
+            let mut manifest = TrashManifest::load()
+                .context("Failed to load trash manifest")?;
+            let outcomes = manifest.restore_paths(files);
+            manifest.save()
+                .context("Failed to update trash manifest")?;
+            
+            let mut restored = 0;
+            for (path, outcome) in outcomes {
+                let shown = path.display().to_string();
+                match outcome {
+                    RestoreOutcome::Restored => {
+                        restored += 1;
+                        println!("   {} {} (from the trash)", "↩️".green(), shown.color(colors::PATH));
+                    }
+                    RestoreOutcome::Occupied => println!("{} {} already exists - move it aside and run {}", 
+                        "⚠️".yellow(), shown.color(colors::PATH), "cleancrush trash restore".bold()),
+                    RestoreOutcome::NotInTrash => println!("{} {} is no longer in the trash", 
+                        "📭".cyan(), shown.color(colors::PATH)),
+                    RestoreOutcome::Unsupported => println!("{} Restore it from your file manager's trash: {}", 
+                        "💡".cyan(), shown.color(colors::PATH)),
+                    RestoreOutcome::Failed(e) => println!("{} Failed to restore {}: {}", 
+                        "❌".red(), shown.color(colors::PATH), e),
+                }
+            }
+            println!("{} Restored {} of {} files", "✅".green(), restored, files.len());
+        }
+        CleanupAction::Archive => {
+            // The files went to the archive even if the config has changed since
+            let mut archive_config = config.clone();
+            archive_config.default_action = CleanupAction::Archive;
+            let archive_system = ArchiveSystem::new(archive_config)?;
+            let result = archive_system.restore_paths(files)?;
+            for path in &result.restored {
+                println!("   {} {} (from the archive)", "↩️".green(), path.display().to_string().color(colors::PATH));
+            }
+            archive_system.print_restore_summary(&result, false);
+        }
     }
     
     Ok(())
 }
 
+/// Protected folders (not patterns) whose path is gone
+fn missing_protected_folders(config: &Config) -> Vec<PathBuf> {
+    config.protected_folders.iter()
+        .filter(|p| !p.glob && !p.path.exists())
+        .map(|p| p.path.clone())
+        .collect()
+}
+
+/// Whether a stored protected entry is the one named on the command line
+/// (folders compare the way protection matches them)
+fn same_protected_entry(stored: &Path, entry: &Path, is_glob: bool) -> bool {
+    if is_glob {
+        return stored == entry;
+    }
+    config::path_starts_with(stored, entry) && config::path_starts_with(entry, stored)
+}
+
 fn handle_protect(
     config: &mut Config,
     subcommand: cli::ProtectArgs,
 ) -> Result<()> {
     match subcommand {
-        cli::ProtectArgs::Add { path, protection } => {
-            let abs_path = path.canonicalize()
-                .context(format!("Failed to canonicalize path: {}", path.display()))?;
+        cli::ProtectArgs::Add { path, glob, protection } => {
+            let (entry, is_glob) = match (path, glob) {
+                (_, Some(pattern)) => (PathBuf::from(pattern.as_str()), true),
+                (Some(path), None) => (path.canonicalize()
+                    .context(format!("Failed to canonicalize path: {}", path.display()))?, false),
+                (None, None) => unreachable!("clap requires a path or --glob"),
+            };
             
             // Check if already protected
-            if config.is_protected(&abs_path).is_some() {
-                println!("{} Already protected: {}", "ℹ️".cyan(), abs_path.display());
+            let already = if is_glob {
+                config.protected_folders.iter().any(|p| p.glob && p.path == entry)
+            } else {
+                config.is_protected(&entry).is_some()
+            };
+            if already {
+                println!("{} Already protected: {}", "ℹ️".cyan(), entry.display());
                 return Ok(());
             }
             
@@ -799,27 +1978,36 @@ fn handle_protect(
             };
             
             config.protected_folders.push(ProtectedFolder {
-                path: abs_path.clone(),
+                path: entry.clone(),
                 protection_type,
+                glob: is_glob,
             });
             
             config.save()
                 .context("Failed to save configuration")?;
-            println!("{} Protected: {}", "✅".green(), abs_path.display());
+            if is_glob {
+                println!("{} Protected everything matching: {}", "✅".green(), entry.display());
+            } else {
+                println!("{} Protected: {}", "✅".green(), entry.display());
+            }
         }
-        cli::ProtectArgs::Remove { path } => {
-            let abs_path = path.canonicalize()
-                .context(format!("Failed to canonicalize path: {}", path.display()))?;
+        cli::ProtectArgs::Remove { path, glob } => {
+            let (entry, is_glob) = match (path, glob) {
+                (_, Some(pattern)) => (PathBuf::from(pattern), true),
+                (Some(path), None) => (path.canonicalize()
+                    .context(format!("Failed to canonicalize path: {}", path.display()))?, false),
+                (None, None) => unreachable!("clap requires a path or --glob"),
+            };
             let before_len = config.protected_folders.len();
             
-            config.protected_folders.retain(|p| p.path != abs_path);
+            config.protected_folders.retain(|p| p.glob != is_glob || !same_protected_entry(&p.path, &entry, is_glob));
             
             if config.protected_folders.len() < before_len {
                 config.save()
                     .context("Failed to save configuration")?;
-                println!("{} Removed protection: {}", "✅".green(), abs_path.display());
+                println!("{} Removed protection: {}", "✅".green(), entry.display());
             } else {
-                println!("{} Not in protected list: {}", "ℹ️".cyan(), abs_path.display());
+                println!("{} Not in protected list: {}", "ℹ️".cyan(), entry.display());
             }
         }
         cli::ProtectArgs::List => {
@@ -834,10 +2022,57 @@ fn handle_protect(
                         ProtectionType::Hard => "Hard (never scan)",
                         ProtectionType::Soft => "Soft (scan but warn)",
                     };
-                    println!("• {} ({})", protected.path.display(), protection_type);
+                    // Patterns may name folders that don't exist yet
+                    let missing = !protected.glob && !protected.path.exists();
+                    println!("• {}{} ({}){}", 
+                        protected.path.display(),
+                        if protected.glob { " [pattern]" } else { "" },
+                        protection_type,
+                        if missing { format!(" {} no longer exists", "⚠️".yellow()) } else { String::new() });
+                }
+                
+                if !missing_protected_folders(config).is_empty() {
+                    println!();
+                    println!("{} Run {} to drop folders that no longer exist", 
+                        "💡".cyan(), "cleancrush protect prune".bold());
                 }
             }
         }
+        cli::ProtectArgs::Prune { yes } => {
+            let missing = missing_protected_folders(config);
+            if missing.is_empty() {
+                println!("{} Every protected folder still exists", "✨".green());
+                return Ok(());
+            }
+            
+            println!("{} {} protected folder{} no longer exist{}:", 
+                "🧹".cyan(),
+                missing.len(),
+                if missing.len() == 1 { "" } else { "s" },
+                if missing.len() == 1 { "s" } else { "" });
+            for path in &missing {
+                println!("   • {}", path.display().to_string().color(colors::PATH));
+            }
+            
+            let proceed = yes || (!config::non_interactive() && {
+                use dialoguer::{theme::ColorfulTheme, Confirm};
+                Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Drop them from the protected list?")
+                    .default(true)
+                    .interact()
+                    .context("Failed to get confirmation")?
+            });
+            if !proceed {
+                println!("{} Prune cancelled", "ℹ️".cyan());
+                return Ok(());
+            }
+            
+            config.protected_folders.retain(|p| p.glob || !missing.contains(&p.path));
+            config.save()
+                .context("Failed to save configuration")?;
+            println!("{} Dropped {} protected folder{}", 
+                "✅".green(), missing.len(), if missing.len() == 1 { "" } else { "s" });
+        }
         cli::ProtectArgs::Clear => {
             if !config.protected_folders.is_empty() {
                 use dialoguer::{theme::ColorfulTheme, Confirm};
@@ -862,9 +2097,570 @@ fn handle_protect(
     Ok(())
 }
 
-fn handle_archive(
-    config: &Config,
-    subcommand: cli::ArchiveArgs,
+fn handle_sweep_installers(
+    config: &mut Config,
+    exam_manager: &ExamManager,
+    args: &cli::SweepInstallersArgs,
+    safe_mode: bool,
+    gamification: &mut Gamification,
+) -> Result<()> {
+    let path = match &args.path {
+        Some(path) => path.canonicalize().unwrap_or(path.clone()),
+        None => dirs::download_dir().context("Could not find Downloads folder; pass a path")?,
+    };
+    refuse_broad_path(&path, args.i_know_what_im_doing)?;
+    
+    let (found, skips) = installers::find_installers(&path, args.days, config, exam_manager)
+        .context("Failed to look for installers")?;
+    installers::print_installers(&found, &skips, args.days);
+    
+    if found.is_empty() {
+        return Ok(());
+    }
+    
+    let files: Vec<PathBuf> = found.iter().map(|f| f.path.clone()).collect();
+    
+    if !args.dry_run && !safe_mode {
+        let proceed = args.yes || (!config::non_interactive() && {
+            println!();
+            use dialoguer::{theme::ColorfulTheme, Confirm};
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Clear these {} files?", files.len()))
+                .default(false)
+                .interact()
+                .context("Failed to get confirmation")?
+        });
+        
+        if !proceed {
+            println!("{} Sweep cancelled", "ℹ️".cyan());
+            return Ok(());
+        }
+    }
+    
+    let archive_system = ArchiveSystem::new(config.clone())
+        .context("Failed to create archive system")?;
+    let cleanup_result = archive_system.clean_files(
+        &files,
+        args.dry_run,
+        safe_mode,
+        "old installers",
+    )?;
+    
+    if !safe_mode && !args.dry_run && cleanup_result.files_processed > 0 {
+        config.update_stats(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes,
+        );
+        config.update_last_cleanup()?;
+        digest::refresh_after_cleanup(&cleanup_result.successful_files);
+        
+        let confidences = found.iter()
+            .map(|f| (f.path.clone(), f.confidence))
+            .collect();
+        let unlocks = gamification.update_after_cleanup(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes,
+            CleanupType::Normal,
+            false,
+            cleaned_confidence(&confidences, &cleanup_result.successful_files),
+        );
+        record_unlocks(config, gamification, &unlocks)?;
+        gamification.show_encouragement(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes / (1024 * 1024),
+            &unlocks,
+        );
+    }
+    
+    Ok(())
+}
+
+fn handle_dedupe_downloads(
+    config: &mut Config,
+    exam_manager: &ExamManager,
+    args: &cli::DedupeDownloadsArgs,
+    safe_mode: bool,
+    gamification: &mut Gamification,
+) -> Result<()> {
+    let path = match &args.path {
+        Some(path) => path.canonicalize().unwrap_or(path.clone()),
+        None => dirs::download_dir().context("Could not find Downloads folder; pass a path")?,
+    };
+    refuse_broad_path(&path, args.i_know_what_im_doing)?;
+    
+    let (groups, skips) = downloads::find_download_duplicates(&path, config, exam_manager)
+        .context("Failed to look for re-downloaded files")?;
+    downloads::print_download_duplicates(&groups, &skips);
+    
+    let confidences: HashMap<PathBuf, f32> = groups.iter()
+        .flat_map(|g| g.removable())
+        .filter_map(|c| c.confidence().map(|confidence| (c.path.clone(), confidence)))
+        .collect();
+    let mut files: Vec<PathBuf> = confidences.keys().cloned().collect();
+    files.sort();
+    
+    if files.is_empty() {
+        return Ok(());
+    }
+    
+    if !args.dry_run && !safe_mode {
+        let proceed = args.yes || (!config::non_interactive() && {
+            println!();
+            use dialoguer::{theme::ColorfulTheme, Confirm};
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Remove these {} copies, keeping the originals?", files.len()))
+                .default(false)
+                .interact()
+                .context("Failed to get confirmation")?
+        });
+        
+        if !proceed {
+            println!("{} Dedupe cancelled", "ℹ️".cyan());
+            return Ok(());
+        }
+    }
+    
+    let archive_system = ArchiveSystem::new(config.clone())
+        .context("Failed to create archive system")?;
+    let cleanup_result = archive_system.clean_files(
+        &files,
+        args.dry_run,
+        safe_mode,
+        "re-downloaded copies",
+    )?;
+    
+    if !safe_mode && !args.dry_run && cleanup_result.files_processed > 0 {
+        config.update_stats(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes,
+        );
+        config.update_last_cleanup()?;
+        digest::refresh_after_cleanup(&cleanup_result.successful_files);
+        
+        let unlocks = gamification.update_after_cleanup(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes,
+            CleanupType::Duplicate,
+            false,
+            cleaned_confidence(&confidences, &cleanup_result.successful_files),
+        );
+        record_unlocks(config, gamification, &unlocks)?;
+        gamification.show_encouragement(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes / (1024 * 1024),
+            &unlocks,
+        );
+    }
+    
+    Ok(())
+}
+
+fn handle_sweep_cache(
+    config: &mut Config,
+    exam_manager: &ExamManager,
+    args: &cli::SweepCacheArgs,
+    safe_mode: bool,
+    gamification: &mut Gamification,
+) -> Result<()> {
+    let path = args.path.canonicalize()
+        .with_context(|| format!("Folder not found: {}", args.path.display()))?;
+    refuse_broad_path(&path, args.i_know_what_im_doing)?;
+    
+    let (folders, skips) = dev_cache::find_caches(&path, config, exam_manager)
+        .context("Failed to look for cache folders")?;
+    dev_cache::print_caches(&folders, &skips);
+    
+    if folders.is_empty() {
+        return Ok(());
+    }
+    
+    let files: Vec<PathBuf> = folders.iter()
+        .flat_map(|f| f.files.iter().cloned())
+        .collect();
+    
+    if !args.dry_run && !safe_mode {
+        let proceed = args.yes || (!config::non_interactive() && {
+            println!();
+            use dialoguer::{theme::ColorfulTheme, Confirm};
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Clear these {} cache files?", files.len()))
+                .default(false)
+                .interact()
+                .context("Failed to get confirmation")?
+        });
+        
+        if !proceed {
+            println!("{} Sweep cancelled", "ℹ️".cyan());
+            return Ok(());
+        }
+    }
+    
+    let archive_system = ArchiveSystem::new(config.clone())
+        .context("Failed to create archive system")?;
+    let cleanup_result = archive_system.clean_files(
+        &files,
+        args.dry_run,
+        safe_mode,
+        "notebook and Python caches",
+    )?;
+    
+    if !safe_mode && !args.dry_run && cleanup_result.files_processed > 0 {
+        let removed = dev_cache::remove_empty_cache_dirs(&folders);
+        if removed > 0 {
+            println!("{} Removed {} empty cache folder{}", 
+                "🧹".cyan(), removed, if removed == 1 { "" } else { "s" });
+        }
+        
+        config.update_stats(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes,
+        );
+        config.update_last_cleanup()?;
+        digest::refresh_after_cleanup(&cleanup_result.successful_files);
+        
+        let confidences = files.iter()
+            .map(|path| (path.clone(), dev_cache::CACHE_CONFIDENCE))
+            .collect();
+        let unlocks = gamification.update_after_cleanup(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes,
+            CleanupType::Normal,
+            false,
+            cleaned_confidence(&confidences, &cleanup_result.successful_files),
+        );
+        record_unlocks(config, gamification, &unlocks)?;
+        gamification.show_encouragement(
+            cleanup_result.files_processed,
+            cleanup_result.total_size_bytes / (1024 * 1024),
+            &unlocks,
+        );
+    }
+    
+    Ok(())
+}
+
+fn handle_exam_ignore(config: &mut Config, action: cli::ExamIgnoreArgs) -> Result<()> {
+    // `*.ics` means the same as `.ics`
+    let normalize = |pattern: &str| pattern.trim().trim_start_matches('*').to_lowercase();
+    
+    match action {
+        cli::ExamIgnoreArgs::List => {
+            println!("{}", "🎓 NEVER EXAM-TRACKED".bold().color(colors::HEADER));
+            println!("{}", "─".repeat(50).color(colors::PATH));
+            if config.exam_ignore.is_empty() {
+                println!("Nothing yet - add one with {}", "cleancrush exam ignore add .ics".bold());
+            }
+            for pattern in &config.exam_ignore {
+                println!("• {}", pattern);
+            }
+        }
+        cli::ExamIgnoreArgs::Add { pattern } => {
+            let pattern = normalize(&pattern);
+            if pattern.is_empty() || pattern == "." {
+                anyhow::bail!("Give an extension like .ics or part of a file name");
+            }
+            if config.exam_ignore.contains(&pattern) {
+                println!("{} Already ignored for exams: {}", "ℹ️".cyan(), pattern);
+                return Ok(());
+            }
+            
+            // Files already tracked this exam period stop being tracked too
+            let untracked = match &mut config.exam_tracking {
+                Some(tracking) if tracking.active => tracking.untrack(|path| config::exam_ignore_matches(&pattern, path)),
+                _ => 0,
+            };
+            
+            config.exam_ignore.push(pattern.clone());
+            config.save().context("Failed to save configuration")?;
+            println!("{} Files matching {} won't be tracked for exams", "✅".green(), pattern.color(colors::PATH));
+            if untracked > 0 {
+                println!("   {} {} already-tracked file{} won't be cleaned at {}", 
+                    "🛡️".cyan(), untracked, if untracked == 1 { "" } else { "s" }, "exam end".bold());
+            }
+        }
+        cli::ExamIgnoreArgs::Remove { pattern } => {
+            let pattern = normalize(&pattern);
+            let before = config.exam_ignore.len();
+            config.exam_ignore.retain(|p| *p != pattern);
+            if config.exam_ignore.len() == before {
+                println!("{} Not ignored for exams: {}", "ℹ️".cyan(), pattern);
+                return Ok(());
+            }
+            config.save().context("Failed to save configuration")?;
+            println!("{} Files matching {} can be exam-tracked again", "✅".green(), pattern);
+        }
+    }
+    
+    Ok(())
+}
+
+fn handle_ignore_file(config: &mut Config, args: &cli::IgnoreFileArgs) -> Result<()> {
+    let Some(name) = &args.name else {
+        println!("{}", "🙈 NEVER SUGGESTED".bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        if config.never_suggest.is_empty() {
+            println!("Nothing yet - add one with {}", "cleancrush ignore-file resume.pdf".bold());
+        }
+        for entry in &config.never_suggest {
+            println!("• {}", entry);
+        }
+        return Ok(());
+    };
+    
+    // A plain path is stored in full so only that one file is ignored
+    let probe = CourseOverride { pattern: name.clone(), course: String::new() };
+    let entry = if !probe.is_glob() && name.contains(['/', '\\']) {
+        Path::new(name).canonicalize()
+            .context(format!("Failed to canonicalize path: {}", name))?
+            .to_string_lossy()
+            .to_string()
+    } else {
+        glob::Pattern::new(name)
+            .map_err(|e| anyhow::anyhow!("Invalid pattern '{}': {}", name, e))?;
+        name.clone()
+    };
+    
+    if args.remove {
+        let before = config.never_suggest.len();
+        config.never_suggest.retain(|e| e != &entry);
+        if config.never_suggest.len() == before {
+            println!("{} Not on the never-suggest list: {}", "ℹ️".cyan(), entry);
+            return Ok(());
+        }
+        config.save().context("Failed to save configuration")?;
+        println!("{} {} can be suggested again", "✅".green(), entry);
+        return Ok(());
+    }
+    
+    if config.never_suggest.contains(&entry) {
+        println!("{} Already ignored: {}", "ℹ️".cyan(), entry);
+        return Ok(());
+    }
+    config.never_suggest.push(entry.clone());
+    config.save().context("Failed to save configuration")?;
+    println!("{} {} will never be suggested", "✅".green(), entry.color(colors::PATH));
+    Ok(())
+}
+
+/// Turn a `course set/remove` target into the stored pattern
+fn course_pattern(target: &str) -> Result<String> {
+    let probe = CourseOverride { pattern: target.to_string(), course: String::new() };
+    
+    if !probe.is_glob() {
+        let abs_path = Path::new(target).canonicalize()
+            .context(format!("Failed to canonicalize path: {}", target))?;
+        let pattern = abs_path.to_str()
+            .context(format!("Course overrides need a UTF-8 path: {}", abs_path.display()))?;
+        return Ok(pattern.to_string());
+    }
+    
+    // Path globs are matched against absolute paths; bare globs match file names
+    let is_path_glob = target.contains(['/', '\\']);
+    if is_path_glob && Path::new(target).is_relative() {
+        let cwd = std::env::current_dir()
+            .context("Failed to get current directory")?;
+        return Ok(cwd.join(target).to_string_lossy().to_string());
+    }
+    
+    Ok(target.to_string())
+}
+
+fn handle_course(
+    config: &mut Config,
+    subcommand: cli::CourseArgs,
+) -> Result<()> {
+    match subcommand {
+        cli::CourseArgs::Set { target, course } => {
+            let pattern = course_pattern(&target)?;
+            let course = course.trim().to_lowercase();
+            if course.is_empty() {
+                anyhow::bail!("Course name cannot be empty");
+            }
+            
+            if let Err(e) = glob::Pattern::new(&pattern) {
+                anyhow::bail!("Invalid pattern '{}': {}", pattern, e);
+            }
+            
+            config.course_overrides.retain(|o| o.pattern != pattern);
+            config.course_overrides.push(CourseOverride {
+                pattern: pattern.clone(),
+                course: course.clone(),
+            });
+            
+            config.save()
+                .context("Failed to save configuration")?;
+            println!("{} {} → {}", "✅".green(), pattern.color(colors::PATH), course.color(colors::HEADER));
+        }
+        cli::CourseArgs::Remove { target } => {
+            let pattern = course_pattern(&target).unwrap_or(target);
+            let before_len = config.course_overrides.len();
+            
+            config.course_overrides.retain(|o| o.pattern != pattern);
+            
+            if config.course_overrides.len() < before_len {
+                config.save()
+                    .context("Failed to save configuration")?;
+                println!("{} Removed course override: {}", "✅".green(), pattern);
+            } else {
+                println!("{} No course override for: {}", "ℹ️".cyan(), pattern);
+            }
+        }
+        cli::CourseArgs::List => {
+            println!("{}", "🏷️ COURSE OVERRIDES".bold().color(colors::HEADER));
+            println!("{}", "─".repeat(50).color(colors::PATH));
+            
+            if config.course_overrides.is_empty() {
+                println!("No course overrides");
+                println!("{} Add one with {}", "💡".cyan(), "cleancrush course set <path-or-glob> <course>".bold());
+            } else {
+                for course_override in &config.course_overrides {
+                    println!("• {} → {}", 
+                        course_override.pattern.color(colors::PATH), 
+                        course_override.course.color(colors::HEADER));
+                }
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+fn handle_config(
+    config: &mut Config,
+    args: cli::ConfigArgs,
+    safe_mode: bool,
+) -> Result<()> {
+    match args.action {
+        None => config.display(),
+        Some(cli::ConfigAction::Reset { yes, hard }) => {
+            if safe_mode {
+                println!("{} SAFE MODE: Configuration not reset", "🔒".yellow());
+                return Ok(());
+            }
+            
+            let keeping = if hard { "everything, progress included" } else { "your settings (streaks, achievements and totals are kept)" };
+            let proceed = yes || (!config::non_interactive() && {
+                use dialoguer::{theme::ColorfulTheme, Confirm};
+                Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("Reset {}?", keeping))
+                    .default(false)
+                    .interact()
+                    .context("Failed to get confirmation")?
+            });
+            if !proceed {
+                println!("{} Reset cancelled", "ℹ️".cyan());
+                return Ok(());
+            }
+            
+            let mut fresh = if config::non_interactive() {
+                Config::defaults()
+            } else {
+                Config::first_time_setup()?
+            };
+            if hard {
+                Gamification::new().save()
+                    .context("Failed to reset progress")?;
+            } else {
+                fresh.keep_progress_from(config);
+            }
+            
+            // Saving copies the old config to the backup file first
+            fresh.save()
+                .context("Failed to save configuration")?;
+            *config = fresh;
+            println!("{} Configuration reset - the previous one is in {}", 
+                "✅".green(),
+                Config::backup_path()?.display().to_string().color(colors::PATH));
+        }
+        Some(cli::ConfigAction::Set(setting)) => {
+            let changed: String = match setting {
+                cli::ConfigSetting::DefaultAction { value } => {
+                    config.default_action = match value {
+                        cli::CleanupActionCli::RecycleBin => CleanupAction::RecycleBin,
+                        cli::CleanupActionCli::Archive => CleanupAction::Archive,
+                    };
+                    match value {
+                        cli::CleanupActionCli::RecycleBin => "Cleaned files go to the Recycle Bin/Trash",
+                        cli::CleanupActionCli::Archive => "Cleaned files are archived into course folders",
+                    }.to_string()
+                }
+                cli::ConfigSetting::ReminderSchedule { value } => {
+                    config.reminder_schedule = match value {
+                        cli::ScheduleType::Never => ReminderSchedule::Never,
+                        cli::ScheduleType::Weekly => ReminderSchedule::Weekly,
+                        cli::ScheduleType::Monthly => ReminderSchedule::Monthly,
+                    };
+                    match value {
+                        cli::ScheduleType::Never => "Reminders disabled",
+                        cli::ScheduleType::Weekly => "Weekly reminders enabled (Sundays)",
+                        cli::ScheduleType::Monthly => "Monthly reminders enabled (1st of month)",
+                    }.to_string()
+                }
+                cli::ConfigSetting::ExamMonitoring { value } => {
+                    config.enable_exam_monitoring = matches!(value, cli::Toggle::On);
+                    if config.enable_exam_monitoring { "Exam monitoring enabled" } else { "Exam monitoring disabled" }.to_string()
+                }
+                cli::ConfigSetting::ConfidenceModel { value } => {
+                    config.confidence_model = match value {
+                        cli::ConfidenceModelCli::Max => ConfidenceModel::Max,
+                        cli::ConfidenceModelCli::Combined => ConfidenceModel::Combined,
+                    };
+                    match value {
+                        cli::ConfidenceModelCli::Max => "Confidence model: Max (strongest signal)",
+                        cli::ConfidenceModelCli::Combined => "Confidence model: Combined (signals compound)",
+                    }.to_string()
+                }
+                cli::ConfigSetting::VeryLargeMb { value } => {
+                    config.very_large_mb = value;
+                    format!("Large files over {} MB now count as very large in the score", value)
+                }
+                cli::ConfigSetting::CompressArchives { value } => {
+                    config.compress_archives = matches!(value, cli::Toggle::On);
+                    if config.compress_archives { "Archived files will be zipped after each archive run" } else { "Archived files stay uncompressed" }.to_string()
+                }
+            };
+            
+            config.save()
+                .context("Failed to save configuration")?;
+            println!("{} {}", "✅".green(), changed);
+        }
+        Some(cli::ConfigAction::Course(cli::ConfigCourseArgs::Add { name, keyword })) => {
+            let name = name.trim().to_lowercase();
+            let keyword = keyword.trim().to_lowercase();
+            if name.is_empty() || keyword.is_empty() {
+                anyhow::bail!("Course name and keyword cannot be empty");
+            }
+            
+            let added = match config.courses.iter_mut().find(|(course, _)| *course == name) {
+                Some((_, keywords)) if keywords.contains(&keyword) => false,
+                Some((_, keywords)) => {
+                    keywords.push(keyword.clone());
+                    true
+                }
+                None => {
+                    config.courses.push((name.clone(), vec![keyword.clone()]));
+                    true
+                }
+            };
+            
+            if !added {
+                println!("{} {} already detects {}", "ℹ️".cyan(), keyword.color(colors::PATH), name.color(colors::HEADER));
+                return Ok(());
+            }
+            
+            config.save()
+                .context("Failed to save configuration")?;
+            println!("{} Files named like \"*{}*\" → {}", "✅".green(), keyword.color(colors::PATH), name.color(colors::HEADER));
+            println!("   {} The longest matching keyword wins, earlier courses break ties - see {}", "💡".cyan(), "cleancrush config".bold());
+        }
+    }
+    
+    Ok(())
+}
+
+fn handle_archive(
+    config: &Config,
+    subcommand: cli::ArchiveArgs,
     safe_mode: bool,
 ) -> Result<()> {
     let archive_system = ArchiveSystem::new(config.clone())
@@ -902,20 +2698,326 @@ fn handle_archive(
                 );
             }
         }
-        cli::ArchiveArgs::Clean { days, yes } => {
+        cli::ArchiveArgs::Clean { days, yes, confirm, compress } => {
             if safe_mode {
                 println!("{} Archive cleaning disabled in safe mode", "⚠️".yellow());
                 return Ok(());
             }
             
-            archive_system.clean_old_archives(days, yes)?;
+            let policy = config.confirm_defaults.archive_clean.resolve(yes, confirm);
+            archive_system.clean_old_archives(days, policy, compress)?;
         }
         cli::ArchiveArgs::Stats => {
             archive_system.show_stats()?;
         }
-        cli::ArchiveArgs::Restore { .. } => {
-            println!("{} Archive restore not yet implemented", "⚠️".yellow());
-            println!("Coming in a future update!");
+        cli::ArchiveArgs::Find { text, restore } => {
+            let matches = archive_system.find_files(&text)
+                .context("Failed to search archives")?;
+            
+            if let Some(number) = restore {
+                let found = number.checked_sub(1).and_then(|i| matches.get(i))
+                    .with_context(|| format!("No match {} - `cleancrush archive find {}` lists {}", number, text, matches.len()))?;
+                let result = archive_system.restore(
+                    &found.archive_dir,
+                    &[found.index],
+                    None,
+                    false,
+                    safe_mode,
+                ).context("Failed to restore from archive")?;
+                archive_system.print_restore_summary(&result, safe_mode);
+                return Ok(());
+            }
+            
+            println!();
+            println!("{} {}", "🔎 ARCHIVED FILES MATCHING".bold().color(colors::HEADER), format!("\"{}\"", text).bold());
+            println!("{}", "─".repeat(50).color(colors::PATH));
+            
+            if matches.is_empty() {
+                println!("{} Nothing in any archive matches", "📭".cyan());
+                return Ok(());
+            }
+            
+            for (i, found) in matches.iter().enumerate() {
+                let archive = found.archive_dir.file_name().unwrap_or_default().to_string_lossy();
+                let status = if found.file.is_present() { "".normal() } else { " (missing)".dimmed() };
+                println!("{:3}. {} ({}, {})",
+                    i + 1,
+                    found.file.original_path.display().to_string().color(colors::PATH),
+                    cli::format_size(found.file.size_bytes),
+                    found.file.course.color(colors::HEADER));
+                // Consolidated archives are named by label, so add their date
+                let date = found.archive_date.format("%Y-%m-%d").to_string();
+                let archived_in = if archive == date { date } else { format!("{} ({})", archive, date) };
+                println!("     📁 {} → {}{}",
+                    archived_in,
+                    found.file.archived_path.display(),
+                    status);
+                println!("     ↩️  {}", format!("cleancrush archive restore {} {}", archive, found.index).dimmed());
+            }
+            
+            println!();
+            println!("{} Restore one with {}", 
+                "💡".cyan(),
+                format!("cleancrush archive find {} --restore N", text).bold());
+        }
+        cli::ArchiveArgs::Restore { date, mut indices, all, interactive, output, merge } => {
+            let archive_dir = archive_system.find_archive(&date)?;
+            
+            if interactive {
+                if config::non_interactive() {
+                    anyhow::bail!("--interactive needs a terminal; pass file numbers or --all instead");
+                }
+                indices = archive_system.pick_files(&archive_dir)?;
+                if indices.is_empty() {
+                    println!("{} Nothing selected - no files restored", "ℹ️".cyan());
+                    return Ok(());
+                }
+            } else if indices.is_empty() && !all {
+                archive_system.show_archive_files(&archive_dir)?;
+                println!();
+                println!("{} Restore with {}, {} or {}", 
+                    "💡".cyan(),
+                    format!("cleancrush archive restore {} 1 2 3", date).bold(),
+                    format!("cleancrush archive restore {} --all", date).bold(),
+                    format!("cleancrush archive restore {} --interactive", date).bold());
+                return Ok(());
+            }
+            
+            let result = archive_system.restore(
+                &archive_dir, 
+                &indices, 
+                output.as_deref(), 
+                merge, 
+                safe_mode,
+            ).context("Failed to restore from archive")?;
+            archive_system.print_restore_summary(&result, safe_mode);
+        }
+        cli::ArchiveArgs::Tidy { dry_run, yes } => {
+            let untidy = archive_system.find_untidy_archives()
+                .context("Failed to check archives")?;
+            archive_system.print_untidy(&untidy);
+            
+            let (empty, stale): (Vec<_>, Vec<_>) = untidy.into_iter()
+                .filter(|archive| !archive.keep_forever)
+                .partition(|archive| archive.reason == UntidyReason::Empty);
+            if empty.is_empty() && stale.is_empty() {
+                return Ok(());
+            }
+            if dry_run || safe_mode {
+                println!();
+                println!("{} Would remove {} empty folder{} and offer {} stale one{}", "🌵".yellow(),
+                    empty.len(), if empty.len() == 1 { "" } else { "s" },
+                    stale.len(), if stale.len() == 1 { "" } else { "s" });
+                return Ok(());
+            }
+            
+            // Empty folders hold nothing, so they go without asking
+            let mut to_remove: Vec<PathBuf> = empty.into_iter().map(|archive| archive.path).collect();
+            if !stale.is_empty() {
+                let remove_stale = yes || (!config::non_interactive() && {
+                    println!();
+                    use dialoguer::{theme::ColorfulTheme, Confirm};
+                    Confirm::with_theme(&ColorfulTheme::default())
+                        .with_prompt(format!("Delete the {} stale archive folders, leftover files included?", stale.len()))
+                        .default(false)
+                        .interact()
+                        .context("Failed to get confirmation")?
+                });
+                if remove_stale {
+                    to_remove.extend(stale.into_iter().map(|archive| archive.path));
+                } else {
+                    println!("{} Stale folders kept", "ℹ️".cyan());
+                }
+            }
+            
+            println!();
+            let mut removed = 0;
+            for path in &to_remove {
+                match fs::remove_dir_all(path) {
+                    Ok(()) => {
+                        removed += 1;
+                        println!("{} Removed {}", "✅".green(), path.display());
+                    }
+                    Err(e) => println!("{} Couldn't remove {}: {}", "⚠️".yellow(), path.display(), e),
+                }
+            }
+            println!("{} Tidied {} archive folder{}", "🧽".cyan(), removed, if removed == 1 { "" } else { "s" });
+        }
+        cli::ArchiveArgs::Consolidate { dates, all, into, dry_run } => {
+            let dry_run = dry_run || safe_mode;
+            let archives = archive_system.list_archives()
+                .context("Failed to list archives")?;
+            
+            let mut sources = Vec::new();
+            if all {
+                sources = archives;
+            } else {
+                for date in &dates {
+                    let dir = archive_system.find_archive(date)?;
+                    if let Some(source) = archives.iter().find(|(path, _)| *path == dir) {
+                        if !sources.contains(source) {
+                            sources.push(source.clone());
+                        }
+                    }
+                }
+            }
+            // Merging into an existing archive adds to it rather than moving it
+            sources.retain(|(path, _)| path.file_name().is_none_or(|n| n != into.as_str()));
+            
+            if sources.is_empty() {
+                println!("{} No archives to merge into {}", "📭".cyan(), into);
+                return Ok(());
+            }
+            
+            println!();
+            println!("{} Merging {} archives into {}:", "📦".cyan(), sources.len(), into.bold());
+            for (path, _) in &sources {
+                println!("   • {}", path.display().to_string().color(colors::PATH));
+            }
+            
+            let result = archive_system.consolidate_archives(&sources, &into, dry_run)
+                .context("Failed to consolidate archives")?;
+            archive_system.print_consolidate_summary(&result, dry_run);
+            
+            if !dry_run && result.moved > 0 {
+                println!();
+                println!("{} Restore from it with {}", "💡".cyan(),
+                    format!("cleancrush archive restore {}", into).bold());
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+fn handle_restore_last(
+    config: &Config,
+    args: &cli::RestoreLastArgs,
+    safe_mode: bool,
+) -> Result<()> {
+    let mut history = History::load()
+        .context("Failed to load cleanup history")?;
+    let Some(last) = history.last_cleanup() else {
+        println!("{} No cleanup to restore - the journaled ones were all put back, or nothing has been cleaned yet", 
+            "ℹ️".cyan());
+        return Ok(());
+    };
+    
+    let destination = match last.action {
+        CleanupAction::RecycleBin => "the Recycle Bin/Trash",
+        CleanupAction::Archive => "the archive",
+    };
+    println!("{} Last cleanup: {} on {} - {} file{} sent to {}", 
+        "🕐".cyan(),
+        last.operation.bold(),
+        last.cleaned_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+        last.files.len(),
+        if last.files.len() == 1 { "" } else { "s" },
+        destination);
+    
+    if args.dry_run || safe_mode {
+        if safe_mode {
+            println!("{} SAFE MODE: Showing preview only", "🔒".yellow());
+        }
+        for path in &last.files {
+            println!("   • {}", path.display().to_string().color(colors::PATH));
+        }
+        return Ok(());
+    }
+    
+    restore_cleaned_files(config, &last.action, &last.files)?;
+    
+    last.restored = true;
+    history.save()
+        .context("Failed to update cleanup history")?;
+    Ok(())
+}
+
+fn handle_trash(
+    subcommand: cli::TrashArgs,
+    safe_mode: bool,
+) -> Result<()> {
+    let mut manifest = TrashManifest::load()
+        .context("Failed to load trash manifest")?;
+    
+    match subcommand {
+        cli::TrashArgs::List => {
+            manifest.print_list();
+        }
+        cli::TrashArgs::Restore { indices, all } => {
+            if indices.is_empty() && !all {
+                manifest.print_list();
+                println!();
+                println!("{} Restore with {} or {}", 
+                    "💡".cyan(),
+                    "cleancrush trash restore 1 2 3".bold(),
+                    "cleancrush trash restore --all".bold());
+                return Ok(());
+            }
+            
+            if safe_mode {
+                println!("{} Restoring disabled in safe mode", "⚠️".yellow());
+                return Ok(());
+            }
+            
+            let selected: Vec<usize> = if all {
+                (0..manifest.files.len()).collect()
+            } else {
+                let mut selected = Vec::new();
+                for index in indices {
+                    if index == 0 || index > manifest.files.len() {
+                        anyhow::bail!("Invalid file number: {} (valid: 1-{})", index, manifest.files.len());
+                    }
+                    if !selected.contains(&(index - 1)) {
+                        selected.push(index - 1);
+                    }
+                }
+                selected
+            };
+            
+            let mut finished = Vec::new();
+            let mut restored = 0;
+            
+            for i in selected {
+                let entry = &manifest.files[i];
+                let shown = entry.original_path.display().to_string();
+                match trash_manifest::restore(entry) {
+                    RestoreOutcome::Restored => {
+                        println!("{} Restored {}", "✅".green(), shown.color(colors::PATH));
+                        restored += 1;
+                        finished.push(i);
+                    }
+                    RestoreOutcome::Occupied => {
+                        println!("{} {} already exists - move it aside and try again", "⚠️".yellow(), shown.color(colors::PATH));
+                    }
+                    RestoreOutcome::NotInTrash => {
+                        println!("{} {} is no longer in the trash (it may have been emptied)", "📭".cyan(), shown.color(colors::PATH));
+                        finished.push(i);
+                    }
+                    RestoreOutcome::Unsupported => {
+                        println!("{} Restore it from your file manager's trash: {}", "💡".cyan(), shown.color(colors::PATH));
+                    }
+                    RestoreOutcome::Failed(e) => {
+                        println!("{} Failed to restore {}: {}", "❌".red(), shown.color(colors::PATH), e);
+                    }
+                }
+            }
+            
+            if !finished.is_empty() {
+                let mut i = 0;
+                manifest.files.retain(|_| {
+                    let keep = !finished.contains(&i);
+                    i += 1;
+                    keep
+                });
+                manifest.save()
+                    .context("Failed to update trash manifest")?;
+            }
+            
+            if restored > 0 {
+                println!();
+                println!("{} Restored {} file{}", "✅".green(), restored, if restored == 1 { "" } else { "s" });
+            }
         }
     }
     
@@ -924,7 +3026,9 @@ fn handle_archive(
 
 fn handle_schedule(
     config: &mut Config,
+    exam_manager: &ExamManager,
     subcommand: cli::ScheduleArgs,
+    safe_mode: bool,
 ) -> Result<()> {
     match subcommand {
         cli::ScheduleArgs::Set { schedule } => {
@@ -964,21 +3068,186 @@ fn handle_schedule(
                 );
             }
         }
-        cli::ScheduleArgs::Run => {
-            println!("{} Running scheduled cleanup...", "🧹".cyan());
-            // This would trigger a cleanup based on schedule
-            // For now, just remind
-            show_reminder(config);
+        cli::ScheduleArgs::Run => run_scheduled_cleanup(config, exam_manager, safe_mode)?,
+    }
+    
+    Ok(())
+}
+
+/// Unattended cleanup for `schedule run` (e.g. from cron): clean duplicates
+/// and old files in the default folder without prompting. Anything that
+/// would need a decision - cloud, protected, locked or exam-tracked files -
+/// is skipped rather than asked about.
+fn run_scheduled_cleanup(config: &mut Config, exam_manager: &ExamManager, safe_mode: bool) -> Result<()> {
+    if matches!(config.reminder_schedule, ReminderSchedule::Never) {
+        anyhow::bail!("Scheduled cleanup is off - turn it on with `cleancrush schedule set weekly`");
+    }
+    
+    let folder = config.primary_scan_path();
+    // Nobody is there to confirm a sweep this wide, so there's no override
+    if let Some((folder, what)) = broad_path(&folder) {
+        anyhow::bail!("{} is {} - scheduled cleanup only sweeps narrower folders; set `default_scan_paths` in ~/.cleancrush.json",
+            folder.display(), what);
+    }
+    println!("{} Running scheduled cleanup in {}...", "🧹".cyan(), folder.display());
+    
+    let scanner = Scanner::new(config.clone(), exam_manager.is_active());
+    let scan_result = scanner.scan(&folder, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
+        .context("Failed to scan for scheduled cleanup")?;
+    
+    // Duplicates (one copy of each kept) plus old files
+    let mut files = duplicates_to_clean(
+        &scan_result.files_by_category(FileCategory::Duplicate),
+        &[],
+        cli::KeepCopy::default(),
+    );
+    for file in scan_result.files.iter().filter(|f| f.category == FileCategory::Old) {
+        if !files.contains(&file.path) {
+            files.push(file.path.clone());
+        }
+    }
+    let files = skip_symlinks(files, false);
+    
+    let archive_system = ArchiveSystem::new(config.clone())
+        .context("Failed to create archive system")?;
+    let tracked = exam_manager.tracked_among(&files);
+    let (files, skipped): (Vec<PathBuf>, Vec<PathBuf>) = files.into_iter()
+        .partition(|path| !tracked.contains(path)
+            && !archive_system.is_in_cloud_folder(path)
+            && config.is_protected(path).is_none()
+            && !archive_system.is_file_locked(path));
+    
+    if !skipped.is_empty() {
+        println!("{} Skipping {} file{} that would need a decision (cloud, protected, open or exam-tracked) - clean them with {}",
+            "ℹ️".cyan(),
+            skipped.len(),
+            if skipped.len() == 1 { "" } else { "s" },
+            "cleancrush clean".bold());
+    }
+    
+    if files.is_empty() {
+        println!("{} Nothing to clean - {} is tidy ✨", "✨".green(), folder.display());
+        if !safe_mode {
+            config.update_last_cleanup()?;
         }
+        return Ok(());
+    }
+    
+    let cleanup_result = archive_system.clean_files(&files, false, safe_mode, "scheduled cleanup")?;
+    
+    if !safe_mode {
+        if cleanup_result.files_processed > 0 {
+            config.update_stats(
+                cleanup_result.files_processed,
+                cleanup_result.total_size_bytes,
+            );
+            digest::refresh_after_cleanup(&cleanup_result.successful_files);
+        }
+        config.update_last_cleanup()?;
+    }
+    
+    println!();
+    println!("{} Scheduled cleanup: {} {} of {} files, {} {}",
+        "📅".cyan(),
+        if safe_mode { "would remove" } else { "removed" },
+        cleanup_result.files_processed,
+        files.len(),
+        if safe_mode { "would free" } else { "freed" },
+        cli::format_size(cleanup_result.total_size_bytes));
+    
+    Ok(())
+}
+
+fn handle_status(
+    config: &Config,
+    exam_manager: &ExamManager,
+    gamification: &Gamification,
+) -> Result<()> {
+    // Quick estimate first, so its scan output comes before the dashboard
+    let folder = config.primary_scan_path();
+    let score = if folder.exists() {
+        let scanner = Scanner::new(config.clone(), false)
+            .with_options(ScanOptions { quick: true, ..ScanOptions::default() });
+        let result = scanner.scan(&folder, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
+            .context("Failed to scan for the cleanliness estimate")?;
+        let (duplicates, old, large, very_large) = result.estimated_cleanliness_counts(config.very_large_bytes());
+        Some(gamification.calculate_cleanliness_score(duplicates, old, large, very_large))
+    } else {
+        None
+    };
+    
+    println!();
+    println!("{}", "📋 CLEANCRUSH STATUS".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+    
+    // Exam mode
+    match exam_manager.get_tracker() {
+        Some(tracker) => println!("🎓 Exam mode: {} ({} days, {} files tracked)",
+            "Active".color(colors::SUCCESS),
+            tracker.days_since_start(),
+            tracker.total_files()),
+        None => println!("🎓 Exam mode: Not active"),
+    }
+    if let Some(end_date) = exam_manager.overdue_end_date() {
+        println!("   {} Ended {} - run {} for post-exam cleanup", 
+            "📅".yellow(), end_date.format("%Y-%m-%d"), "cleancrush exam end".bold());
+    }
+    
+    // Last cleanup and reminders
+    let days_since = config.last_cleanup.as_ref()
+        .and_then(|last| last.parse::<chrono::DateTime<Utc>>().ok())
+        .map(|last| (Utc::now() - last).num_days());
+    let due = if config.is_reminder_due() {
+        format!(" - {}", "cleanup reminder due".color(colors::WARNING))
+    } else {
+        String::new()
+    };
+    match days_since {
+        Some(days) => println!("🧹 Last cleanup: {} days ago{}", days, due),
+        None => println!("🧹 Last cleanup: never{}", due),
+    }
+    
+    // Archives
+    let archive_system = ArchiveSystem::new(config.clone())
+        .context("Failed to create archive system")?;
+    let archives = archive_system.archives_needing_attention()?;
+    if archives.is_empty() {
+        println!("📁 Archives: none need attention");
+    } else {
+        println!("📁 Archives: {} need{} attention ({})",
+            archives.len().to_string().color(colors::WARNING),
+            if archives.len() == 1 { "s" } else { "" },
+            "cleancrush archive list".bold());
+    }
+    
+    // Streak
+    println!("🔥 Streak: {}", gamification.streak_window.describe(gamification.current_streak).color(colors::WARNING));
+    
+    // Cleanliness
+    match score {
+        Some((score, _)) => println!("🏆 {}: ~{}/100 (estimate)",
+            folder.display().to_string().color(colors::PATH), score),
+        None => println!("🏆 {}: folder not found", folder.display().to_string().color(colors::PATH)),
     }
     
     Ok(())
 }
 
 fn handle_stats(
-    config: &Config,
-    gamification: &Gamification,
+    config: &mut Config,
+    gamification: &mut Gamification,
+    args: &cli::StatsArgs,
+    safe_mode: bool,
 ) -> Result<()> {
+    if args.reset {
+        return reset_stats(config, gamification, args.keep_achievements, args.yes, safe_mode);
+    }
+    
+    if let Some(period) = args.period {
+        gamification.display_period(period.days());
+        return Ok(());
+    }
+    
     println!();
     println!("{}", "📊 CLEANCRUSH STATISTICS".bold().color(colors::HEADER));
     println!("{}", "─".repeat(50).color(colors::PATH));
@@ -987,8 +3256,8 @@ fn handle_stats(
         config.total_files_cleaned.to_string().color(colors::SUCCESS));
     println!("💾 Space freed: {:.1} MB", 
         config.total_space_freed_mb.to_string().color(colors::SUCCESS));
-    println!("🔥 Current streak: {} days", 
-        config.streaks.to_string().color(colors::WARNING));
+    println!("🔥 Current streak: {}", 
+        gamification.streak_window.describe(config.streaks).color(colors::WARNING));
     
     if let Some(last) = &config.last_cleanup {
         let last_date: chrono::DateTime<Utc> = last.parse()
@@ -1012,40 +3281,70 @@ fn handle_stats(
     Ok(())
 }
 
+/// `stats --reset`: zero streaks and totals for a fresh semester
+fn reset_stats(
+    config: &mut Config,
+    gamification: &mut Gamification,
+    keep_achievements: bool,
+    yes: bool,
+    safe_mode: bool,
+) -> Result<()> {
+    if safe_mode {
+        println!("{} SAFE MODE: Stats not reset", "🔒".yellow());
+        return Ok(());
+    }
+    
+    let clearing = if keep_achievements {
+        "streaks and totals (achievements are kept)"
+    } else {
+        "streaks, totals and achievements"
+    };
+    let proceed = yes || (!config::non_interactive() && {
+        use dialoguer::{theme::ColorfulTheme, Confirm};
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Reset {}?", clearing))
+            .default(false)
+            .interact()
+            .context("Failed to get confirmation")?
+    });
+    if !proceed {
+        println!("{} Reset cancelled", "ℹ️".cyan());
+        return Ok(());
+    }
+    
+    config.reset_progress(keep_achievements);
+    gamification.reset(keep_achievements);
+    config.save().context("Failed to save stats")?;
+    gamification.save().context("Failed to save progress")?;
+    
+    println!("{} Fresh slate - reset {}", "🌱".green(), clearing);
+    Ok(())
+}
+
 fn handle_score(
     config: &Config,
     args: &cli::ScoreArgs,
+    safe_mode: bool,
 ) -> Result<()> {
     let path = args.path.canonicalize()
         .context(format!("Failed to canonicalize path: {}", args.path.display()))?;
     
-    let scanner = Scanner::new(config.clone(), false);
+    if args.trend {
+        let history = ScoreHistory::load()?;
+        score_history::print_trend(&path, &history.for_folder(&path));
+        return Ok(());
+    }
+    
+    let scanner = Scanner::new(config.clone(), false)
+        .with_options(ScanOptions { quick: args.quick, ..ScanOptions::default() });
     let result = scanner.scan(&path, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
         .context("Failed to scan directory for scoring")?;
     
     // Calculate cleanliness score USING the gamification method
-    let gamification = Gamification::load_from_config(config);
+    let gamification = Gamification::load(config);
     
-    let mut duplicate_count = 0;
-    let mut old_count = 0;
-    let mut large_count = 0;
-    let mut very_large_count = 0;
+    let (duplicate_count, old_count, large_count, very_large_count) = result.estimated_cleanliness_counts(config.very_large_bytes());
     
-    for file in &result.files {
-        match file.category {
-            FileCategory::Duplicate => duplicate_count += 1,
-            FileCategory::Old => old_count += 1,
-            FileCategory::Large => {
-                if file.size_bytes > 500 * 1024 * 1024 {
-                    very_large_count += 1;
-                } else {
-                    large_count += 1;
-                }
-            }
-            _ => {}
-        }
-    }
-
 // USE the calculate_cleanliness_score method
 let (score, breakdown) = gamification.calculate_cleanliness_score(
         duplicate_count,
@@ -1055,7 +3354,11 @@ let (score, breakdown) = gamification.calculate_cleanliness_score(
     );
     
     println!();
-    println!("{}", "🏆 CLEANLINESS SCORE".bold().color(colors::HEADER));
+    if args.quick {
+        println!("{}", "🏆 CLEANLINESS SCORE (estimate)".bold().color(colors::HEADER));
+    } else {
+        println!("{}", "🏆 CLEANLINESS SCORE".bold().color(colors::HEADER));
+    }
     println!("{}", "─".repeat(50).color(colors::PATH));
     
     // Show score with emoji
@@ -1088,6 +3391,22 @@ let (score, breakdown) = gamification.calculate_cleanliness_score(
         }.color(score_color)
     );
     
+    // Remember it for `score --trend`; the score itself is already shown
+    if !safe_mode {
+        if let Err(e) = ScoreHistory::record(&path, score, args.quick, &breakdown) {
+            println!("{} Couldn't save this score for --trend: {:#}", "⚠️".yellow(), e);
+        }
+    }
+    
+    if args.quick {
+        match result.sampled_files {
+            Some(sampled) => println!("{} Estimated from {} of {} files; duplicates guessed by name and size", 
+                "ℹ️".cyan(), sampled, result.total_files_scanned),
+            None => println!("{} Duplicates guessed by name and size, not content", "ℹ️".cyan()),
+        }
+        println!("   Run without {} for the exact score", "--quick".bold());
+    }
+    
     // Show breakdown from the gamification method
 if !breakdown.is_empty() && breakdown != "Perfect! No issues found ✨" {
         println!();
@@ -1098,6 +3417,10 @@ if !breakdown.is_empty() && breakdown != "Perfect! No issues found ✨" {
         println!("{} Perfect! No issues found ✨", "🎉".green());
     }
     
+    if args.detailed {
+        print_score_details(&path, &result, config.very_large_bytes());
+    }
+    
     // Show suggestions
     println!();
     println!("{} To improve your score:", "💡".cyan());
@@ -1117,6 +3440,254 @@ if !breakdown.is_empty() && breakdown != "Perfect! No issues found ✨" {
             "cleancrush suggest".bold());
     }
     
+    if args.explain {
+        print_score_explanation(&path, &result, config.very_large_bytes());
+    }
+    
+    Ok(())
+}
+
+/// Files behind each score penalty: (label, points per file, file indices).
+/// Same buckets as `ScanResult::cleanliness_counts`; indices are into this
+/// scan only.
+fn penalty_groups(result: &ScanResult, very_large_bytes: u64) -> [(&'static str, usize, Vec<usize>); 4] {
+    use gamification::{DUPLICATE_PENALTY, OLD_PENALTY, LARGE_PENALTY, VERY_LARGE_PENALTY};
+    
+    let mut groups: [(&str, usize, Vec<usize>); 4] = [
+        ("duplicate", DUPLICATE_PENALTY, Vec::new()),
+        ("old file", OLD_PENALTY, Vec::new()),
+        ("large file", LARGE_PENALTY, Vec::new()),
+        ("very large file", VERY_LARGE_PENALTY, Vec::new()),
+    ];
+    for (i, file) in result.files.iter().enumerate() {
+        let group = match file.category {
+            FileCategory::Duplicate => 0,
+            FileCategory::Old => 1,
+            FileCategory::Large if file.size_bytes > very_large_bytes => 3,
+            FileCategory::Large => 2,
+            _ => continue,
+        };
+        groups[group].2.push(i);
+    }
+    groups
+}
+
+/// `score --detailed`: every file that costs points, biggest cost first
+fn print_score_details(root: &Path, result: &ScanResult, very_large_bytes: u64) {
+    let groups = penalty_groups(result, very_large_bytes);
+    let mut costs: Vec<(usize, usize, &str)> = groups.iter()
+        .flat_map(|(label, penalty, indices)| indices.iter().map(move |&i| (*penalty, i, *label)))
+        .collect();
+    costs.sort_by_key(|&(penalty, i, _)| (std::cmp::Reverse(penalty), i));
+    
+    println!();
+    println!("{}", "📋 POINTS PER FILE".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+    
+    if costs.is_empty() {
+        println!("{} No file costs any points ✨", "🎉".green());
+        return;
+    }
+    
+    for (penalty, i, label) in costs {
+        let file = &result.files[i];
+        let shown = file.path.strip_prefix(root).unwrap_or(&file.path);
+        println!("   {} {} ({}, {})",
+            format!("-{}", penalty).color(colors::HIGH_CONFIDENCE).bold(),
+            shown.display().to_string().color(colors::PATH),
+            label,
+            cli::format_size(file.size_bytes));
+    }
+    
+    if let Some(sampled) = result.sampled_files {
+        println!("{} Only the {} sampled files are listed; the score counts the whole folder", 
+            "ℹ️".cyan(), sampled);
+    }
+}
+
+/// Trace each score penalty back to the files behind it
+fn print_score_explanation(root: &Path, result: &ScanResult, very_large_bytes: u64) {
+    let groups = penalty_groups(result, very_large_bytes);
+    
+    println!();
+    println!("{}", "🔍 WHERE THE POINTS WENT".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+    
+    if groups.iter().all(|(_, _, indices)| indices.is_empty()) {
+        println!("{} No penalties - nothing to explain ✨", "🎉".green());
+        return;
+    }
+    
+    for (label, penalty, indices) in &groups {
+        if indices.is_empty() {
+            continue;
+        }
+        
+        let total: u64 = indices.iter().map(|&i| result.files[i].size_bytes).sum();
+        println!();
+        println!("{} {} {}{} ({})",
+            format!("-{}:", penalty * indices.len()).color(colors::HIGH_CONFIDENCE).bold(),
+            indices.len(),
+            label,
+            if indices.len() == 1 { "" } else { "s" },
+            cli::format_size(total));
+        
+        for &i in indices {
+            let file = &result.files[i];
+            println!("   • {} ({})",
+                file.path.display().to_string().color(colors::PATH),
+                cli::format_size(file.size_bytes));
+        }
+    }
+    
+    // Score's scan isn't saved, so its numbers wouldn't match `delete`
+    println!();
+    println!("{} Review and clean these with {}",
+        "💡".cyan(),
+        format!("cleancrush suggest \"{}\"", root.display()).bold());
+}
+
+/// Recommend the one cleanup with the best space-to-risk payoff
+fn handle_top(
+    config: &Config,
+    args: &cli::TopArgs,
+) -> Result<()> {
+    let path = args.path.canonicalize()
+        .context(format!("Failed to canonicalize path: {}", args.path.display()))?;
+    
+    let scanner = Scanner::new(config.clone(), false);
+    let result = scanner.scan(&path, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
+        .context("Failed to scan directory")?;
+    
+    // Each candidate sweep matches what the suggested `clean --mode` would pick
+    let candidates = [
+        (FileCategory::Duplicate, ("duplicate copy", "duplicate copies"), "--mode duplicates".to_string()),
+        (FileCategory::Old, ("old file", "old files"), format!("--mode old --days {}", DEFAULT_OLD_DAYS)),
+        (FileCategory::Large, ("large file", "large files"), "--mode large".to_string()),
+    ];
+    
+    // Payoff is reclaimable space weighted by confidence, so sure wins beat risky ones
+    let best = candidates.iter()
+        .map(|(category, label, mode)| {
+            let files = sweep(&result.files, category.clone());
+            let bytes: u64 = files.iter().map(|f| f.size_bytes).sum();
+            let payoff: f64 = files.iter().map(|f| f.size_bytes as f64 * f.confidence as f64).sum();
+            let confidence = if files.is_empty() {
+                0.0
+            } else {
+                files.iter().map(|f| f.confidence).sum::<f32>() / files.len() as f32
+            };
+            (files.len(), bytes, payoff, confidence, *label, mode)
+        })
+        .filter(|(count, ..)| *count > 0)
+        .max_by(|a, b| a.2.total_cmp(&b.2));
+    
+    println!();
+    println!("{}", "🎯 BIGGEST WIN".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+    
+    let Some((count, bytes, _, confidence, label, mode)) = best else {
+        println!("{} Nothing worth cleaning in {} ✨", "🎉".green(), path.display());
+        return Ok(());
+    };
+    
+    println!("Deleting {} {} in {} frees {} ({} confidence).",
+        count.to_string().bold(),
+        if count == 1 { label.0 } else { label.1 },
+        path.display().to_string().color(colors::PATH),
+        cli::format_size(bytes).bold(),
+        config.confidence_level(confidence).color(config.confidence_color(confidence)));
+    println!();
+    println!("{} Run: {}", 
+        "👉".cyan(),
+        format!("cleancrush clean {} \"{}\"", mode, path.display()).bold());
+    println!("{} Preview first with {}, or see everything with {}", 
+        "💡".cyan(),
+        "--dry-run".bold(),
+        "cleancrush suggest".bold());
+    
+    Ok(())
+}
+
+fn handle_calibrate(
+    config: &Config,
+    exam_manager: &ExamManager,
+    args: &cli::CalibrateArgs,
+    safe_mode: bool,
+) -> Result<()> {
+    if config::non_interactive() {
+        anyhow::bail!("calibrate asks about each file - run it in a terminal");
+    }
+    
+    let path = args.path.canonicalize()
+        .context(format!("Failed to canonicalize path: {}", args.path.display()))?;
+    
+    // Every band is wanted, including files a normal scan would drop
+    let options = ScanOptions {
+        keep_low_confidence: true,
+        ..ScanOptions::default()
+    };
+    let scanner = Scanner::new(config.clone(), exam_manager.is_active())
+        .with_options(options);
+    let result = scanner.scan(&path, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB)
+        .context("Failed to scan directory")?;
+    
+    let sample = calibration::sample(&result.files, args.samples);
+    if sample.is_empty() {
+        println!("{} No files to ask about in {}", "ℹ️".cyan(), path.display());
+        return Ok(());
+    }
+    
+    println!();
+    println!("{}", "🎯 CONFIDENCE CALIBRATION".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+    println!("Would you actually clean each of these? Scores stay hidden until the end (Esc to stop early).");
+    println!();
+    
+    use dialoguer::{theme::ColorfulTheme, Confirm};
+    let mut answers = Vec::new();
+    for (i, file) in sample.iter().enumerate() {
+        println!("{:3}. {}", i + 1, file.display_path().color(colors::PATH));
+        println!("     {}, {} days old", cli::format_size(file.size_bytes), file.days_old);
+        let answer = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Would you clean this?")
+            .default(false)
+            .interact_opt()
+            .context("Failed to get answer")?;
+        let Some(would_clean) = answer else {
+            break;
+        };
+        answers.push(calibration::Answer { confidence: file.confidence, would_clean });
+        println!();
+    }
+    
+    if answers.is_empty() {
+        println!("{} No answers given", "ℹ️".cyan());
+        return Ok(());
+    }
+    
+    println!();
+    println!("{}", "📊 THIS SESSION".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+    calibration::print_report(&answers, config.preselect_confidence);
+    
+    if safe_mode {
+        println!("{} SAFE MODE: answers not saved", "🔒".yellow());
+        return Ok(());
+    }
+    
+    let mut stats = calibration::CalibrationStats::load()?;
+    stats.record(&answers);
+    stats.save()?;
+    
+    if stats.sessions > 1 {
+        println!();
+        println!("{}", format!("📈 ALL SESSIONS ({} answers over {} runs)", stats.answers.len(), stats.sessions)
+            .bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        calibration::print_report(&stats.answers, config.preselect_confidence);
+    }
+    
     Ok(())
 }
 
@@ -1130,7 +3701,45 @@ fn handle_achievements(gamification: &Gamification) -> Result<()> {
     Ok(())
 }
 
-fn show_reminder(config: &Config) {
+/// The exam period's end date has passed: offer post-exam cleanup, or just
+/// point at `exam end` with `notify_only` (safe mode) or without a terminal
+fn offer_exam_end(
+    config: &mut Config,
+    exam_manager: &mut ExamManager,
+    end_date: chrono::DateTime<Utc>,
+    notify_only: bool,
+    gamification: &mut Gamification,
+) -> Result<()> {
+    let name = exam_manager.get_tracker()
+        .and_then(|tracker| tracker.exam_period_name.clone())
+        .map(|name| format!(" '{}'", name))
+        .unwrap_or_default();
+    println!();
+    println!("{} Your exam period{} ended on {}", "📅".yellow(), name, end_date.format("%Y-%m-%d"));
+    
+    if notify_only || config::non_interactive() {
+        println!("   Run {} for post-exam cleanup", "cleancrush exam end".bold());
+        return Ok(());
+    }
+    
+    use dialoguer::{theme::ColorfulTheme, Confirm};
+    let proceed = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Run post-exam cleanup now?")
+        .default(true)
+        .interact()
+        .context("Failed to get confirmation")?;
+    if proceed {
+        handle_exam(config, exam_manager, cli::ExamArgs::End { yes: false, confirm: false }, false, gamification)?;
+    } else {
+        println!("   Run {} when you're ready", "cleancrush exam end".bold());
+    }
+    
+    Ok(())
+}
+
+/// `writable` is false for read-only commands, which don't hold the instance
+/// lock and so only read the digest baseline
+fn show_reminder(config: &Config, scan: bool, writable: bool) {
     println!();
     println!("{}", "💡 CLEANUP REMINDER".bold().color(colors::HEADER));
     println!("{}", "─".repeat(50).color(colors::PATH));
@@ -1150,6 +3759,24 @@ fn show_reminder(config: &Config) {
     println!("It's been {} days since your last cleanup.", 
         days_since.to_string().color(colors::WARNING));
     
+    // Data-driven nudge: compare Downloads against the last cleanup
+    if scan {
+        if let Some(downloads) = dirs::download_dir() {
+            match digest::print_growth_summary(&downloads, writable) {
+                Ok(true) => {
+                    println!("{} Run: {}", "💡".cyan(), "cleancrush scan ~/Downloads".bold());
+                    println!();
+                    return;
+                }
+                Ok(false) if writable => {
+                    println!("{} Measured Downloads - next reminder will show how much it grew", "📏".cyan());
+                }
+                Ok(false) => {}
+                Err(e) => println!("{} Couldn't measure Downloads: {}", "⚠️".yellow(), e),
+            }
+        }
+    }
+    
     use dialoguer::{theme::ColorfulTheme, Confirm};
     let want_scan = Confirm::with_theme(&ColorfulTheme::default())
         .with_prompt("Want to scan your Downloads folder?")
@@ -1162,4 +3789,280 @@ fn show_reminder(config: &Config) {
     }
     
     println!();
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolated_home;
+    
+    fn init(force: bool) -> Result<()> {
+        handle_init(&cli::InitArgs { non_interactive: true, force, dry_run: false }, false)
+    }
+    
+    /// Three identical copies: organized/, dl/ and the oldest in dl/old/
+    fn duplicate_copies(dir: &Path) -> Vec<scanner::FileInfo> {
+        for (folder, days) in [("organized", 2), ("dl", 1), ("dl/old", 3)] {
+            let path = dir.join(folder).join("notes.pdf");
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "same notes").unwrap();
+            fs::File::options().write(true).open(&path).unwrap()
+                .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(days * 86_400)).unwrap();
+        }
+        Scanner::new(Config::defaults(), false).scan(dir, 30, 100).unwrap()
+            .files_by_category(FileCategory::Duplicate)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+    
+    fn cleaned(files: &[scanner::FileInfo], patterns: &[&str], keep: cli::KeepCopy) -> Vec<PathBuf> {
+        let patterns: Vec<glob::Pattern> = patterns.iter().map(|p| glob::Pattern::new(p).unwrap()).collect();
+        duplicates_to_clean(&files.iter().collect::<Vec<_>>(), &patterns, keep)
+    }
+    
+    #[test]
+    fn keep_patterns_win_over_keep_oldest_and_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = duplicate_copies(dir.path());
+        assert_eq!(files.len(), 3);
+        let copy = |folder: &str| dir.path().join(folder).join("notes.pdf");
+        
+        // A matching copy is kept whichever way --keep points
+        for keep in [cli::KeepCopy::Oldest, cli::KeepCopy::Newest] {
+            let mut expected = vec![copy("dl"), copy("dl/old")];
+            expected.sort();
+            assert_eq!(cleaned(&files, &["*/organized/*"], keep), expected);
+        }
+        
+        // Every matching copy stays
+        assert_eq!(cleaned(&files, &["*/organized/*", "*/old/*"], cli::KeepCopy::Newest), vec![copy("dl")]);
+        assert!(cleaned(&files, &["notes.pdf"], cli::KeepCopy::Oldest).is_empty());
+        
+        // No match: the oldest or newest copy is kept
+        let mut expected = vec![copy("dl"), copy("organized")];
+        expected.sort();
+        assert_eq!(cleaned(&files, &["*/archive/*"], cli::KeepCopy::Oldest), expected);
+        let mut expected = vec![copy("dl/old"), copy("organized")];
+        expected.sort();
+        assert_eq!(cleaned(&files, &[], cli::KeepCopy::Newest), expected);
+    }
+    
+    #[test]
+    fn drifted_copies_are_never_the_kept_duplicate() {
+        let (_guard, _home) = isolated_home();
+        let dir = tempfile::tempdir().unwrap();
+        duplicate_copies(dir.path());
+        let copy = |folder: &str| dir.path().join(folder).join("notes.pdf");
+        let saved = dir.path().join("scan.json");
+        let result = Scanner::new(Config::defaults(), false).scan(dir.path(), 30, 100).unwrap();
+        SavedScan::save(dir.path(), &result, &saved).unwrap();
+        // The oldest copy, which would be kept, changes after the scan
+        fs::write(copy("dl/old"), "rewritten notes").unwrap();
+        
+        let mut config = Config::defaults();
+        config.default_action = CleanupAction::Archive;
+        let mut exam_manager = ExamManager::new(config.clone());
+        let cli = Cli::try_parse_from(["cleancrush", "clean", "--from", saved.to_str().unwrap(), "--mode", "duplicates", "--yes"]).unwrap();
+        let Commands::Clean(args) = cli.command else {
+            unreachable!()
+        };
+        handle_clean(&mut config, &mut exam_manager, &args, false, false, &mut Gamification::new()).unwrap();
+        
+        assert!(copy("organized").exists());
+        assert!(!copy("dl").exists());
+        assert!(copy("dl/old").exists());
+    }
+    
+    #[test]
+    fn space_goals_leave_one_copy_of_each_duplicate() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = duplicate_copies(dir.path());
+        let size = files[0].size_bytes;
+        
+        // Only two of the three copies can go
+        assert!(select_for_target(&files, 3 * size).is_none());
+        let selected = select_for_target(&files, 2 * size).unwrap();
+        assert_eq!(selected.len(), 2);
+        assert!(!selected.contains(&dir.path().join("dl/old").join("notes.pdf")));
+    }
+    
+    #[test]
+    fn interactive_defaults_leave_the_kept_copy_unchecked() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = duplicate_copies(dir.path());
+        // Real-sized copies all score as exact duplicates
+        for file in &mut files {
+            file.confidence = 0.99;
+        }
+        
+        let defaults = preselected(&files, 0.8);
+        let checked: Vec<&Path> = files.iter()
+            .zip(&defaults)
+            .filter(|(_, &checked)| checked)
+            .map(|(f, _)| f.path.as_path())
+            .collect();
+        assert_eq!(checked.len(), 2);
+        assert!(!checked.contains(&dir.path().join("dl/old").join("notes.pdf").as_path()));
+    }
+    
+    #[test]
+    fn duplicate_sweeps_keep_one_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = duplicate_copies(dir.path());
+        
+        let swept: Vec<&Path> = sweep(&files, FileCategory::Duplicate).iter()
+            .map(|f| f.path.as_path())
+            .collect();
+        assert_eq!(swept.len(), 2);
+        assert!(!swept.contains(&dir.path().join("dl/old").join("notes.pdf").as_path()));
+        assert!(sweep(&files, FileCategory::Old).is_empty());
+    }
+    
+    #[test]
+    fn streamed_cleanups_untrack_exam_files() {
+        let (_guard, _home) = isolated_home();
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("lecture_week3.pdf");
+        fs::write(&notes, vec![b'a'; 16 * 1024]).unwrap();
+        fs::File::options().write(true).open(&notes).unwrap()
+            .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(120 * 86_400)).unwrap();
+        
+        let mut config = Config::defaults();
+        config.default_action = CleanupAction::Archive;
+        let mut exam_manager = ExamManager::new(config.clone());
+        exam_manager.start_manual(None).unwrap();
+        exam_manager.track_file_if_active(notes.clone(), 16 * 1024, "pdf".to_string(), "general".to_string(), exam::FileCategory::Lecture);
+        config.exam_tracking = exam_manager.tracking_state();
+        
+        let cli = Cli::try_parse_from(["cleancrush", "scan", "--stream", "--stream-clean", "--yes"]).unwrap();
+        let Commands::Scan(args) = cli.command else {
+            unreachable!()
+        };
+        let scanner = Scanner::new(config.clone(), true);
+        stream_scan(&mut config, &mut exam_manager, &scanner, &args, dir.path(), false, &mut Gamification::new()).unwrap();
+        
+        // --yes cleans tracked files, like `clean --yes`, and stops tracking them
+        assert!(!notes.exists());
+        assert!(!exam_manager.is_tracked(&notes));
+        assert!(config.exam_tracking.unwrap().tracked_files.is_empty());
+    }
+    
+    #[test]
+    fn broad_paths_need_the_override() {
+        let (_guard, home) = isolated_home();
+        let downloads = home.join("Downloads");
+        fs::create_dir_all(&downloads).unwrap();
+        
+        // The shell expands `~`; unexpanded it's no narrower
+        for path in [home.as_path(), Path::new("~"), Path::new("/"), Path::new(r"C:\")] {
+            assert!(refuse_broad_path(path, false).is_err(), "{}", path.display());
+            assert!(refuse_broad_path(path, true).is_ok());
+        }
+        assert!(refuse_broad_path(&downloads, false).is_ok());
+    }
+    
+    #[test]
+    fn download_sweeps_refuse_the_home_folder() {
+        let (_guard, home) = isolated_home();
+        let mut config = Config::defaults();
+        let exam_manager = ExamManager::new(config.clone());
+        let mut gamification = Gamification::new();
+        
+        let args = cli::DedupeDownloadsArgs {
+            path: Some(home.clone()),
+            dry_run: true,
+            yes: true,
+            i_know_what_im_doing: false,
+        };
+        let error = handle_dedupe_downloads(&mut config, &exam_manager, &args, false, &mut gamification).unwrap_err();
+        assert!(error.to_string().contains("--i-know-what-im-doing"), "{}", error);
+        
+        let args = cli::SweepInstallersArgs {
+            path: Some(home.clone()),
+            days: 30,
+            dry_run: true,
+            yes: true,
+            i_know_what_im_doing: false,
+        };
+        let error = handle_sweep_installers(&mut config, &exam_manager, &args, false, &mut gamification).unwrap_err();
+        assert!(error.to_string().contains("--i-know-what-im-doing"), "{}", error);
+    }
+    
+    #[test]
+    fn scan_json_includes_the_confidence_histogram() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+        let result = Scanner::new(Config::defaults(), false).scan(dir.path(), 30, 100).unwrap();
+        let out = dir.path().join("reports").join("scan.json");
+        
+        print_scan_json(&result, Some(&out)).unwrap();
+        
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+        let histogram = &json["confidence_histogram"];
+        assert_eq!(histogram["very_high"], 1);
+        assert_eq!(histogram["filtered_out"], result.filtered_low_confidence);
+        assert_eq!(json["files"].as_array().unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn scheduled_cleanup_never_sweeps_the_home_folder() {
+        let (_guard, home) = isolated_home();
+        let mut config = Config::defaults();
+        config.default_scan_paths = vec![home.clone()];
+        let exam_manager = ExamManager::new(config.clone());
+        
+        let error = run_scheduled_cleanup(&mut config, &exam_manager, false).unwrap_err();
+        assert!(error.to_string().contains("your home folder"), "{}", error);
+    }
+    
+    #[test]
+    fn date_windows_use_local_days() {
+        use chrono::TimeZone;
+        
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 10, 14).unwrap();
+        // Both ends of the local day, whatever they are in UTC
+        for (h, m) in [(0, 5), (23, 55)] {
+            let modified = chrono::Local.from_local_datetime(&day.and_hms_opt(h, m, 0).unwrap())
+                .unwrap()
+                .with_timezone(&Utc);
+            assert!(in_date_window(modified, Some(day), Some(day)));
+            assert!(!in_date_window(modified, day.succ_opt(), None));
+            assert!(!in_date_window(modified, None, day.pred_opt()));
+        }
+    }
+    
+    #[test]
+    fn non_interactive_init_writes_a_valid_config() {
+        let (_guard, _home) = isolated_home();
+        let _ = fs::remove_file(Config::config_path().unwrap());
+        
+        init(false).unwrap();
+        
+        let config = Config::load().unwrap();
+        assert!(matches!(config.default_action, CleanupAction::RecycleBin));
+        assert!(matches!(config.reminder_schedule, ReminderSchedule::Weekly));
+        assert!(config.protected_folders.is_empty());
+        assert!(config.enable_exam_monitoring);
+        
+        // Without --force an existing config is left alone
+        assert!(init(false).is_err());
+    }
+    
+    #[test]
+    fn forced_init_keeps_progress() {
+        let (_guard, _home) = isolated_home();
+        let mut config = Config::defaults();
+        config.default_action = CleanupAction::Archive;
+        config.streaks = 4;
+        config.total_files_cleaned = 120;
+        config.save().unwrap();
+        
+        init(true).unwrap();
+        
+        let config = Config::load().unwrap();
+        assert!(matches!(config.default_action, CleanupAction::RecycleBin));
+        assert_eq!(config.streaks, 4);
+        assert_eq!(config.total_files_cleaned, 120);
+    }
+}
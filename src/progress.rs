@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use indicatif::{ProgressBar, ProgressStyle};
+use crate::cli::ProgressMode;
+
+/// Bar width limits; the rest of the line is spinner, counts and message
+const MAX_BAR_WIDTH: usize = 40;
+const MIN_BAR_WIDTH: usize = 10;
+const LINE_RESERVE: usize = 45;
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Set the progress style for the rest of the run (from `--progress`)
+pub fn set_mode(mode: ProgressMode) {
+    let value = match mode {
+        ProgressMode::Bar => 0,
+        ProgressMode::Spinner => 1,
+        ProgressMode::None => 2,
+    };
+    MODE.store(value, Ordering::Relaxed);
+}
+
+/// Current progress style
+pub fn mode() -> ProgressMode {
+    match MODE.load(Ordering::Relaxed) {
+        1 => ProgressMode::Spinner,
+        2 => ProgressMode::None,
+        _ => ProgressMode::Bar,
+    }
+}
+
+/// Turn on `--quiet`: no progress, encouragement or decorative banners
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+    if quiet {
+        set_mode(ProgressMode::None);
+    }
+}
+
+/// Whether only results and errors should be printed
+pub fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Bar width that fits the current terminal
+fn bar_width() -> usize {
+    match terminal_size::terminal_size() {
+        Some((terminal_size::Width(columns), _)) => {
+            (columns as usize).saturating_sub(LINE_RESERVE).clamp(MIN_BAR_WIDTH, MAX_BAR_WIDTH)
+        }
+        None => MAX_BAR_WIDTH,
+    }
+}
+
+/// Create a progress indicator over `len` files.
+/// `suffix` is appended after the file count, e.g. `"{msg}"` or `"({eta})"`.
+pub fn file_bar(len: u64, suffix: &str) -> ProgressBar {
+    match mode() {
+        ProgressMode::None => ProgressBar::hidden(),
+        ProgressMode::Spinner => {
+            let pb = ProgressBar::new(len);
+            if let Ok(style) = ProgressStyle::default_spinner()
+                .template(&format!("{{spinner:.green}} {{pos}}/{{len}} files {}", suffix))
+            {
+                pb.set_style(style);
+            }
+            pb
+        }
+        ProgressMode::Bar => {
+            let pb = ProgressBar::new(len);
+            let template = format!(
+                "{{spinner:.green}} [{{bar:{}.cyan/blue}}] {{pos}}/{{len}} files {}",
+                bar_width(),
+                suffix
+            );
+            if let Ok(style) = ProgressStyle::default_bar().template(&template) {
+                pb.set_style(style.progress_chars("#>-"));
+            }
+            pb
+        }
+    }
+}
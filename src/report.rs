@@ -0,0 +1,94 @@
+use std::path::Path;
+use chrono::Local;
+use crate::cli::format_size;
+use crate::scanner::ScanResult;
+use crate::{FileCategory, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB};
+
+/// How many suggestions the report lists
+pub const REPORT_TOP_SUGGESTIONS: usize = 20;
+
+/// Render a scan as a Markdown report
+pub fn render_markdown(root: &Path, result: &ScanResult, score: u32, breakdown: &str) -> String {
+    let mut lines = Vec::new();
+
+    lines.push("# 🧹 CleanCrush Scan Report".to_string());
+    lines.push(String::new());
+    lines.push(format!("- **Folder:** `{}`", root.display()));
+    lines.push(format!("- **Generated:** {}", Local::now().format("%Y-%m-%d %H:%M")));
+    lines.push(String::new());
+
+    lines.push(format!("## 🏆 Cleanliness Score: {}/100", score));
+    lines.push(String::new());
+    for line in breakdown.lines() {
+        lines.push(format!("- {}", line));
+    }
+    lines.push(String::new());
+
+    lines.push("## 📊 Summary".to_string());
+    lines.push(String::new());
+    lines.push("| | |".to_string());
+    lines.push("|---|---|".to_string());
+    lines.push(format!("| Files scanned | {} |", result.total_files_scanned));
+    lines.push(format!("| Total size | {} |", format_size(result.total_size_bytes)));
+    lines.push(format!("| Cleanup suggestions | {} |", result.total_suggestions()));
+    lines.push(format!("| Scan time | {} s |", result.scan_duration.num_seconds()));
+    lines.push(String::new());
+
+    if result.hit_scan_limit {
+        lines.push(format!("> ⚠️ **Results are incomplete** - the scan stopped after {} files.", result.scan_limit));
+        lines.push(String::new());
+    }
+
+    lines.push("## 🎯 Findings".to_string());
+    lines.push(String::new());
+    lines.push("| Category | Files | Size |".to_string());
+    lines.push("|---|---:|---:|".to_string());
+    let categories = [
+        (FileCategory::Duplicate, "🔄 Duplicates".to_string()),
+        (FileCategory::Old, format!("📅 Old (>{} days)", DEFAULT_OLD_DAYS)),
+        (FileCategory::Large, format!("💪 Large (>{} MB)", DEFAULT_LARGE_MB)),
+        (FileCategory::Empty, "🫙 Empty (0 bytes)".to_string()),
+        (FileCategory::Lecture, "📚 Lectures".to_string()),
+        (FileCategory::Assignment, "📝 Assignments".to_string()),
+        (FileCategory::Reference, "📖 References".to_string()),
+        (FileCategory::Other, "🎫 Other".to_string()),
+    ];
+    for (category, label) in categories {
+        let files = result.files_by_category(category);
+        if files.is_empty() {
+            continue;
+        }
+        let size: u64 = files.iter().map(|f| f.size_bytes).sum();
+        lines.push(format!("| {} | {} | {} |", label, files.len(), format_size(size)));
+    }
+    if result.cloud_files_found > 0 {
+        lines.push(format!("| ☁️ In cloud folders | {} | |", result.cloud_files_found));
+    }
+    lines.push(String::new());
+
+    lines.push(format!("## ✨ Top {} Suggestions", REPORT_TOP_SUGGESTIONS));
+    lines.push(String::new());
+    if result.files.is_empty() {
+        lines.push("Nothing to clean up ✨".to_string());
+    } else {
+        lines.push("| # | File | Size | Confidence | Reason |".to_string());
+        lines.push("|---:|---|---:|---:|---|".to_string());
+        for (i, file) in result.files.iter().take(REPORT_TOP_SUGGESTIONS).enumerate() {
+            let shown = file.path.strip_prefix(root).unwrap_or(&file.path);
+            lines.push(format!("| {} | `{}` | {} | {:.2} | {} |",
+                i + 1,
+                escape_cell(&shown.display().to_string()),
+                format_size(file.size_bytes),
+                file.confidence,
+                escape_cell(&file.reason)));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Keep table cells intact when names contain pipes or newlines
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
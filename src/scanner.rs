@@ -4,12 +4,15 @@ use std::time::SystemTime;
 use chrono::{DateTime, Utc, Duration};
 use walkdir::WalkDir;
 use blake3;
-use regex::Regex;
+use rayon::prelude::*;
 use colored::*;
-use indicatif::{ProgressBar, ProgressStyle};
 use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
 use crate::colors;
 use crate::{FileCategory, DEFAULT_OLD_DAYS, DEFAULT_LARGE_MB};
+use crate::progress;
+use crate::archive;
+use crate::dev_cache;
 use crate::config::{Config, ProtectedFolder, ProtectionType};
 
 const STUDY_EXTENSIONS: &[&str] = &[
@@ -27,6 +30,9 @@ const STUDY_PATTERNS: &[&str] = &[
     "exam", "quiz", "week", "chapter", "slide", "tutorial",
     "worksheet", "solution", "practice", "review",
 ];
+/// Reason given when no specific signal applied
+const GENERIC_REASON: &str = "General study file";
+
 const DUPLICATE_PATTERNS: &[&str] = &[
     "copy", "(1)", "(2)", "_copy", "-copy",
     "final_final", "old", "backup", "version",
@@ -34,18 +40,98 @@ const DUPLICATE_PATTERNS: &[&str] = &[
 const CLOUD_FOLDERS: &[&str] = &[
     "Google Drive", "Dropbox", "OneDrive", "iCloud Drive", "Box", "Sync",
 ];
-const COURSE_PATTERNS: &[(&str, &[&str])] = &[
-    ("math", &["math", "calculus", "algebra", "geometry"]),
-    ("cs", &["cs", "computer science", "programming", "data structures"]),
-    ("physics", &["physics", "mechanics", "quantum"]),
-    ("chemistry", &["chemistry", "organic", "inorganic"]),
-    ("biology", &["biology", "genetics", "ecology"]),
-    ("history", &["history", "world history", "us history"]),
-    ("literature", &["literature", "english", "novel"]),
-];
+/// Files collected per scan before stopping early. Anything past the cap is
+/// never seen, so duplicates of unseen files can't be detected either.
 const MAX_FILES_TO_SCAN: usize = 5000;
+/// Cap used by `--pretend-full` to exercise the early-stop path
+const PRETEND_FULL_LIMIT: usize = 10;
+/// Folder levels walked below the scan root unless `--depth` says otherwise
+const DEFAULT_SCAN_DEPTH: usize = 3;
+
+/// Bytes read from each end of a large file before deciding to hash it whole
+const SAMPLE_HASH_BYTES: u64 = 64 * 1024;
+
+/// Files below this confidence are dropped outside exam mode
+const CONFIDENCE_FLOOR: f32 = 0.4;
+
+/// Highest confidence a file under `tiny_file_kb` can get: cleaning it
+/// frees next to nothing, so it shouldn't outrank big wins
+pub const TINY_FILE_CONFIDENCE: f32 = 0.5;
+
+/// Files analyzed by a quick scan; bigger folders are sampled evenly
+pub const QUICK_SAMPLE_SIZE: usize = 1000;
+
+/// Confidence for empty files: removing one can't lose anything
+const EMPTY_FILE_CONFIDENCE: f32 = 0.95;
+
+/// Files that are empty on purpose and break things when removed
+const EMPTY_MARKER_FILES: &[&str] = &["__init__.py", "py.typed", ".gitkeep", ".keep", ".nojekyll"];
+/// Source files can be empty placeholders in a project, so they're never sure wins
+const CODE_EXTENSIONS: &[&str] = &["py", "java", "c", "cpp", "rs", "js", "html"];
+
+/// Signal for a file named like another once copy markers are stripped
+const NAME_DUPLICATE_CONFIDENCE: f32 = 0.8;
+/// Name-only matches never reach the confidence of exact copies
+const NAME_DUPLICATE_MAX_CONFIDENCE: f32 = 0.9;
+
+/// Candidate file: path, size, modified, created
+type Candidate = (PathBuf, u64, DateTime<Utc>, DateTime<Utc>);
+
+/// How a file matched another as a duplicate
+#[derive(Debug, Clone, Copy)]
+enum DuplicateMatch<'a> {
+    None,
+    /// Same contents (or same size and name in a quick scan)
+    Content,
+    /// Same name once copy markers are stripped, different contents;
+    /// holds the copy being kept
+    Name(&'a Path),
+    /// The copy a name-only group keeps; never suggested for being a copy
+    Kept,
+}
+
+/// Device and inode of a file with more than one hard link, so every link
+/// can be counted once
+#[cfg(unix)]
+fn hardlink_identity(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.nlink() > 1).then(|| (metadata.dev(), metadata.ino()))
+}
 
-#[derive(Debug, Clone)]
+/// Hard links aren't detected outside Unix; each link is its own file
+#[cfg(not(unix))]
+fn hardlink_identity(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Lowercase file name with copy markers removed, so "notes (1).pdf" and
+/// "Notes - Copy.pdf" both become "notes.pdf"
+fn base_name(path: &Path) -> String {
+    let extension = path.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+    let mut stem = path.file_stem().unwrap_or_default().to_string_lossy().to_lowercase();
+    
+    // Markers can stack, e.g. "notes (1) - copy"
+    loop {
+        let before = stem.len();
+        if let Some(shorter) = [" - copy", " copy", "_copy", "-copy"].iter()
+            .find_map(|marker| stem.strip_suffix(marker))
+        {
+            stem = shorter.trim_end().to_string();
+        }
+        if let Some(counter) = stem.strip_suffix(')').and_then(|s| s.rfind('(')) {
+            let digits = &stem[counter + 1..stem.len() - 1];
+            if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+                stem = stem[..counter].trim_end().to_string();
+            }
+        }
+        if stem.len() == before {
+            break;
+        }
+    }
+    format!("{}.{}", stem, extension)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileInfo {
     pub path: PathBuf,
     pub size_bytes: u64,
@@ -53,6 +139,8 @@ pub struct FileInfo {
     pub created: DateTime<Utc>,
     pub days_old: i64,
     pub course: String,
+    /// Course came from a user override rather than detection
+    pub course_overridden: bool,
     pub file_type: String,
     pub hash: Option<String>,
     pub confidence: f32,
@@ -60,9 +148,15 @@ pub struct FileInfo {
     pub category: FileCategory,
     pub is_in_cloud: bool,
     pub is_locked: bool,
+    /// Where the file points if it's a symlink; removing it frees nothing
+    #[serde(default)]
+    pub symlink_target: Option<PathBuf>,
+    /// For a name-only duplicate, the copy the scan keeps in its place
+    #[serde(default)]
+    pub duplicate_of: Option<PathBuf>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ScanResult {
     pub files: Vec<FileInfo>,
     pub total_files_scanned: usize,
@@ -71,35 +165,217 @@ pub struct ScanResult {
     pub old_files_found: usize,
     pub large_files_found: usize,
     pub cloud_files_found: usize,
+    /// Not saved with `scan --save`; zero for loaded scans
+    #[serde(skip)]
     pub scan_duration: Duration,
+    /// The walk stopped at the file cap, so results are partial
+    pub hit_scan_limit: bool,
+    /// The file cap that was in effect
+    pub scan_limit: usize,
+    /// Files dropped for scoring below the confidence floor
+    pub filtered_low_confidence: usize,
+    /// Files analyzed when a quick scan only looked at a sample
+    #[serde(default)]
+    pub sampled_files: Option<usize>,
+}
+
+/// Suggestion counts per confidence band
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfidenceHistogram {
+    /// 0.9 and above
+    pub very_high: usize,
+    /// 0.7 up to 0.9
+    pub high: usize,
+    /// 0.4 up to 0.7
+    pub medium: usize,
+    /// Below 0.4 (only kept in exam mode)
+    pub low: usize,
+    /// Dropped for falling under the confidence floor
+    pub filtered_out: usize,
+}
+
+/// Age buckets for `scan --histogram`: label and the oldest age (in days) it holds
+const AGE_BUCKETS: [(&str, i64); 5] = [
+    ("0-7 days  ", 7),
+    ("8-30 days ", 30),
+    ("31-60 days", 60),
+    ("61-90 days", 90),
+    ("90+ days  ", i64::MAX),
+];
+
+/// Suggestions in one age bucket
+#[derive(Debug, Clone, Default)]
+pub struct AgeBucket {
+    pub label: &'static str,
+    pub count: usize,
+    pub size_bytes: u64,
+}
+
+/// Per-run scan settings chosen on the command line
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Only consider these extensions (lowercase, no leading dot)
+    pub extensions: Option<Vec<String>>,
+    /// Never consider these extensions (lowercase, no leading dot)
+    pub excluded_extensions: Vec<String>,
+    /// Skip files and folders matching these patterns (`--exclude`)
+    pub excludes: Vec<glob::Pattern>,
+    /// File cap for the walk (default: 5000)
+    pub max_files: Option<usize>,
+    /// Folder depth for the walk (default: 3, 0 for unlimited)
+    pub max_depth: Option<usize>,
+    /// Skip files smaller than this many bytes
+    pub min_size_bytes: Option<u64>,
+    /// Skip files larger than this many bytes
+    pub max_size_bytes: Option<u64>,
+    /// Behave as if the file cap was reached after a handful of files
+    pub pretend_full: bool,
+    /// Also consider .zip files and read their listing (names and sizes only)
+    pub inspect_archives: bool,
+    /// Estimate instead of analyze: no hashing (duplicates guessed from name
+    /// and size) and only a sample of big folders
+    pub quick: bool,
+    /// Keep files below the confidence floor (calibration wants every band)
+    pub keep_low_confidence: bool,
+    /// List files skipped because of `never_suggest` or `--exclude`
+    pub verbose: bool,
+}
+
+impl ScanOptions {
+    /// Restrict the scan to the given extensions, warning about unknown ones.
+    /// Fails if none of them is supported, rather than scanning nothing.
+    pub fn with_extensions(mut self, extensions: &[String]) -> Result<Self> {
+        if extensions.is_empty() {
+            return Ok(self);
+        }
+        
+        let mut known = Vec::new();
+        let mut unknown = Vec::new();
+        for ext in extensions {
+            let ext = ext.trim().trim_start_matches('.').to_lowercase();
+            if ext.is_empty() {
+                continue;
+            }
+            if EXAM_EXTENSIONS.contains(&ext.as_str()) {
+                if !known.contains(&ext) {
+                    known.push(ext);
+                }
+            } else {
+                unknown.push(ext);
+            }
+        }
+        
+        if known.is_empty() {
+            anyhow::bail!("No supported extension in --ext {} (supported: {})", 
+                extensions.join(","), EXAM_EXTENSIONS.join(", "));
+        }
+        for ext in unknown {
+            println!("{} Unknown extension '{}' ignored (supported: {})", 
+                "⚠️".yellow(), ext, EXAM_EXTENSIONS.join(", "));
+        }
+        
+        self.extensions = Some(known);
+        Ok(self)
+    }
+    
+    /// Leave the given extensions out of the scan
+    pub fn with_excluded_extensions(mut self, extensions: &[String]) -> Self {
+        for ext in extensions {
+            let ext = ext.trim().trim_start_matches('.').to_lowercase();
+            if !ext.is_empty() && !self.excluded_extensions.contains(&ext) {
+                self.excluded_extensions.push(ext);
+            }
+        }
+        self
+    }
+    
+    /// Skip files and folders matching `patterns` for this run. Bare names and
+    /// globs (`node_modules`, `*.tmp`) match any file or folder name,
+    /// case-insensitively; patterns with a separator match the whole path.
+    pub fn with_excludes(mut self, patterns: &[String]) -> Result<Self> {
+        for pattern in patterns {
+            let pattern = pattern.trim().trim_end_matches(['/', '\\']);
+            if pattern.is_empty() {
+                continue;
+            }
+            let compiled = glob::Pattern::new(pattern)
+                .with_context(|| format!("Invalid --exclude pattern '{}'", pattern))?;
+            self.excludes.push(compiled);
+        }
+        Ok(self)
+    }
+    
+    /// Whether a file or folder matches one of the `--exclude` patterns
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let options = glob::MatchOptions {
+            case_sensitive: false,
+            ..glob::MatchOptions::new()
+        };
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        
+        self.excludes.iter().any(|pattern| {
+            if pattern.as_str().contains(['/', '\\']) {
+                pattern.matches_path_with(path, options)
+            } else {
+                pattern.matches_with(&name, options)
+            }
+        })
+    }
+    
+    /// Keep only files between `min_mb` and `max_mb` (either bound optional)
+    pub fn with_size_range(mut self, min_mb: Option<f64>, max_mb: Option<f64>) -> Result<Self> {
+        if let (Some(min), Some(max)) = (min_mb, max_mb) {
+            if min > max {
+                anyhow::bail!("--min-size ({} MB) is larger than --max-size ({} MB)", min, max);
+            }
+        }
+        
+        let to_bytes = |mb: f64| (mb * 1024.0 * 1024.0) as u64;
+        self.min_size_bytes = min_mb.map(to_bytes);
+        self.max_size_bytes = max_mb.map(to_bytes);
+        Ok(self)
+    }
+    
+    /// Whether a file of `size` bytes passes the size filters
+    pub fn in_size_range(&self, size: u64) -> bool {
+        self.min_size_bytes.is_none_or(|min| size >= min)
+            && self.max_size_bytes.is_none_or(|max| size <= max)
+    }
 }
 
 pub struct Scanner {
     config: Config,
     is_exam_mode: bool,
-    course_regexes: Vec<(String, Regex)>,
+    options: ScanOptions,
 }
 
 impl Scanner {
     pub fn new(config: Config, is_exam_mode: bool) -> Self {
-        // Compile course detection regexes
-        let course_regexes = COURSE_PATTERNS
-            .iter()
-            .map(|(course, patterns): &(&str, &[&str])| {
-                let pattern = patterns.join("|");
-                let regex = Regex::new(&format!(r"(?i)({})", pattern))
-                    .expect("Invalid course regex");
-                (course.to_string(), regex)
-            })
-            .collect();
-        
         Self {
             config,
             is_exam_mode,
-            course_regexes,
+            options: ScanOptions::default(),
         }
     }
     
+    /// Apply per-run scan options
+    pub fn with_options(mut self, options: ScanOptions) -> Self {
+        self.options = options;
+        self
+    }
+    
+    /// Check `--exclude`, listing what it skipped in verbose mode
+    fn is_excluded_entry(&self, path: &Path) -> bool {
+        if !self.options.is_excluded(path) {
+            return false;
+        }
+        log::info!("Excluded for this run: {}", path.display());
+        if self.options.verbose {
+            println!("{} {} (excluded)", "🚫".cyan(), path.display());
+        }
+        true
+    }
+    
     /// Helper to demonstrate ProtectedFolder is used
     fn get_protection_info(&self, path: &Path) -> Option<&ProtectedFolder> {
         self.config.is_protected(path)
@@ -107,9 +383,26 @@ impl Scanner {
     
     /// Scan a directory for study files
     pub fn scan(&self, path: &Path, days_threshold: u64, large_threshold_mb: u64) -> Result<ScanResult> {
+        self.scan_streaming(path, days_threshold, large_threshold_mb, |_| Ok(()))
+    }
+    
+    /// Scan a directory, handing each suggestion to `on_file` as soon as it is
+    /// analyzed (in discovery order, after the duplicate pass). The result is
+    /// still sorted by confidence at the end. An error from `on_file` stops the scan.
+    pub fn scan_streaming(
+        &self,
+        path: &Path,
+        days_threshold: u64,
+        large_threshold_mb: u64,
+        mut on_file: impl FnMut(&FileInfo) -> Result<()>,
+    ) -> Result<ScanResult> {
         let start_time = Utc::now();
         
-        println!("{} {}", "🔍 Scanning:".color(colors::HEADER), path.display());
+        if !progress::quiet() {
+            println!("{} {}", "🔍 Scanning:".color(colors::HEADER), path.display());
+        }
+        log::info!("Scanning {} (old > {} days, large > {} MB, exam mode {})", 
+            path.display(), days_threshold, large_threshold_mb, self.is_exam_mode);
         
         if !path.exists() {
             return Err(anyhow::anyhow!("Path does not exist: {}", path.display()));
@@ -120,6 +413,11 @@ impl Scanner {
             return Ok(ScanResult::empty());
         }
         
+        if archive::archive_roots().iter().any(|root| path.starts_with(root)) {
+            println!("{} Skipping CleanCrush archive: {}", "📁".blue(), path.display());
+            return Ok(ScanResult::empty());
+        }
+        
         // Check if path is protected
         if let Some(protected) = self.get_protection_info(path) {
             match protected.protection_type {
@@ -134,33 +432,59 @@ impl Scanner {
         }
         
         // Collect all candidate files
-        let candidates = self.collect_candidates(path)?;
-        let candidates_clone = candidates.clone();
+        let (candidates, hit_scan_limit) = self.collect_candidates(path)?;
+        let total_files_scanned = candidates.len();
 
         if candidates.is_empty() {
-            println!("{} No study files found", "✨".green());
+            if !progress::quiet() {
+                println!("{} No study files found", "✨".green());
+            }
             return Ok(ScanResult::empty());
         }
         
-        println!("Found {} candidate files", candidates.len());
+        if !progress::quiet() {
+            println!("Found {} candidate files", candidates.len());
+        }
+        
+        // Detect duplicates (over every candidate, so a sample still sees pairs)
+        let (hash_cache, hash_groups) = if self.options.quick {
+            self.estimate_duplicates(&candidates)
+        } else {
+            self.detect_duplicates(&candidates)
+        };
+        
+        let name_duplicates = self.detect_name_duplicates(&candidates, |path| {
+            hash_cache.get(path)
+                .and_then(|hash| hash_groups.get(hash))
+                .is_some_and(|group| group.len() > 1)
+        });
+        let kept_copies: std::collections::HashSet<&PathBuf> = name_duplicates.values().collect();
+        
+        // Duplicates are known for the whole folder even when sampling
+        let all_duplicates: usize = hash_groups.values()
+            .filter(|group| group.len() > 1)
+            .map(Vec::len)
+            .sum();
         
-        // Detect duplicates
-        let (hash_cache, hash_groups) = self.detect_duplicates(&candidates);
+        let mut candidates = candidates;
+        let mut sampled_files = None;
+        if self.options.quick && candidates.len() > QUICK_SAMPLE_SIZE {
+            let step = candidates.len().div_ceil(QUICK_SAMPLE_SIZE);
+            candidates = candidates.into_iter().step_by(step).collect();
+            println!("Estimating from a sample of {} files", candidates.len());
+            sampled_files = Some(candidates.len());
+        }
         
         // Analyze each candidate
         let mut files = Vec::new();
         let mut total_size = 0;
         let mut duplicates_found = 0;
+        let mut filtered_low_confidence = 0;
         let mut old_files_found = 0;
         let mut large_files_found = 0;
         let mut cloud_files_found = 0;
         
-        let pb = ProgressBar::new(candidates.len() as u64);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} files ({eta})")?
-                .progress_chars("#>-")
-        );
+        let pb = progress::file_bar(candidates.len() as u64, "({eta})");
         
         for (path, size, modified, created) in candidates {
             pb.inc(1);
@@ -171,7 +495,7 @@ impl Scanner {
             }
             
             let days_old = (Utc::now() - modified).num_days();
-            let course = self.detect_course(&path);
+            let (course, course_overridden) = self.detect_course(&path);
             let file_type = self.get_file_type(&path);
             
             // Check for duplicates using hash_groups
@@ -181,7 +505,14 @@ impl Scanner {
                 false
             };
             
-            let category = if is_duplicate {
+            let duplicate = match name_duplicates.get(&path) {
+                _ if is_duplicate => DuplicateMatch::Content,
+                Some(kept) => DuplicateMatch::Name(kept),
+                None if kept_copies.contains(&path) => DuplicateMatch::Kept,
+                None => DuplicateMatch::None,
+            };
+            
+            let category = if matches!(duplicate, DuplicateMatch::Content | DuplicateMatch::Name(_)) {
                 FileCategory::Duplicate
             } else {
                 self.categorize_file(&path, days_old, size, large_threshold_mb)
@@ -195,13 +526,30 @@ impl Scanner {
             }
             
             // Calculate confidence and reason
-            let (confidence, reason) = self.calculate_confidence(
+            let (mut confidence, mut reason) = self.calculate_confidence(
                 &path, days_old, size, days_threshold, large_threshold_mb, 
-                &hash_groups, &category, is_duplicate
+                &hash_groups, &category, duplicate
             );
             
+            // Annotate zips with what they hold; their contents are never
+            // cleanup candidates on their own
+            if self.options.inspect_archives {
+                if let Some(listing) = ZipListing::read(&path) {
+                    let (signal, summary) = listing.signal();
+                    if let Some(signal) = signal {
+                        confidence = self.config.confidence_model.combine(&[confidence, signal]);
+                    }
+                    reason = if reason == GENERIC_REASON {
+                        summary
+                    } else {
+                        format!("{} + {}", reason, summary)
+                    };
+                }
+            }
+            
             // Skip low confidence files during normal mode
-            if !self.is_exam_mode && confidence < 0.4 {
+            if !self.is_exam_mode && !self.options.keep_low_confidence && confidence < CONFIDENCE_FLOOR {
+                filtered_low_confidence += 1;
                 continue;
             }
             
@@ -215,63 +563,109 @@ impl Scanner {
             
             total_size += size;
             
-            files.push(FileInfo {
+            let info = FileInfo {
                 path: path.clone(),
                 size_bytes: size,
                 modified,
                 created,
                 days_old,
                 course,
+                course_overridden,
                 file_type,
-                hash: hash_cache.get(&path).cloned(),
+                hash: if self.options.quick { None } else { hash_cache.get(&path).cloned() },
                 confidence,
                 reason,
                 category,
                 is_in_cloud,
                 is_locked,
-            });
+                symlink_target: fs::read_link(&path).ok(),
+                duplicate_of: match duplicate {
+                    DuplicateMatch::Name(kept) => Some(kept.to_path_buf()),
+                    _ => None,
+                },
+            };
+            pb.suspend(|| on_file(&info))?;
+            files.push(info);
         }
         
         pb.finish_and_clear();
         
+        if sampled_files.is_some() {
+            duplicates_found = all_duplicates;
+        }
+        
         // Sort by confidence (highest first)
         files.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
         
         let scan_duration = Utc::now() - start_time;
+        log::info!("Scan of {} done in {} ms: {} files scanned, {} suggestions ({} duplicates, {} old, {} large), {} below confidence floor{}", 
+            path.display(), scan_duration.num_milliseconds(), total_files_scanned, files.len(),
+            duplicates_found, old_files_found, large_files_found, filtered_low_confidence,
+            if hit_scan_limit { ", stopped at file cap" } else { "" });
         
         Ok(ScanResult {
             files,
-            total_files_scanned: candidates_clone.len(),
+            total_files_scanned,
             total_size_bytes: total_size,
             duplicates_found,
             old_files_found,
             large_files_found,
             cloud_files_found,
             scan_duration,
+            hit_scan_limit,
+            scan_limit: self.scan_limit(),
+            filtered_low_confidence,
+            sampled_files,
         })
     }
     
-    /// Collect candidate study files
-    fn collect_candidates(&self, path: &Path) -> Result<Vec<(PathBuf, u64, DateTime<Utc>, DateTime<Utc>)>> {
+    /// Effective file cap for this scan
+    fn scan_limit(&self) -> usize {
+        if self.options.pretend_full {
+            PRETEND_FULL_LIMIT
+        } else {
+            self.options.max_files.unwrap_or(MAX_FILES_TO_SCAN)
+        }
+    }
+    
+    /// Collect candidate study files, and whether the file cap cut the walk short
+    fn collect_candidates(&self, path: &Path) -> Result<(Vec<Candidate>, bool)> {
         let mut candidates = Vec::new();
         let mut file_count = 0;
+        let limit = self.scan_limit();
+        let mut hit_limit = self.options.pretend_full;
+        let archive_roots = archive::archive_roots();
+        let mut seen_hardlinks = std::collections::HashSet::new();
         
+        // Depth is limited for performance; 0 means no limit
+        let max_depth = match self.options.max_depth.unwrap_or(DEFAULT_SCAN_DEPTH) {
+            0 => usize::MAX,
+            depth => depth,
+        };
         let walker = WalkDir::new(path)
-            .max_depth(3) // Limit depth for performance
+            .max_depth(max_depth)
             .follow_links(false) // Don't follow symlinks
             .into_iter()
+            // Never descend into our own archive, even when it's inside the scan;
+            // Jupyter checkpoints and Python caches are `sweep-cache`'s job;
+            // `--exclude` matches drop whole folders before they're walked
+            .filter_entry(|e| !archive_roots.iter().any(|root| e.path().starts_with(root))
+                && !(e.file_type().is_dir() && dev_cache::is_cache_dir(e.path()))
+                && !(e.depth() > 0 && self.is_excluded_entry(e.path())))
             .filter_map(|e| e.ok());
         
         for entry in walker {
-            if file_count >= MAX_FILES_TO_SCAN {
-                println!("{} Scanned maximum {} files. Stopping early.", "⚠️".yellow(), MAX_FILES_TO_SCAN);
+            if file_count >= limit {
+                println!("{} Scanned maximum {} files. Stopping early.", "⚠️".yellow(), limit);
+                hit_limit = true;
                 break;
             }
             
             let entry_path = entry.path();
             
-            // Skip directories
-            if !entry.file_type().is_file() {
+            // Skip directories; symlinks are kept only when they point at a file
+            let is_symlink = entry.file_type().is_symlink();
+            if !entry.file_type().is_file() && !(is_symlink && entry_path.is_file()) {
                 continue;
             }
             
@@ -280,6 +674,13 @@ impl Scanner {
                 continue;
             }
             
+            // Names are only matched and shown in lossy form; the real path is
+            // kept for every file operation
+            if entry_path.to_str().is_none() {
+                println!("{} File name isn't valid UTF-8 (shown approximately): {}", 
+                    "⚠️".yellow(), entry_path.display());
+            }
+            
             // Check protection - USE ProtectedFolder
             if let Some(protected) = self.get_protection_info(entry_path) {
                 if matches!(protected.protection_type, ProtectionType::Hard) {
@@ -299,17 +700,50 @@ impl Scanner {
                 STUDY_EXTENSIONS
             };
             
-            if !extensions.contains(&extension.as_str()) {
+            let allowed = (match &self.options.extensions {
+                Some(only) => only.contains(&extension),
+                None => extensions.contains(&extension.as_str()),
+            } || (self.options.inspect_archives && extension == "zip"))
+                && !self.options.excluded_extensions.contains(&extension);
+            
+            if !allowed {
+                continue;
+            }
+            
+            // Files the user asked never to hear about
+            if let Some(entry) = self.config.never_suggest_match(entry_path) {
+                log::info!("Ignored by user: {} (never_suggest '{}')", entry_path.display(), entry);
+                if self.options.verbose {
+                    println!("{} {} (ignored by user: {})", "🙈".cyan(), entry_path.display(), entry);
+                }
                 continue;
             }
             
-            // Get file metadata
-            let metadata = match fs::metadata(entry_path) {
+            // Get file metadata (of the link itself for symlinks)
+            let metadata = match fs::symlink_metadata(entry_path) {
                 Ok(m) => m,
                 Err(_) => continue, // Skip files we can't read
             };
             
-            let size = metadata.len();
+            // Hard links share one copy of the data: the first link found
+            // stands for the file, so a linked pair is never a duplicate
+            if !is_symlink {
+                if let Some(identity) = hardlink_identity(&metadata) {
+                    if !seen_hardlinks.insert(identity) {
+                        log::info!("Skipped hard link to a file already seen: {}", entry_path.display());
+                        if self.options.verbose {
+                            println!("{} {} (hard link to a file already scanned)", "🔗".cyan(), entry_path.display());
+                        }
+                        continue;
+                    }
+                }
+            }
+            
+            // Removing a symlink frees nothing, so it never counts as space
+            let size = if is_symlink { 0 } else { metadata.len() };
+            if !self.options.in_size_range(size) {
+                continue;
+            }
             let modified: DateTime<Utc> = metadata.modified()
                 .unwrap_or_else(|_| SystemTime::now())
                 .into();
@@ -321,13 +755,13 @@ impl Scanner {
             file_count += 1;
         }
         
-        Ok(candidates)
+        Ok((candidates, hit_limit))
     }
     
     /// Detect duplicate files using hashing
     fn detect_duplicates(
         &self, 
-        candidates: &[(PathBuf, u64, DateTime<Utc>, DateTime<Utc>)]
+        candidates: &[Candidate]
     ) -> (std::collections::HashMap<PathBuf, String>, std::collections::HashMap<String, Vec<PathBuf>>) {
         let mut size_groups = std::collections::HashMap::new();
         let mut hash_cache = std::collections::HashMap::new();
@@ -338,54 +772,97 @@ impl Scanner {
             size_groups.entry(*size).or_insert_with(Vec::new).push(path.clone());
         }
         
-        // Hash only files with same size (potential duplicates)
+        // Only files sharing a size can be duplicates. Big ones get a cheap
+        // look at their first and last bytes first, so most never get read whole.
+        let mut to_hash = Vec::new();
+        let mut to_sample = Vec::new();
         for (size, paths) in size_groups {
             if size == 0 || paths.len() < 2 {
                 continue;
             }
-            
-            for path in paths {
-                if let Ok(hash) = self.hash_file(&path) {
-                    hash_cache.insert(path.clone(), hash.clone());
-                    hash_groups.entry(hash).or_insert_with(Vec::new).push(path.clone());
-                }
+            if size > 2 * SAMPLE_HASH_BYTES {
+                to_sample.extend(paths);
+            } else {
+                to_hash.extend(paths);
             }
         }
         
+        // Same size and same ends: now worth a full hash
+        let mut sample_groups: std::collections::HashMap<String, Vec<PathBuf>> = std::collections::HashMap::new();
+        for (path, sample) in hash_in_parallel(to_sample, sample_hash, "checked for duplicates") {
+            sample_groups.entry(sample).or_default().push(path);
+        }
+        to_hash.extend(sample_groups.into_values().filter(|paths| paths.len() >= 2).flatten());
+        
+        for (path, hash) in hash_in_parallel(to_hash, hash_file, "hashed for duplicates") {
+            hash_cache.insert(path.clone(), hash.clone());
+            hash_groups.entry(hash).or_insert_with(Vec::new).push(path);
+        }
+        
         (hash_cache, hash_groups)
     }
     
-    /// Hash a file using streaming (memory-safe)
-    fn hash_file(&self, path: &Path) -> Result<String> {
-        let mut hasher = blake3::Hasher::new();
-        let mut file = fs::File::open(path).context("Failed to open file for hashing")?;
+    /// Guess duplicates without reading files: same size and the same name
+    /// once copy markers like "(1)" or "_copy" are stripped. Returns the same
+    /// shape as `detect_duplicates`, keyed by size and name instead of hash.
+    fn estimate_duplicates(
+        &self, 
+        candidates: &[Candidate]
+    ) -> (std::collections::HashMap<PathBuf, String>, std::collections::HashMap<String, Vec<PathBuf>>) {
+        let mut hash_cache = std::collections::HashMap::new();
+        let mut hash_groups = std::collections::HashMap::new();
         
-        let mut buffer = [0u8; 8192]; // 8KB chunks - memory safe
-        loop {
-            let n = std::io::Read::read(&mut file, &mut buffer)?;
-            if n == 0 {
-                break;
+        for (path, size, _, _) in candidates {
+            if *size == 0 {
+                continue;
             }
-            hasher.update(&buffer[..n]);
+            let key = format!("{}:{}", size, base_name(path));
+            hash_cache.insert(path.clone(), key.clone());
+            hash_groups.entry(key).or_insert_with(Vec::new).push(path.clone());
         }
         
-        Ok(hasher.finalize().to_string())
+        (hash_cache, hash_groups)
     }
     
-    /// Detect course from filename
-    fn detect_course(&self, path: &Path) -> String {
-        let filename = path.file_name()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_lowercase();
+    /// Files named like another in the same folder once copy markers are
+    /// stripped ("report (1).pdf" next to "report.pdf") whose contents differ.
+    /// The newest in each group is kept; the others map to it.
+    fn detect_name_duplicates(
+        &self,
+        candidates: &[Candidate],
+        is_content_duplicate: impl Fn(&Path) -> bool,
+    ) -> std::collections::HashMap<PathBuf, PathBuf> {
+        let mut by_name: std::collections::HashMap<(PathBuf, String), Vec<(PathBuf, DateTime<Utc>)>> = 
+            std::collections::HashMap::new();
+        
+        for (path, size, modified, _) in candidates {
+            if *size == 0 || is_content_duplicate(path) {
+                continue;
+            }
+            let Some(parent) = path.parent() else {
+                continue;
+            };
+            by_name.entry((parent.to_path_buf(), base_name(path)))
+                .or_default()
+                .push((path.clone(), *modified));
+        }
         
-        for (course, regex) in &self.course_regexes {
-            if regex.is_match(&filename) {
-                return course.clone();
+        let mut duplicates = std::collections::HashMap::new();
+        for mut group in by_name.into_values().filter(|group| group.len() > 1) {
+            group.sort_by_key(|(path, modified)| (std::cmp::Reverse(*modified), path.clone()));
+            let (kept, _) = group.remove(0);
+            for (path, _) in group {
+                duplicates.insert(path, kept.clone());
             }
         }
         
-        "general".to_string()
+        duplicates
+    }
+    
+    /// Detect course from filename; user overrides win (second value is true).
+    /// Shared with archiving, so a file lands in the course folder it was tagged with.
+    fn detect_course(&self, path: &Path) -> (String, bool) {
+        archive::detect_course_with_source(&self.config, path)
     }
     
     /// Get file type string
@@ -406,6 +883,11 @@ impl Scanner {
     ) -> FileCategory {
         let filename = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
         
+        // Nothing in it, whatever the name says (symlinks are size 0 too)
+        if size == 0 && fs::read_link(path).is_err() && !is_empty_on_purpose(path) {
+            return FileCategory::Empty;
+        }
+        
         // Check filename patterns
         if filename.contains("lecture") || filename.contains("slide") || filename.contains("presentation") {
             return FileCategory::Lecture;
@@ -458,14 +940,20 @@ impl Scanner {
         large_threshold_mb: u64,
         hash_groups: &std::collections::HashMap<String, Vec<PathBuf>>,
         category: &FileCategory,
-        is_duplicate: bool,
+        duplicate: DuplicateMatch,
     ) -> (f32, String) {
-        let mut confidence: f32 = 0.0;
+        // Each independent reason contributes one signal; the configured
+        // model decides how they combine
+        let mut signals: Vec<f32> = Vec::new();
         let mut reasons = Vec::new();
         let filename = path.file_name().unwrap_or_default().to_string_lossy();
         
-        // Check for exact duplicates using hash_groups
-        if is_duplicate {
+        // Name-only matches, then exact duplicates using hash_groups
+        if let DuplicateMatch::Name(kept) = duplicate {
+            signals.push(NAME_DUPLICATE_CONFIDENCE);
+            reasons.push(format!("Likely duplicate (same base name as {})", 
+                kept.file_name().unwrap_or_default().to_string_lossy()));
+        } else if matches!(duplicate, DuplicateMatch::Content) {
             // ACTUALLY USE hash_groups to count duplicates
             let mut duplicate_count = 0;
             for (_, group) in hash_groups {
@@ -475,16 +963,20 @@ impl Scanner {
                 }
             }
             
-            if duplicate_count > 0 {
-                confidence = 0.99;
+            if duplicate_count > 0 && self.options.quick {
+                signals.push(0.99);
+                reasons.push(format!("Likely duplicate ({} copies by name and size)", duplicate_count));
+            } else if duplicate_count > 0 {
+                signals.push(0.99);
                 reasons.push(format!("Exact duplicate ({} copies)", duplicate_count));
             }
         }
         
-        // Check for duplicate filename patterns
-        for pattern in DUPLICATE_PATTERNS {
+        // Check for duplicate filename patterns (the kept copy of a name
+        // group stays, whatever its name says)
+        for pattern in DUPLICATE_PATTERNS.iter().filter(|_| !matches!(duplicate, DuplicateMatch::Kept)) {
             if filename.to_lowercase().contains(pattern) {
-                confidence = confidence.max(0.85);
+                signals.push(0.85);
                 reasons.push("Filename suggests duplicate".to_string());
                 break;
             }
@@ -492,11 +984,11 @@ impl Scanner {
         
         // Age-based confidence
         if days_old > 90 {
-            confidence = confidence.max(0.95);
+            signals.push(0.95);
             reasons.push(format!("Very old ({} days)", days_old));
         } else if days_old > days_threshold as i64 {
             let age_confidence = 0.7 + ((days_old - days_threshold as i64) as f32 / 30.0).min(0.25);
-            confidence = confidence.max(age_confidence);
+            signals.push(age_confidence);
             reasons.push(format!("Old ({} days)", days_old));
         }
         
@@ -505,20 +997,28 @@ impl Scanner {
         if size > large_threshold_bytes {
             let size_mb = size as f32 / (1024.0 * 1024.0);
             let size_confidence = 0.7 + (size_mb / 1000.0).min(0.25);
-            confidence = confidence.max(size_confidence);
+            signals.push(size_confidence);
             reasons.push(format!("Large file ({:.1} MB)", size_mb));
         }
         
         // Study pattern confidence
         for pattern in STUDY_PATTERNS {
             if filename.to_lowercase().contains(pattern) {
-                confidence = confidence.max(0.75);
+                signals.push(0.75);
                 reasons.push("Study-related file".to_string());
                 break;
             }
         }
         
-        // Category-based adjustments
+        // Empty files hold nothing worth keeping
+        if *category == FileCategory::Empty {
+            signals.push(EMPTY_FILE_CONFIDENCE);
+            reasons.push("Empty file (0 bytes)".to_string());
+        }
+        
+        let mut confidence = self.config.confidence_model.combine(&signals);
+        
+        // Category-based floor (restates the signals above, so it never compounds)
         match category {
             FileCategory::Lecture | FileCategory::Assignment | FileCategory::Reference => {
                 confidence = confidence.max(0.65);
@@ -533,8 +1033,14 @@ impl Scanner {
                 // Lower confidence for uncategorized
                 confidence = confidence.max(0.4);
             }
+            FileCategory::Empty => {
+                confidence = confidence.max(EMPTY_FILE_CONFIDENCE);
+            }
             FileCategory::Duplicate => {
-                // Already handled above
+                // Already handled above; contents differ, so stay below exact copies
+                if matches!(duplicate, DuplicateMatch::Name(_)) {
+                    confidence = confidence.min(NAME_DUPLICATE_MAX_CONFIDENCE);
+                }
             }
         }
         
@@ -551,12 +1057,23 @@ impl Scanner {
             }
         }
         
+        // Tiny files free almost nothing, whatever else is true of them
+        // (symlinks are size 0 but already labeled as such)
+        // (empty files hold nothing, so they're sure wins rather than small ones)
+        let tiny_limit = self.config.tiny_file_kb * 1024;
+        if size < tiny_limit && fs::read_link(path).is_err() && confidence > TINY_FILE_CONFIDENCE
+            && *category != FileCategory::Empty
+        {
+            confidence = TINY_FILE_CONFIDENCE;
+            reasons.push(format!("Tiny file ({:.1} KB, frees little)", size as f32 / 1024.0));
+        }
+        
         // Default minimum confidence
         confidence = confidence.max(0.1);
         
         // Build reason string
         let reason = if reasons.is_empty() {
-            "General study file".to_string()
+            GENERIC_REASON.to_string()
         } else {
             reasons.join(" + ")
         };
@@ -564,6 +1081,67 @@ impl Scanner {
         (confidence.min(1.0), reason)
     }
     
+    /// Show how suggestions spread across confidence bands
+    fn print_confidence_histogram(&self, histogram: &ConfidenceHistogram) {
+        const BAR_WIDTH: usize = 20;
+        
+        println!();
+        println!("{}", "📈 CONFIDENCE".bold().color(colors::HEADER));
+        
+        let mut rows = vec![
+            ("0.9+     ", histogram.very_high, colors::HIGH_CONFIDENCE),
+            ("0.7–0.9  ", histogram.high, colors::MEDIUM_CONFIDENCE),
+            ("0.4–0.7  ", histogram.medium, colors::LOW_CONFIDENCE),
+        ];
+        if histogram.low > 0 {
+            rows.push(("below 0.4", histogram.low, colors::LOW_CONFIDENCE));
+        }
+        
+        let max = rows.iter().map(|(_, count, _)| *count).max().unwrap_or(0).max(1);
+        for (label, count, color) in rows {
+            let width = (count * BAR_WIDTH).div_ceil(max);
+            println!("   {} {:<width$} {}",
+                label,
+                "█".repeat(width).color(color),
+                count,
+                width = BAR_WIDTH);
+        }
+        
+        if histogram.filtered_out > 0 {
+            println!("   {} {} file{} below {:.1} hidden", 
+                "🙈".dimmed(),
+                histogram.filtered_out.to_string().color(colors::WARNING),
+                if histogram.filtered_out == 1 { "" } else { "s" },
+                CONFIDENCE_FLOOR);
+        }
+    }
+    
+    /// Show how suggestions spread across age buckets, with the space each holds
+    pub fn print_age_histogram(&self, result: &ScanResult) {
+        const BAR_WIDTH: usize = 20;
+        
+        println!();
+        println!("{}", "📅 AGE".bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+        
+        if result.files.is_empty() {
+            println!("{} No suggestions to sort by age", "ℹ️".cyan());
+            return;
+        }
+        
+        let buckets = result.age_histogram();
+        let max = buckets.iter().map(|b| b.count).max().unwrap_or(0).max(1);
+        for bucket in buckets {
+            let width = (bucket.count * BAR_WIDTH).div_ceil(max);
+            println!("   {} {:<width$} {} ({})",
+                bucket.label,
+                "█".repeat(width).color(colors::WARNING),
+                bucket.count,
+                crate::cli::format_size(bucket.size_bytes),
+                width = BAR_WIDTH);
+        }
+    }
+    
     /// Print scan results in a nice format
     pub fn print_results(&self, result: &ScanResult, show_detailed: bool) {
         println!();
@@ -595,19 +1173,56 @@ impl Scanner {
                 result.cloud_files_found.to_string().color(colors::WARNING));
         }
         
+        if !result.files.is_empty() {
+            println!();
+            println!("{}", "🧩 COMPOSITION".bold().color(colors::HEADER));
+            let categories = [
+                (FileCategory::Duplicate, "🔄 Duplicates"),
+                (FileCategory::Old, "📅 Old"),
+                (FileCategory::Large, "💪 Large"),
+                (FileCategory::Empty, "🫙 Empty"),
+                (FileCategory::Lecture, "📚 Lectures"),
+                (FileCategory::Assignment, "📝 Assignments"),
+                (FileCategory::Reference, "📖 References"),
+                (FileCategory::Other, "🎫 Other"),
+            ];
+            for (category, label) in categories {
+                let files = result.files_by_category(category);
+                if files.is_empty() {
+                    continue;
+                }
+                let size: u64 = files.iter().map(|f| f.size_bytes).sum();
+                println!("{}: {} ({})", label, 
+                    files.len().to_string().color(colors::SUCCESS), 
+                    crate::cli::format_size(size));
+            }
+        }
+        
+        if !result.files.is_empty() || result.filtered_low_confidence > 0 {
+            self.print_confidence_histogram(&result.confidence_histogram());
+        }
+        
+        if result.hit_scan_limit {
+            println!();
+            println!("{}", "⚠️  RESULTS ARE INCOMPLETE".bold().color(colors::WARNING));
+            println!("   The scan stopped after {} files, so some files were never looked at.", 
+                result.scan_limit);
+            println!("   Duplicates of files beyond the limit could not be detected.");
+            println!("   {} Scan a smaller subfolder, or raise the cap with {}, to get complete results", "💡".cyan(), "--limit".bold());
+            match self.options.max_depth.unwrap_or(DEFAULT_SCAN_DEPTH) {
+                0 => {}
+                depth => println!("   {} Files more than {} folder levels down are never scanned; look deeper with {}",
+                    "💡".cyan(), depth, "--depth N".bold()),
+            }
+        }
+        
         if !result.files.is_empty() {
             println!();
             println!("{}", "✨ TOP SUGGESTIONS".bold().color(colors::HEADER));
             println!("{}", "─".repeat(50).color(colors::PATH));
             
             for (i, file) in result.files.iter().take(10).enumerate() {
-                let confidence_color = if file.confidence > 0.8 {
-                    colors::HIGH_CONFIDENCE
-                } else if file.confidence > 0.6 {
-                    colors::MEDIUM_CONFIDENCE
-                } else {
-                    colors::LOW_CONFIDENCE
-                };
+                let confidence_color = self.config.confidence_color(file.confidence);
                 
                 let size_mb = file.size_bytes as f32 / (1024.0 * 1024.0);
                 
@@ -616,7 +1231,7 @@ impl Scanner {
                     "⚡".color(confidence_color),
                     file.confidence,
                     "⚡".color(colors::SUCCESS),
-                    file.path.display().to_string().color(colors::PATH)
+                    file.display_path().color(colors::PATH)
                 );
                 
                 if show_detailed {
@@ -624,7 +1239,7 @@ impl Scanner {
                     // USE all FileInfo fields
                     println!("     Type: {}, Course: {}, Size: {:.1} MB", 
                         file.file_type.to_uppercase().color(colors::HEADER),
-                        file.course.color(colors::SUCCESS),
+                        file.course_label().color(colors::SUCCESS),
                         size_mb
                     );
                     println!("     Modified: {} ({} days ago), Created: {}", 
@@ -635,6 +1250,7 @@ impl Scanner {
                     println!("     Hash: {}", 
                         file.hash.as_ref().unwrap_or(&"N/A".to_string()).color(colors::PATH));
                     println!("     Reason: {}", file.reason.dimmed());
+                    println!("     Action: {}", archive::destination_label(&self.config, &file.path));
                     
                     if file.is_in_cloud {
                         println!("     {} In cloud folder", "☁️".yellow());
@@ -662,9 +1278,247 @@ impl Scanner {
     }
 }
 
+/// Package markers and source files, which projects keep empty on purpose
+fn is_empty_on_purpose(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+    let extension = path.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+    EMPTY_MARKER_FILES.contains(&name.as_str()) || CODE_EXTENSIONS.contains(&extension.as_str())
+}
+
+/// Hash `paths` on all cores, dropping files that can't be read. The
+/// progress bar is shared across threads.
+fn hash_in_parallel(
+    paths: Vec<PathBuf>,
+    hasher: fn(&Path) -> Result<String>,
+    label: &str,
+) -> Vec<(PathBuf, String)> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+    
+    let pb = progress::file_bar(paths.len() as u64, label);
+    let hashes = paths.into_par_iter()
+        .filter_map(|path| {
+            let hash = hasher(&path).ok();
+            pb.inc(1);
+            hash.map(|hash| (path, hash))
+        })
+        .collect();
+    pb.finish_and_clear();
+    hashes
+}
+
+/// Hash only the first and last `SAMPLE_HASH_BYTES` of a file (plus its
+/// size). Different samples mean different files; equal samples still need
+/// `hash_file` to be sure.
+pub fn sample_hash(path: &Path) -> Result<String> {
+    use std::io::{Read, Seek, SeekFrom};
+    
+    let mut file = fs::File::open(path).context("Failed to open file for hashing")?;
+    let size = file.metadata()?.len();
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&size.to_le_bytes());
+    
+    let mut buffer = vec![0u8; SAMPLE_HASH_BYTES as usize];
+    file.read_exact(&mut buffer)?;
+    hasher.update(&buffer);
+    file.seek(SeekFrom::End(-(SAMPLE_HASH_BYTES as i64)))?;
+    file.read_exact(&mut buffer)?;
+    hasher.update(&buffer);
+    
+    Ok(hasher.finalize().to_string())
+}
+
+/// Hash a file using streaming (memory-safe)
+pub fn hash_file(path: &Path) -> Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    let mut file = fs::File::open(path).context("Failed to open file for hashing")?;
+    
+    let mut buffer = [0u8; 8192]; // 8KB chunks - memory safe
+    loop {
+        let n = std::io::Read::read(&mut file, &mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    
+    Ok(hasher.finalize().to_string())
+}
+
+/// What a zip holds, read from its central directory only (no extraction)
+#[derive(Debug, Clone, Default)]
+pub struct ZipListing {
+    pub files: usize,
+    pub total_bytes: u64,
+    /// Files with a study extension (pdf, docx, ...)
+    pub study_files: usize,
+    /// Most common extension among the files, with its count
+    pub top_extension: Option<(String, usize)>,
+}
+
+impl ZipListing {
+    /// Read a zip's listing; None if it isn't a readable zip
+    pub fn read(path: &Path) -> Option<Self> {
+        let file = fs::File::open(path).ok()?;
+        let mut archive = zip::ZipArchive::new(file).ok()?;
+        let mut listing = Self::default();
+        let mut extension_counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        
+        for i in 0..archive.len() {
+            // Raw access reads entry headers only, never decompresses
+            let entry = match archive.by_index_raw(i) {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+            if entry.is_dir() {
+                continue;
+            }
+            
+            listing.files += 1;
+            listing.total_bytes += entry.size();
+            
+            let extension = Path::new(entry.name()).extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if STUDY_EXTENSIONS.contains(&extension.as_str()) {
+                listing.study_files += 1;
+            }
+            if !extension.is_empty() {
+                *extension_counts.entry(extension).or_insert(0) += 1;
+            }
+        }
+        
+        listing.top_extension = extension_counts.into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)));
+        Some(listing)
+    }
+    
+    /// Extra confidence (mostly study files means likely used-up course
+    /// material) and a human summary for the reason
+    pub fn signal(&self) -> (Option<f32>, String) {
+        let size_mb = self.total_bytes as f64 / (1024.0 * 1024.0);
+        
+        if self.files == 0 {
+            return (None, "Empty zip".to_string());
+        }
+        
+        let described = match &self.top_extension {
+            Some((ext, count)) if *count * 2 > self.files => format!("{} {}s", count, ext.to_uppercase()),
+            _ => format!("{} files", self.files),
+        };
+        
+        if self.study_files * 2 > self.files {
+            (Some(0.8), format!("Zip contains {} totaling {:.1} MB - likely already-used course material", 
+                described, size_mb))
+        } else {
+            (None, format!("Zip contains {} totaling {:.1} MB", described, size_mb))
+        }
+    }
+}
+
+impl FileInfo {
+    /// Course name, marked when it came from an override
+    pub fn course_label(&self) -> String {
+        if self.course_overridden {
+            format!("{} (override)", self.course)
+        } else {
+            self.course.clone()
+        }
+    }
+    
+    /// Path as shown to the user, with the target for symlinks
+    pub fn display_path(&self) -> String {
+        match &self.symlink_target {
+            Some(target) => format!("{} (symlink → {})", self.path.display(), target.display()),
+            None => self.path.display().to_string(),
+        }
+    }
+    
+    /// Why this file no longer matches what the scan saw, if it doesn't
+    pub fn drift(&self) -> Option<&'static str> {
+        let metadata = match fs::symlink_metadata(&self.path) {
+            Ok(metadata) => metadata,
+            Err(_) => return Some("no longer exists"),
+        };
+        
+        // Symlinks are recorded with no size
+        let size = if metadata.file_type().is_symlink() { 0 } else { metadata.len() };
+        let modified = metadata.modified().ok().map(DateTime::<Utc>::from);
+        if size != self.size_bytes || modified != Some(self.modified) {
+            return Some("changed since the scan");
+        }
+        
+        None
+    }
+}
+
+/// The most recent `scan`/`suggest` listing, so `delete 1 3` means the
+/// files that were shown
+const LAST_SCAN_FILE: &str = ".cleancrush_lastscan.json";
+
+/// A scan written by `scan --save`, so later commands can reuse it
+/// (with stable indices) instead of re-scanning
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedScan {
+    pub root: PathBuf,
+    pub scanned_at: DateTime<Utc>,
+    pub result: ScanResult,
+}
+
+impl SavedScan {
+    /// Write a scan to a file
+    pub fn save(root: &Path, result: &ScanResult, file: &Path) -> Result<()> {
+        #[derive(Serialize)]
+        struct SavedScanRef<'a> {
+            root: &'a Path,
+            scanned_at: DateTime<Utc>,
+            result: &'a ScanResult,
+        }
+        
+        let data = serde_json::to_string_pretty(&SavedScanRef {
+            root,
+            scanned_at: Utc::now(),
+            result,
+        })?;
+        fs::write(file, data)
+            .with_context(|| format!("Failed to write scan to {}", file.display()))?;
+        Ok(())
+    }
+    
+    /// Where the most recent scan is kept
+    pub fn last_scan_path() -> Result<PathBuf> {
+        Config::state_path(LAST_SCAN_FILE)
+    }
+    
+    /// Remember a scan as the one `delete` numbers refer to. Best effort:
+    /// failing to write it only means `delete` scans again.
+    pub fn save_last(root: &Path, result: &ScanResult) {
+        let saved = Self::last_scan_path().and_then(|file| Self::save(root, result, &file));
+        if let Err(e) = saved {
+            log::warn!("Couldn't remember this scan for delete: {:#}", e);
+        }
+    }
+    
+    /// Read a scan saved with `scan --save`
+    pub fn load(file: &Path) -> Result<Self> {
+        let data = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read saved scan {}", file.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("{} is not a saved CleanCrush scan", file.display()))
+    }
+    
+    /// Files that went missing or changed since the scan, with the reason
+    pub fn drifted_files(&self) -> Vec<(PathBuf, &'static str)> {
+        self.result.files.iter()
+            .filter_map(|f| f.drift().map(|reason| (f.path.clone(), reason)))
+            .collect()
+    }
+}
+
 impl ScanResult {
     /// Create empty scan result
-    fn empty() -> Self {
+    pub fn empty() -> Self {
         Self {
             files: Vec::new(),
             total_files_scanned: 0,
@@ -674,6 +1528,10 @@ impl ScanResult {
             large_files_found: 0,
             cloud_files_found: 0,
             scan_duration: Duration::zero(),
+            hit_scan_limit: false,
+            scan_limit: MAX_FILES_TO_SCAN,
+            filtered_low_confidence: 0,
+            sampled_files: None,
         }
     }
     
@@ -688,4 +1546,276 @@ impl ScanResult {
     pub fn total_suggestions(&self) -> usize {
         self.files.len()
     }
-}
\ No newline at end of file
+    
+    /// Counts used for the cleanliness score:
+    /// (duplicates, old files, large files, very large files over
+    /// `very_large_bytes`)
+    pub fn cleanliness_counts(&self, very_large_bytes: u64) -> (usize, usize, usize, usize) {
+        let mut counts = (0, 0, 0, 0);
+        
+        for file in &self.files {
+            match file.category {
+                FileCategory::Duplicate => counts.0 += 1,
+                FileCategory::Old => counts.1 += 1,
+                FileCategory::Large if file.size_bytes > very_large_bytes => counts.3 += 1,
+                FileCategory::Large => counts.2 += 1,
+                _ => {}
+            }
+        }
+        
+        counts
+    }
+    
+    /// `cleanliness_counts` scaled up to the whole folder when only a sample
+    /// was analyzed (duplicates are counted over every file, so never scaled)
+    pub fn estimated_cleanliness_counts(&self, very_large_bytes: u64) -> (usize, usize, usize, usize) {
+        let counts = self.cleanliness_counts(very_large_bytes);
+        let scale = match self.sampled_files {
+            Some(sampled) if sampled > 0 => self.total_files_scanned as f64 / sampled as f64,
+            _ => return counts,
+        };
+        let scaled = |count: usize| (count as f64 * scale).round() as usize;
+        (self.duplicates_found, scaled(counts.1), scaled(counts.2), scaled(counts.3))
+    }
+    
+    /// Bucket suggestions by confidence
+    pub fn confidence_histogram(&self) -> ConfidenceHistogram {
+        let mut histogram = ConfidenceHistogram {
+            filtered_out: self.filtered_low_confidence,
+            ..ConfidenceHistogram::default()
+        };
+        
+        for file in &self.files {
+            match file.confidence {
+                c if c >= 0.9 => histogram.very_high += 1,
+                c if c >= 0.7 => histogram.high += 1,
+                c if c >= CONFIDENCE_FLOOR => histogram.medium += 1,
+                _ => histogram.low += 1,
+            }
+        }
+        
+        histogram
+    }
+    
+    /// Bucket suggestions by how many days ago they were modified
+    pub fn age_histogram(&self) -> Vec<AgeBucket> {
+        let mut buckets: Vec<AgeBucket> = AGE_BUCKETS.iter()
+            .map(|(label, _)| AgeBucket { label, ..AgeBucket::default() })
+            .collect();
+        
+        for file in &self.files {
+            let index = AGE_BUCKETS.iter()
+                .position(|(_, max_days)| file.days_old <= *max_days)
+                .unwrap_or(AGE_BUCKETS.len() - 1);
+            buckets[index].count += 1;
+            buckets[index].size_bytes += file.size_bytes;
+        }
+        
+        buckets
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfidenceModel;
+    
+    fn scan(dir: &Path) -> ScanResult {
+        Scanner::new(Config::defaults(), false)
+            .scan(dir, 30, 100)
+            .expect("scan failed")
+    }
+    
+    /// A file of `kb` kilobytes of `fill` last modified `days` ago
+    fn aged_file(path: &Path, fill: u8, kb: usize, days: u64) {
+        fs::write(path, vec![fill; kb * 1024]).unwrap();
+        fs::File::options().write(true).open(path).unwrap()
+            .set_modified(SystemTime::now() - std::time::Duration::from_secs(days * 86_400)).unwrap();
+    }
+    
+    #[test]
+    fn combined_model_compounds_several_signals() {
+        let dir = tempfile::tempdir().unwrap();
+        let both = dir.path().join("lecture_week3.pdf");
+        let one = dir.path().join("misc.pdf");
+        aged_file(&both, b'a', 16, 35);
+        aged_file(&one, b'b', 16, 35);
+        
+        let confidence_with = |model: ConfidenceModel| {
+            let mut config = Config::defaults();
+            config.confidence_model = model;
+            let result = Scanner::new(config, false).scan(dir.path(), 30, 100).unwrap();
+            let of = |path: &Path| result.files.iter().find(|f| f.path == path).unwrap().confidence;
+            (of(&both), of(&one))
+        };
+        let (max_both, max_one) = confidence_with(ConfidenceModel::Max);
+        let (combined_both, combined_one) = confidence_with(ConfidenceModel::Combined);
+        
+        // Old and study-related: the strongest reason alone, or both together
+        assert!(combined_both > max_both, "{} vs {}", combined_both, max_both);
+        assert!(combined_both > 0.95);
+        // A single reason scores the same either way
+        assert_eq!(combined_one, max_one);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_names_keep_their_real_path() {
+        use std::os::unix::ffi::OsStrExt;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(std::ffi::OsStr::from_bytes(b"r\xe9sum\xe9 (copy).pdf"));
+        aged_file(&file, b'x', 16, 200);
+        
+        let result = scan(dir.path());
+        
+        let found = result.files.iter().find(|f| f.path == file).unwrap();
+        assert!(found.path.exists());
+        assert!(found.path.to_str().is_none());
+    }
+    
+    #[test]
+    fn archived_files_are_never_candidates() {
+        let (_guard, home) = crate::test_support::isolated_home();
+        let archived = home.join("CleanCrush-Archive").join("2024-01-15").join("math");
+        let downloads = home.join("Downloads");
+        fs::create_dir_all(&archived).unwrap();
+        fs::create_dir_all(&downloads).unwrap();
+        aged_file(&archived.join("calc_notes.pdf"), b'c', 16, 200);
+        aged_file(&downloads.join("calc_notes.pdf"), b'c', 16, 200);
+        
+        let result = scan(&home);
+        
+        let roots = archive::archive_roots();
+        assert!(!result.files.iter().any(|f| roots.iter().any(|root| f.path.starts_with(root))));
+        // The archived copy doesn't make the one in Downloads a duplicate
+        let kept = result.files.iter().find(|f| f.path.starts_with(&downloads)).unwrap();
+        assert_ne!(kept.category, FileCategory::Duplicate);
+        
+        assert!(scan(&archived).files.is_empty());
+    }
+    
+    #[test]
+    fn tiny_old_files_rank_below_large_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let tiny = dir.path().join("todo.txt");
+        aged_file(&tiny, b't', 2, 200);
+        for name in ["slides.pdf", "slides copy.pdf"] {
+            aged_file(&dir.path().join(name), b'r', 2048, 1);
+        }
+        
+        let result = scan(dir.path());
+        
+        let position = |name: &str| result.files.iter().position(|f| f.path.ends_with(name)).unwrap();
+        let tiny_file = &result.files[position("todo.txt")];
+        assert_eq!(tiny_file.confidence, TINY_FILE_CONFIDENCE);
+        assert!(tiny_file.reason.contains("Tiny file"));
+        assert!(position("slides.pdf") < position("todo.txt"));
+        assert!(position("slides copy.pdf") < position("todo.txt"));
+    }
+    
+    #[test]
+    fn deeper_depths_reach_nested_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("term1").join("math").join("week3").join("extra");
+        fs::create_dir_all(&nested).unwrap();
+        let deep = nested.join("worksheet.pdf");
+        aged_file(&deep, b'w', 16, 200);
+        aged_file(&dir.path().join("syllabus.pdf"), b's', 16, 200);
+        
+        let found = |depth: Option<usize>| {
+            let options = ScanOptions { max_depth: depth, ..ScanOptions::default() };
+            let (candidates, _) = Scanner::new(Config::defaults(), false)
+                .with_options(options)
+                .collect_candidates(dir.path())
+                .unwrap();
+            candidates.into_iter().map(|(path, ..)| path).collect::<Vec<_>>()
+        };
+        
+        let shallow = found(None);
+        assert!(shallow.iter().any(|p| p.ends_with("syllabus.pdf")));
+        assert!(!shallow.contains(&deep));
+        assert!(found(Some(5)).contains(&deep));
+        assert!(found(Some(0)).contains(&deep));
+    }
+    
+    #[test]
+    fn hard_protection_patterns_are_never_scanned() {
+        let dir = tempfile::tempdir().unwrap();
+        let private = dir.path().join("term2").join("Personal");
+        fs::create_dir_all(&private).unwrap();
+        aged_file(&private.join("transcript.pdf"), b'p', 16, 200);
+        aged_file(&dir.path().join("syllabus.pdf"), b's', 16, 200);
+        
+        let mut config = Config::defaults();
+        config.protected_folders.push(ProtectedFolder {
+            path: PathBuf::from("personal"),
+            protection_type: ProtectionType::Hard,
+            glob: true,
+        });
+        let result = Scanner::new(config, false).scan(dir.path(), 30, 100).unwrap();
+        
+        assert!(result.files.iter().any(|f| f.path.ends_with("syllabus.pdf")));
+        assert!(!result.files.iter().any(|f| f.path.starts_with(&private)));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn hard_links_are_one_file_not_two_copies() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("thesis.pdf");
+        aged_file(&original, b'h', 64, 200);
+        fs::hard_link(&original, dir.path().join("thesis-link.pdf")).unwrap();
+        
+        let result = scan(dir.path());
+        
+        assert_eq!(result.files.len(), 1);
+        assert_ne!(result.files[0].category, FileCategory::Duplicate);
+        assert_eq!(result.total_size_bytes, 64 * 1024);
+    }
+    
+    #[test]
+    fn extension_filters_need_one_supported_extension() {
+        let ext = |list: &[&str]| ScanOptions::default()
+            .with_extensions(&list.iter().map(|e| e.to_string()).collect::<Vec<_>>());
+        
+        assert!(ext(&["exe", " .zzz", ""]).is_err());
+        assert_eq!(ext(&[".PDF", "exe", "pdf"]).unwrap().extensions, Some(vec!["pdf".to_string()]));
+        assert_eq!(ext(&[]).unwrap().extensions, None);
+    }
+    
+    #[test]
+    fn empty_package_markers_are_not_sure_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("proj").join("pkg");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("__init__.py"), "").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+        
+        let result = scan(dir.path());
+        
+        let marker = result.files.iter().find(|f| f.path.ends_with("__init__.py"));
+        assert!(marker.is_none_or(|f| f.category != FileCategory::Empty && f.confidence < 0.8));
+        let notes = result.files.iter().find(|f| f.path.ends_with("notes.txt")).unwrap();
+        assert_eq!(notes.category, FileCategory::Empty);
+    }
+    
+    #[test]
+    fn name_duplicates_point_at_the_kept_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("report.pdf");
+        let kept = dir.path().join("report (1).pdf");
+        fs::write(&old, "first draft").unwrap();
+        fs::File::options().write(true).open(&old).unwrap()
+            .set_modified(SystemTime::now() - std::time::Duration::from_secs(86_400)).unwrap();
+        fs::write(&kept, "final version").unwrap();
+        
+        let result = scan(dir.path());
+        
+        let copy = result.files.iter().find(|f| f.path == old).unwrap();
+        assert_eq!(copy.category, FileCategory::Duplicate);
+        assert_eq!(copy.duplicate_of.as_deref(), Some(kept.as_path()));
+        assert!(result.files.iter()
+            .find(|f| f.path == kept)
+            .is_none_or(|f| f.category != FileCategory::Duplicate && f.confidence < 0.8));
+    }
+}
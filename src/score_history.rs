@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use colored::*;
+use anyhow::{Result, Context};
+use crate::colors;
+use crate::config::Config;
+
+const SCORE_HISTORY_FILE: &str = ".cleancrush_scores.json";
+
+/// Scores remembered per folder
+const SCORES_PER_FOLDER: usize = 30;
+
+/// Runs shown by `score --trend`
+pub const TREND_RUNS: usize = 10;
+
+/// One `cleancrush score` run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub scored_at: DateTime<Utc>,
+    pub path: PathBuf,
+    pub score: u32,
+    /// From `--quick`, so only an estimate
+    #[serde(default)]
+    pub estimate: bool,
+    /// Penalty breakdown from `calculate_cleanliness_score`
+    #[serde(default)]
+    pub breakdown: String,
+}
+
+/// Scores from every run, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreHistory {
+    pub scores: Vec<ScoreEntry>,
+}
+
+impl ScoreHistory {
+    /// Get the path to the history file
+    pub fn path() -> Result<PathBuf> {
+        Config::state_path(SCORE_HISTORY_FILE)
+    }
+
+    /// Load the history, or start an empty one
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Save the history (written to a temp file first so it's never half-written)
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let tmp_path = path.with_extension("json.tmp");
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(&tmp_path, data)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Add a score that was just calculated, dropping the folder's oldest
+    /// runs past the limit
+    pub fn record(path: &Path, score: u32, estimate: bool, breakdown: &str) -> Result<()> {
+        let mut history = Self::load()?;
+        history.scores.push(ScoreEntry {
+            scored_at: Utc::now(),
+            path: path.to_path_buf(),
+            score,
+            estimate,
+            breakdown: breakdown.to_string(),
+        });
+
+        let excess = history.for_folder(path).len().saturating_sub(SCORES_PER_FOLDER);
+        let mut dropped = 0;
+        history.scores.retain(|entry| {
+            let drop = dropped < excess && entry.path == path;
+            if drop {
+                dropped += 1;
+            }
+            !drop
+        });
+        history.save()
+    }
+
+    /// Runs for one folder, oldest first
+    pub fn for_folder(&self, path: &Path) -> Vec<&ScoreEntry> {
+        self.scores.iter().filter(|entry| entry.path == path).collect()
+    }
+}
+
+/// Print the last runs for a folder with the change from run to run
+pub fn print_trend(path: &Path, entries: &[&ScoreEntry]) {
+    println!();
+    println!("{}", "📈 SCORE TREND".bold().color(colors::HEADER));
+    println!("{}", "─".repeat(50).color(colors::PATH));
+    println!("{}", path.display().to_string().color(colors::PATH));
+
+    if entries.is_empty() {
+        println!("{} No scores yet - run {} to record one",
+            "ℹ️".cyan(),
+            format!("cleancrush score {}", path.display()).bold());
+        return;
+    }
+
+    let recent = &entries[entries.len().saturating_sub(TREND_RUNS)..];
+    println!();
+    let mut previous: Option<u32> = None;
+    for entry in recent {
+        let change = match previous {
+            Some(before) if entry.score > before => format!("+{}", entry.score - before).color(colors::SUCCESS),
+            Some(before) if entry.score < before => format!("-{}", before - entry.score).color(colors::HIGH_CONFIDENCE),
+            Some(_) => "=".dimmed(),
+            None => "".normal(),
+        };
+        println!("   {} {:>3}/100 {:<20} {}{}",
+            entry.scored_at.format("%Y-%m-%d %H:%M"),
+            entry.score,
+            "█".repeat(entry.score as usize / 5).color(colors::SUCCESS),
+            change,
+            if entry.estimate { " (estimate)".dimmed() } else { "".normal() });
+        previous = Some(entry.score);
+    }
+
+    let first = recent[0];
+    let last = recent[recent.len() - 1];
+    println!();
+    if recent.len() == 1 {
+        println!("{} One score so far - check back after your next cleanup", "🌱".green());
+    } else if last.score > first.score {
+        println!("{} Up {} points since {} - keep it going!",
+            "🎉".green(), last.score - first.score, first.scored_at.format("%Y-%m-%d"));
+    } else if last.score < first.score {
+        println!("{} Down {} points since {} - {} shows where they went",
+            "💡".cyan(), first.score - last.score, first.scored_at.format("%Y-%m-%d"),
+            format!("cleancrush score {} --explain", path.display()).bold());
+    } else {
+        println!("{} Holding steady at {}/100", "✨".green(), last.score);
+    }
+
+    if !last.breakdown.is_empty() {
+        println!();
+        println!("{} Latest breakdown:", "📊".cyan());
+        println!("{}", last.breakdown);
+    }
+}
@@ -0,0 +1,20 @@
+//! Shared setup for tests that touch CleanCrush's own files
+
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// Point the home directory and config at a throwaway folder for the whole
+/// test run. Hold the guard while using them so tests don't interleave.
+pub fn isolated_home() -> (MutexGuard<'static, ()>, PathBuf) {
+    static HOME: OnceLock<tempfile::TempDir> = OnceLock::new();
+    static LOCK: Mutex<()> = Mutex::new(());
+    
+    let home = HOME.get_or_init(|| {
+        let dir = tempfile::tempdir().expect("Failed to create test home");
+        std::env::set_var("HOME", dir.path());
+        std::env::set_var(crate::config::CONFIG_ENV, dir.path().join(".cleancrush.json"));
+        dir
+    });
+    let guard = LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    (guard, home.path().to_path_buf())
+}
@@ -0,0 +1,212 @@
+use std::fs;
+use std::path::PathBuf;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use colored::*;
+use anyhow::{Result, Context};
+use crate::colors;
+use crate::cli::format_size;
+use crate::config::Config;
+
+const TRASH_MANIFEST: &str = ".cleancrush_trash.json";
+
+/// How long trashed files are listed (matches the promised restore window)
+pub const RESTORE_WINDOW_DAYS: i64 = 30;
+
+/// A file CleanCrush sent to the Recycle Bin/Trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedFile {
+    pub original_path: PathBuf,
+    pub size_bytes: u64,
+    pub trashed_at: DateTime<Utc>,
+}
+
+/// Our own record of trashed files, kept whether or not the OS trash
+/// can be listed or restored from
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrashManifest {
+    /// Newest first
+    pub files: Vec<TrashedFile>,
+}
+
+/// What happened when restoring one manifest entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestoreOutcome {
+    Restored,
+    /// Something already exists at the original path
+    Occupied,
+    /// Not found in the OS trash (probably emptied)
+    NotInTrash,
+    /// The OS trash can't be restored from on this platform
+    #[allow(dead_code)]
+    Unsupported,
+    Failed(String),
+}
+
+impl TrashManifest {
+    /// Get the path to the manifest file
+    pub fn path() -> Result<PathBuf> {
+        Config::state_path(TRASH_MANIFEST)
+    }
+
+    /// Load the manifest, dropping entries older than the restore window
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut manifest: Self = serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        manifest.prune();
+        Ok(manifest)
+    }
+
+    /// Save the manifest (written to a temp file first so it's never half-written)
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let tmp_path = path.with_extension("json.tmp");
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(&tmp_path, data)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Drop entries older than the restore window, returning how many were removed
+    pub fn prune(&mut self) -> usize {
+        let cutoff = Utc::now() - Duration::days(RESTORE_WINDOW_DAYS);
+        let before = self.files.len();
+        self.files.retain(|f| f.trashed_at >= cutoff);
+        before - self.files.len()
+    }
+
+    /// Record files that were just trashed
+    pub fn record(trashed: &[(PathBuf, u64)]) -> Result<()> {
+        if trashed.is_empty() {
+            return Ok(());
+        }
+
+        let mut manifest = Self::load()?;
+        let now = Utc::now();
+        for (path, size) in trashed {
+            manifest.files.push(TrashedFile {
+                original_path: path.clone(),
+                size_bytes: *size,
+                trashed_at: now,
+            });
+        }
+        manifest.files.sort_by_key(|f| std::cmp::Reverse(f.trashed_at));
+        manifest.save()
+    }
+
+    /// Restore the newest trashed copy of each path, dropping entries that are
+    /// done with (restored, or no longer in the trash). Paths with no entry
+    /// come back as `NotInTrash`. Call `save` afterwards.
+    pub fn restore_paths(&mut self, paths: &[PathBuf]) -> Vec<(PathBuf, RestoreOutcome)> {
+        let mut outcomes = Vec::new();
+        
+        for path in paths {
+            // Newest first, so the first match is the latest copy
+            let Some(i) = self.files.iter().position(|f| &f.original_path == path) else {
+                outcomes.push((path.clone(), RestoreOutcome::NotInTrash));
+                continue;
+            };
+            
+            let outcome = restore(&self.files[i]);
+            if matches!(outcome, RestoreOutcome::Restored | RestoreOutcome::NotInTrash) {
+                self.files.remove(i);
+            }
+            outcomes.push((path.clone(), outcome));
+        }
+        
+        outcomes
+    }
+    
+    /// Print the trashed files with their restore numbers
+    pub fn print_list(&self) {
+        println!();
+        println!("{}", "🗑️ TRASHED FILES".bold().color(colors::HEADER));
+        println!("{}", "─".repeat(50).color(colors::PATH));
+
+        if self.files.is_empty() {
+            println!("{} Nothing trashed in the last {} days", "📭".cyan(), RESTORE_WINDOW_DAYS);
+            return;
+        }
+
+        for (i, file) in self.files.iter().enumerate() {
+            let days_left = RESTORE_WINDOW_DAYS - (Utc::now() - file.trashed_at).num_days();
+            println!("{:3}. {} ({}, trashed {}, {} days left)",
+                i + 1,
+                file.original_path.display().to_string().color(colors::PATH),
+                format_size(file.size_bytes),
+                file.trashed_at.format("%Y-%m-%d"),
+                days_left.max(0));
+        }
+
+        if !os_restore_supported() {
+            println!();
+            println!("{} Restoring from the trash isn't supported here - use your file manager", "💡".cyan());
+        }
+    }
+}
+
+/// Whether the OS trash can be listed and restored from on this platform
+pub fn os_restore_supported() -> bool {
+    cfg!(any(
+        target_os = "windows",
+        all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+    ))
+}
+
+/// Restore a trashed file to its original location using the OS trash
+#[cfg(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+))]
+pub fn restore(entry: &TrashedFile) -> RestoreOutcome {
+    // Compare paths, tolerating symlinked parents
+    fn same_path(a: &std::path::Path, b: &std::path::Path) -> bool {
+        if a == b {
+            return true;
+        }
+        match (a.parent().and_then(|p| p.canonicalize().ok()), b.parent().and_then(|p| p.canonicalize().ok())) {
+            (Some(pa), Some(pb)) => pa == pb && a.file_name() == b.file_name(),
+            _ => false,
+        }
+    }
+
+    if entry.original_path.exists() {
+        return RestoreOutcome::Occupied;
+    }
+
+    let items = match trash::os_limited::list() {
+        Ok(items) => items,
+        Err(e) => return RestoreOutcome::Failed(e.to_string()),
+    };
+
+    // If the same path was trashed more than once, take the newest copy
+    let item = items.into_iter()
+        .filter(|item| same_path(&item.original_path(), &entry.original_path))
+        .max_by_key(|item| item.time_deleted);
+
+    match item {
+        Some(item) => match trash::os_limited::restore_all([item]) {
+            Ok(()) => RestoreOutcome::Restored,
+            Err(e) => RestoreOutcome::Failed(e.to_string()),
+        },
+        None => RestoreOutcome::NotInTrash,
+    }
+}
+
+/// Restore a trashed file to its original location using the OS trash
+#[cfg(not(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+)))]
+pub fn restore(_entry: &TrashedFile) -> RestoreOutcome {
+    RestoreOutcome::Unsupported
+}